@@ -24,10 +24,37 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             cc_session_manager::commands::scan_projects,
             cc_session_manager::commands::get_project_sessions,
             cc_session_manager::commands::delete_sessions,
+            cc_session_manager::commands::get_session_chains,
+            cc_session_manager::commands::export_sessions,
             cc_session_manager::commands::delete_project,
             cc_session_manager::commands::filter_sessions_by_age,
             cc_session_manager::commands::delete_old_sessions,
             cc_session_manager::commands::get_session_content,
+            cc_session_manager::commands::get_conversation_tree,
+            cc_session_manager::commands::diff_sessions,
+            cc_session_manager::commands::get_resume_command,
+            cc_session_manager::commands::filter_artifacts_by_age,
+            cc_session_manager::commands::delete_artifacts,
+            cc_session_manager::commands::delete_old_artifacts,
+            cc_session_manager::commands::find_orphaned_artifacts,
+            cc_session_manager::commands::delete_orphaned_artifacts,
+            cc_session_manager::commands::find_duplicate_sessions,
+            cc_session_manager::commands::check_integrity,
+            cc_session_manager::commands::repair_sessions,
+            cc_session_manager::commands::run_doctor,
+            cc_session_manager::commands::scan_secrets,
+            cc_session_manager::commands::redact_secrets,
+            cc_session_manager::commands::get_activity,
+            cc_session_manager::commands::get_project_detail,
+            cc_session_manager::commands::get_branch_groups,
+            cc_session_manager::commands::get_store_ranking,
+            cc_session_manager::commands::get_tool_usage,
+            cc_session_manager::commands::archive_sessions,
+            cc_session_manager::commands::move_sessions,
+            cc_session_manager::commands::empty_trash,
+            cc_session_manager::commands::preview_relocation,
+            cc_session_manager::commands::apply_relocation,
+            cc_session_manager::commands::find_stale_branch_sessions,
             find_claude_directory,
         ])
         .run(tauri::generate_context!())
@@ -0,0 +1,106 @@
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, Timelike};
+use std::collections::{HashMap, HashSet};
+
+/// Number of days covered by the calendar heatmap, ending today.
+pub const CALENDAR_DAYS: i64 = 365;
+
+/// Messages and sessions on one calendar day.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DayActivity {
+    pub date: NaiveDate,
+    /// Sessions with at least one message on this day.
+    pub sessions: usize,
+    pub messages: usize,
+}
+
+/// When messages were written, in local time.
+#[derive(Debug, Clone)]
+pub struct Activity {
+    /// One entry per day of the past year, oldest first, including idle days.
+    pub days: Vec<DayActivity>,
+    /// Messages per weekday (Monday first) and hour of day, over all time.
+    pub hourly: [[usize; 24]; 7],
+    /// Messages in the past year.
+    pub total_messages: usize,
+}
+
+impl Activity {
+    /// Builds the heatmaps from the message timestamps of each session.
+    pub fn from_sessions(sessions: &[Vec<DateTime<Local>>], today: NaiveDate) -> Self {
+        let first_day = today - Duration::days(CALENDAR_DAYS - 1);
+        let mut messages: HashMap<NaiveDate, usize> = HashMap::new();
+        let mut session_days: HashMap<NaiveDate, usize> = HashMap::new();
+        let mut hourly = [[0; 24]; 7];
+
+        for timestamps in sessions {
+            let mut days = HashSet::new();
+            for timestamp in timestamps {
+                let date = timestamp.date_naive();
+                hourly[timestamp.weekday().num_days_from_monday() as usize][timestamp.hour() as usize] += 1;
+                if date >= first_day && date <= today {
+                    *messages.entry(date).or_default() += 1;
+                    days.insert(date);
+                }
+            }
+            for date in days {
+                *session_days.entry(date).or_default() += 1;
+            }
+        }
+
+        let days: Vec<DayActivity> = (0..CALENDAR_DAYS)
+            .map(|offset| {
+                let date = first_day + Duration::days(offset);
+                DayActivity {
+                    date,
+                    sessions: session_days.get(&date).copied().unwrap_or(0),
+                    messages: messages.get(&date).copied().unwrap_or(0),
+                }
+            })
+            .collect();
+
+        Self {
+            total_messages: days.iter().map(|d| d.messages).sum(),
+            days,
+            hourly,
+        }
+    }
+
+    /// The day of the past year with the most messages, if any.
+    pub fn busiest_day(&self) -> Option<&DayActivity> {
+        self.days.iter().filter(|d| d.messages > 0).max_by_key(|d| d.messages)
+    }
+
+    pub fn active_days(&self) -> usize {
+        self.days.iter().filter(|d| d.messages > 0).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_calendar_and_hourly_counts() {
+        let at = |y, m, d, h| Local.with_ymd_and_hms(y, m, d, h, 15, 0).unwrap();
+        let today = NaiveDate::from_ymd_opt(2024, 6, 5).unwrap();
+        let sessions = vec![
+            // Wednesday 2024-06-05, twice at 9:00, plus a message too old for the calendar
+            vec![at(2024, 6, 5, 9), at(2024, 6, 5, 9), at(2023, 1, 2, 22)],
+            vec![at(2024, 6, 5, 14), at(2024, 6, 3, 14)],
+        ];
+
+        let activity = Activity::from_sessions(&sessions, today);
+
+        assert_eq!(activity.days.len(), CALENDAR_DAYS as usize);
+        let last = activity.days.last().unwrap();
+        assert_eq!((last.date, last.sessions, last.messages), (today, 2, 3));
+        assert_eq!(activity.active_days(), 2);
+        assert_eq!(activity.busiest_day().unwrap().date, today);
+        assert_eq!(activity.hourly[2][9], 2);
+        assert_eq!(activity.hourly[0][14], 1);
+        assert_eq!(activity.hourly[0][22], 1);
+        // The 2023 message counts towards the hourly matrix only
+        assert_eq!(activity.total_messages, 4);
+    }
+}
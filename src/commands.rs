@@ -1,7 +1,25 @@
-use crate::models::Session;
+use crate::activity::Activity;
+use crate::anonymize::Replacement;
+use crate::conversation::ConversationTree;
+use crate::diff::{DiffMessage, DiffRow, SessionDiff};
+use crate::export::{self, ExportFormat};
+use crate::models::{
+    Artifact, ArtifactCategory, DoctorFinding, DuplicateSession, IntegrityIssue, MoveOutcome, Project,
+    ProjectDetail, ProjectDuplicates, ProjectSession, RedactionOutcome, RelocationReport, RepairOutcome, ResumeCommand,
+    SecretFinding, Session, SessionChain, SessionIntegrity, SessionSecrets, StaleBranchReport, TokenUsage, ToolCounts, ToolUsage,
+};
+use crate::config::SecretPatternConfig;
+use crate::listing::group_by_branch;
+use crate::ranking::{RankedSession, StoreRanking};
+use crate::secrets::SecretScanner;
+use crate::tools::{SessionToolUsage, ToolReport};
+use crate::transcript;
+use crate::utils::format_size;
 use crate::SessionManager;
 use anyhow::{anyhow, Result};
-use serde::Serialize;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 #[derive(Serialize)]
@@ -20,6 +38,52 @@ pub struct SessionDto {
     pub size: String,
     pub age_days: u64,
     pub content_preview: Option<String>,
+    pub linked_size: Option<String>,
+    pub subagents: Vec<SessionDto>,
+    pub session_id: Option<String>,
+    pub cwd: Option<String>,
+    pub git_branch: Option<String>,
+    pub version: Option<String>,
+    pub first_timestamp: Option<String>,
+    pub last_timestamp: Option<String>,
+    pub user_messages: usize,
+    pub assistant_messages: usize,
+    pub tool_messages: usize,
+    pub models: Vec<String>,
+    /// Tool calls made in this transcript, not counting sub-agents.
+    pub tool_calls: usize,
+    pub tool_errors: usize,
+}
+
+impl From<Session> for SessionDto {
+    fn from(s: Session) -> Self {
+        let size = s.format_size();
+        let age_days = s.get_age_days();
+        let linked_size = (s.linked_size > 0).then(|| format_size(s.linked_size));
+        let metadata = s.metadata;
+        let tools = metadata.tools.total();
+        SessionDto {
+            name: s.name,
+            path: s.path.to_string_lossy().to_string(),
+            size,
+            age_days,
+            content_preview: s.content_preview,
+            linked_size,
+            subagents: s.subagents.into_iter().map(SessionDto::from).collect(),
+            session_id: metadata.session_id,
+            cwd: metadata.cwd,
+            git_branch: metadata.git_branch,
+            version: metadata.version,
+            first_timestamp: metadata.first_timestamp.map(|t| t.to_rfc3339()),
+            last_timestamp: metadata.last_timestamp.map(|t| t.to_rfc3339()),
+            user_messages: metadata.user_messages,
+            assistant_messages: metadata.assistant_messages,
+            tool_messages: metadata.tool_messages,
+            models: metadata.models,
+            tool_calls: tools.calls,
+            tool_errors: tools.errors,
+        }
+    }
 }
 
 #[derive(Serialize)]
@@ -27,125 +91,1243 @@ pub struct StatisticsDto {
     pub total_projects: usize,
     pub total_sessions: usize,
     pub total_size: String,
+    pub artifacts: Vec<ArtifactUsageDto>,
+    pub total_artifacts_size: String,
+    pub age_buckets: Vec<AgeBucketDto>,
+    pub oldest_session: Option<ProjectSessionDto>,
+    pub newest_session: Option<ProjectSessionDto>,
+    pub empty_projects: usize,
+    pub average_session_size: String,
+    pub median_session_size: String,
+    pub largest_project: Option<ProjectSummaryDto>,
+}
+
+#[derive(Serialize)]
+pub struct AgeBucketDto {
+    pub label: String,
+    pub count: usize,
+    pub size: String,
+}
+
+#[derive(Serialize)]
+pub struct ProjectSessionDto {
+    pub project_name: String,
+    pub session: SessionDto,
+}
+
+impl From<ProjectSession> for ProjectSessionDto {
+    fn from(s: ProjectSession) -> Self {
+        ProjectSessionDto {
+            project_name: s.project_name,
+            session: SessionDto::from(s.session),
+        }
+    }
+}
+
+/// A project without its session list.
+#[derive(Serialize)]
+pub struct ProjectSummaryDto {
+    pub name: String,
+    pub path: String,
+    pub session_count: usize,
+    pub total_size: String,
+}
+
+impl From<&Project> for ProjectSummaryDto {
+    fn from(p: &Project) -> Self {
+        ProjectSummaryDto {
+            name: p.name.clone(),
+            path: p.path.to_string_lossy().to_string(),
+            session_count: p.sessions.len(),
+            total_size: p.format_size(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct RankedSessionDto {
+    pub project_name: String,
+    pub session: SessionDto,
+    pub total_size: String,
+    pub project_median: String,
+    /// How many times the project's median this session is, if there is one.
+    pub median_ratio: Option<f64>,
+    pub is_outlier: bool,
+    pub growth_per_day: Option<String>,
+}
+
+impl From<RankedSession> for RankedSessionDto {
+    fn from(r: RankedSession) -> Self {
+        RankedSessionDto {
+            total_size: format_size(r.session.total_size()),
+            project_median: format_size(r.project_median),
+            median_ratio: r.median_ratio(),
+            growth_per_day: r.growth_per_day.map(format_size),
+            project_name: r.project_name,
+            is_outlier: r.is_outlier,
+            session: SessionDto::from(r.session),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct StoreRankingDto {
+    pub largest_sessions: Vec<RankedSessionDto>,
+    pub fastest_growing: Vec<RankedSessionDto>,
+    pub largest_projects: Vec<ProjectSummaryDto>,
+}
+
+impl From<StoreRanking> for StoreRankingDto {
+    fn from(r: StoreRanking) -> Self {
+        StoreRankingDto {
+            largest_sessions: r.largest_sessions.into_iter().map(RankedSessionDto::from).collect(),
+            fastest_growing: r.fastest_growing.into_iter().map(RankedSessionDto::from).collect(),
+            largest_projects: r.largest_projects.iter().map(ProjectSummaryDto::from).collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ToolCountsDto {
+    /// Tool name, or MCP server name in `mcp_servers`.
+    pub name: String,
+    pub calls: usize,
+    pub errors: usize,
+    pub error_rate: f64,
+}
+
+impl ToolCountsDto {
+    fn new(name: &str, counts: ToolCounts) -> Self {
+        ToolCountsDto {
+            name: name.to_string(),
+            calls: counts.calls,
+            errors: counts.errors,
+            error_rate: counts.error_rate(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ToolUsageDto {
+    pub calls: usize,
+    pub errors: usize,
+    pub error_rate: f64,
+    /// Most used first.
+    pub tools: Vec<ToolCountsDto>,
+    pub mcp_servers: Vec<ToolCountsDto>,
+}
+
+impl From<&ToolUsage> for ToolUsageDto {
+    fn from(usage: &ToolUsage) -> Self {
+        let total = usage.total();
+        ToolUsageDto {
+            calls: total.calls,
+            errors: total.errors,
+            error_rate: total.error_rate(),
+            tools: usage.ranked().into_iter().map(|(name, counts)| ToolCountsDto::new(name, counts)).collect(),
+            mcp_servers: usage
+                .mcp_servers()
+                .into_iter()
+                .map(|(name, counts)| ToolCountsDto::new(name, counts))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ProjectToolUsageDto {
+    pub project_name: String,
+    pub project_path: String,
+    pub usage: ToolUsageDto,
+}
+
+#[derive(Serialize)]
+pub struct SessionToolUsageDto {
+    pub project_name: String,
+    pub session: SessionDto,
+    /// Includes the session's sub-agents.
+    pub usage: ToolUsageDto,
+}
+
+impl From<&SessionToolUsage> for SessionToolUsageDto {
+    fn from(s: &SessionToolUsage) -> Self {
+        SessionToolUsageDto {
+            project_name: s.project_name.clone(),
+            session: SessionDto::from(s.session.clone()),
+            usage: ToolUsageDto::from(&s.usage),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ToolReportDto {
+    pub overall: ToolUsageDto,
+    /// Most tool calls first.
+    pub projects: Vec<ProjectToolUsageDto>,
+    /// Most tool calls first.
+    pub sessions: Vec<SessionToolUsageDto>,
+    /// Most failed tool calls first.
+    pub failing_sessions: Vec<SessionToolUsageDto>,
+}
+
+impl ToolReportDto {
+    /// Keeps the top `limit` projects and sessions of each list.
+    fn new(report: &ToolReport, limit: usize) -> Self {
+        ToolReportDto {
+            overall: ToolUsageDto::from(&report.overall),
+            projects: report
+                .projects
+                .iter()
+                .take(limit)
+                .map(|p| ProjectToolUsageDto {
+                    project_name: p.project_name.clone(),
+                    project_path: p.project_path.to_string_lossy().to_string(),
+                    usage: ToolUsageDto::from(&p.usage),
+                })
+                .collect(),
+            sessions: report.sessions.iter().take(limit).map(SessionToolUsageDto::from).collect(),
+            failing_sessions: report
+                .sessions_by_errors()
+                .into_iter()
+                .take(limit)
+                .map(SessionToolUsageDto::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct TokenUsageDto {
+    pub input: u64,
+    pub output: u64,
+    pub cache_creation: u64,
+    pub cache_read: u64,
+}
+
+impl From<TokenUsage> for TokenUsageDto {
+    fn from(t: TokenUsage) -> Self {
+        TokenUsageDto {
+            input: t.input,
+            output: t.output,
+            cache_creation: t.cache_creation,
+            cache_read: t.cache_read,
+        }
+    }
+}
+
+/// A name with how often it was seen, e.g. a tool and its call count.
+#[derive(Serialize)]
+pub struct NamedCountDto {
+    pub name: String,
+    pub count: usize,
+}
+
+#[derive(Serialize)]
+pub struct ProjectDetailDto {
+    pub project: ProjectSummaryDto,
+    pub cwd: Option<String>,
+    pub first_activity: Option<String>,
+    pub last_activity: Option<String>,
+    pub average_session_size: String,
+    pub message_count: usize,
+    pub tokens: TokenUsageDto,
+    pub top_tools: Vec<NamedCountDto>,
+    /// Branches with the number of sessions that ran on them.
+    pub git_branches: Vec<NamedCountDto>,
+    pub largest_sessions: Vec<SessionDto>,
+}
+
+impl From<ProjectDetail> for ProjectDetailDto {
+    fn from(d: ProjectDetail) -> Self {
+        let named = |counts: Vec<(String, usize)>| {
+            counts
+                .into_iter()
+                .map(|(name, count)| NamedCountDto { name, count })
+                .collect()
+        };
+        ProjectDetailDto {
+            project: ProjectSummaryDto::from(&d.project),
+            average_session_size: format_size(d.average_session_size()),
+            cwd: d.cwd,
+            first_activity: d.first_activity.map(|t| t.to_rfc3339()),
+            last_activity: d.last_activity.map(|t| t.to_rfc3339()),
+            message_count: d.message_count,
+            tokens: TokenUsageDto::from(d.tokens),
+            top_tools: named(d.top_tools),
+            git_branches: named(d.git_branches),
+            largest_sessions: d.largest_sessions.into_iter().map(SessionDto::from).collect(),
+        }
+    }
+}
+
+/// Heatmap series: one point per day of the past year and one per weekday
+/// (0 = Monday) and hour.
+#[derive(Serialize)]
+pub struct ActivityDto {
+    pub days: Vec<DayActivityDto>,
+    pub hourly: Vec<HourlyActivityDto>,
+    pub total_messages: usize,
+    pub active_days: usize,
+}
+
+#[derive(Serialize)]
+pub struct DayActivityDto {
+    pub date: String,
+    pub sessions: usize,
+    pub messages: usize,
+}
+
+#[derive(Serialize)]
+pub struct HourlyActivityDto {
+    pub weekday: usize,
+    pub hour: usize,
+    pub messages: usize,
+}
+
+impl From<Activity> for ActivityDto {
+    fn from(a: Activity) -> Self {
+        let active_days = a.active_days();
+        ActivityDto {
+            days: a
+                .days
+                .iter()
+                .map(|d| DayActivityDto {
+                    date: d.date.format("%Y-%m-%d").to_string(),
+                    sessions: d.sessions,
+                    messages: d.messages,
+                })
+                .collect(),
+            hourly: a
+                .hourly
+                .iter()
+                .enumerate()
+                .flat_map(|(weekday, hours)| {
+                    hours.iter().enumerate().map(move |(hour, &messages)| HourlyActivityDto {
+                        weekday,
+                        hour,
+                        messages,
+                    })
+                })
+                .collect(),
+            total_messages: a.total_messages,
+            active_days,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ArtifactUsageDto {
+    pub category: String,
+    pub label: String,
+    pub count: usize,
+    pub size: String,
+}
+
+#[derive(Serialize)]
+pub struct ArtifactDto {
+    pub category: String,
+    pub name: String,
+    pub path: String,
+    pub size: String,
+    pub age_days: u64,
+}
+
+impl From<Artifact> for ArtifactDto {
+    fn from(a: Artifact) -> Self {
+        let size = a.format_size();
+        let age_days = a.get_age_days();
+        ArtifactDto {
+            category: a.category.dir_name().to_string(),
+            name: a.name,
+            path: a.path.to_string_lossy().to_string(),
+            size,
+            age_days,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct DuplicateSessionDto {
+    pub redundant: SessionDto,
+    pub kept: SessionDto,
+    pub kind: String,
+    pub shared_entries: usize,
+}
+
+impl From<DuplicateSession> for DuplicateSessionDto {
+    fn from(d: DuplicateSession) -> Self {
+        DuplicateSessionDto {
+            redundant: SessionDto::from(d.redundant),
+            kept: SessionDto::from(d.kept),
+            kind: d.kind.label().to_string(),
+            shared_entries: d.shared_entries,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ProjectDuplicatesDto {
+    pub project_name: String,
+    pub project_path: String,
+    pub redundant_size: String,
+    pub duplicates: Vec<DuplicateSessionDto>,
+}
+
+impl From<ProjectDuplicates> for ProjectDuplicatesDto {
+    fn from(p: ProjectDuplicates) -> Self {
+        let redundant_size = p.format_redundant_bytes();
+        ProjectDuplicatesDto {
+            project_name: p.project_name,
+            project_path: p.project_path.to_string_lossy().to_string(),
+            redundant_size,
+            duplicates: p.duplicates.into_iter().map(DuplicateSessionDto::from).collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct SessionChainDto {
+    /// Members from the oldest to the newest file.
+    pub sessions: Vec<SessionDto>,
+    pub total_size: String,
+    pub duration: String,
+    pub started: Option<String>,
+    pub ended: Option<String>,
+}
+
+impl From<SessionChain> for SessionChainDto {
+    fn from(c: SessionChain) -> Self {
+        SessionChainDto {
+            total_size: c.format_size(),
+            duration: c.format_duration(),
+            started: c.started.map(|t| t.to_rfc3339()),
+            ended: c.ended.map(|t| t.to_rfc3339()),
+            sessions: c.sessions.into_iter().map(SessionDto::from).collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct IntegrityIssueDto {
+    pub kind: String,
+    pub description: String,
+    pub repairable: bool,
+}
+
+impl From<IntegrityIssue> for IntegrityIssueDto {
+    fn from(issue: IntegrityIssue) -> Self {
+        let kind = match issue {
+            IntegrityIssue::InvalidLine { .. } => "invalid_line",
+            IntegrityIssue::TruncatedTail { .. } => "truncated_tail",
+            IntegrityIssue::DuplicateUuid { .. } => "duplicate_uuid",
+            IntegrityIssue::BrokenParent { .. } => "broken_parent",
+        };
+        IntegrityIssueDto {
+            kind: kind.to_string(),
+            description: issue.describe(),
+            repairable: issue.is_repairable(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct SessionIntegrityDto {
+    pub project_name: String,
+    pub session: SessionDto,
+    pub valid_entries: usize,
+    pub repairable: bool,
+    pub issues: Vec<IntegrityIssueDto>,
+}
+
+impl From<SessionIntegrity> for SessionIntegrityDto {
+    fn from(r: SessionIntegrity) -> Self {
+        let repairable = r.is_repairable();
+        SessionIntegrityDto {
+            project_name: r.project_name,
+            session: SessionDto::from(r.session),
+            valid_entries: r.valid_entries,
+            repairable,
+            issues: r.issues.into_iter().map(IntegrityIssueDto::from).collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct RepairOutcomeDto {
+    pub path: String,
+    pub backup_path: Option<String>,
+    pub removed_lines: usize,
+}
+
+impl From<RepairOutcome> for RepairOutcomeDto {
+    fn from(o: RepairOutcome) -> Self {
+        RepairOutcomeDto {
+            path: o.path.to_string_lossy().to_string(),
+            backup_path: o.backup_path.map(|p| p.to_string_lossy().to_string()),
+            removed_lines: o.removed_lines,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct MoveOutcomeDto {
+    pub path: String,
+    pub target_path: String,
+    pub trash_path: String,
+    pub rewritten_cwd: usize,
+}
+
+impl From<MoveOutcome> for MoveOutcomeDto {
+    fn from(o: MoveOutcome) -> Self {
+        MoveOutcomeDto {
+            path: o.path.to_string_lossy().to_string(),
+            target_path: o.target_path.to_string_lossy().to_string(),
+            trash_path: o.trash_path.to_string_lossy().to_string(),
+            rewritten_cwd: o.rewritten_cwd,
+        }
+    }
+}
+
+/// The result of moving one session: its outcome, or why it stayed put.
+#[derive(Serialize)]
+pub struct MoveResultDto {
+    pub path: String,
+    pub name: String,
+    pub outcome: Option<MoveOutcomeDto>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct StaleBranchSessionDto {
+    pub project_name: String,
+    pub branch: String,
+    pub repository: String,
+    pub session: SessionDto,
+}
+
+#[derive(Serialize)]
+pub struct StaleBranchReportDto {
+    pub sessions: Vec<StaleBranchSessionDto>,
+    pub repositories: usize,
+    pub unchecked: usize,
+    pub total_size: String,
+}
+
+impl From<StaleBranchReport> for StaleBranchReportDto {
+    fn from(r: StaleBranchReport) -> Self {
+        let total_size = format_size(r.total_size());
+        StaleBranchReportDto {
+            sessions: r
+                .sessions
+                .into_iter()
+                .map(|s| StaleBranchSessionDto {
+                    project_name: s.project_name,
+                    branch: s.branch,
+                    repository: s.repository.to_string_lossy().to_string(),
+                    session: SessionDto::from(s.session),
+                })
+                .collect(),
+            repositories: r.repositories,
+            unchecked: r.unchecked,
+            total_size,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct BranchGroupDto {
+    pub branch: Option<String>,
+    pub session_paths: Vec<String>,
+    pub size: String,
+    pub last_activity: String,
+}
+
+#[derive(Serialize)]
+pub struct ResumeCommandDto {
+    pub session_id: String,
+    pub cwd: String,
+    pub program: String,
+    pub shell_line: String,
+}
+
+impl From<ResumeCommand> for ResumeCommandDto {
+    fn from(c: ResumeCommand) -> Self {
+        ResumeCommandDto {
+            shell_line: c.shell_line(),
+            session_id: c.session_id,
+            cwd: c.cwd.to_string_lossy().to_string(),
+            program: c.program,
+        }
+    }
+}
+
+/// Sent back unchanged to `apply_relocation` to carry out a preview.
+#[derive(Serialize, Deserialize)]
+pub struct RelocationReportDto {
+    pub old_cwd: String,
+    pub new_cwd: String,
+    pub source: String,
+    pub target: String,
+    pub merge: bool,
+    pub sessions: usize,
+    pub transcripts: usize,
+    pub rewritten_entries: usize,
+    pub conflicts: Vec<String>,
+    pub fingerprint: String,
+    pub dry_run: bool,
+}
+
+impl From<RelocationReport> for RelocationReportDto {
+    fn from(r: RelocationReport) -> Self {
+        RelocationReportDto {
+            old_cwd: r.old_cwd,
+            new_cwd: r.new_cwd,
+            source: r.source.to_string_lossy().to_string(),
+            target: r.target.to_string_lossy().to_string(),
+            merge: r.merge,
+            sessions: r.sessions,
+            transcripts: r.transcripts,
+            rewritten_entries: r.rewritten_entries,
+            conflicts: r.conflicts.iter().map(|p| p.to_string_lossy().to_string()).collect(),
+            fingerprint: r.fingerprint,
+            dry_run: r.dry_run,
+        }
+    }
+}
+
+impl From<RelocationReportDto> for RelocationReport {
+    fn from(r: RelocationReportDto) -> Self {
+        RelocationReport {
+            old_cwd: r.old_cwd,
+            new_cwd: r.new_cwd,
+            source: PathBuf::from(r.source),
+            target: PathBuf::from(r.target),
+            merge: r.merge,
+            sessions: r.sessions,
+            transcripts: r.transcripts,
+            rewritten_entries: r.rewritten_entries,
+            conflicts: r.conflicts.into_iter().map(PathBuf::from).collect(),
+            fingerprint: r.fingerprint,
+            dry_run: r.dry_run,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ReplacementDto {
+    pub placeholder: String,
+    pub category: String,
+    pub original: String,
+    pub occurrences: usize,
+}
+
+impl From<&Replacement> for ReplacementDto {
+    fn from(r: &Replacement) -> Self {
+        ReplacementDto {
+            placeholder: r.placeholder.clone(),
+            category: r.category.clone(),
+            original: r.original.clone(),
+            occurrences: r.occurrences,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ExportResultDto {
+    pub path: String,
+    /// Values replaced by an anonymized export; empty otherwise.
+    pub replacements: Vec<ReplacementDto>,
+}
+
+#[derive(Serialize)]
+pub struct SecretFindingDto {
+    pub line: usize,
+    pub column: usize,
+    pub entry_uuid: Option<String>,
+    pub entry_type: String,
+    pub detector: String,
+    pub masked: String,
+}
+
+impl From<SecretFinding> for SecretFindingDto {
+    fn from(f: SecretFinding) -> Self {
+        SecretFindingDto {
+            line: f.line,
+            column: f.column,
+            entry_uuid: f.entry_uuid,
+            entry_type: f.entry_type,
+            detector: f.detector,
+            masked: f.masked,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct SessionSecretsDto {
+    pub project_name: String,
+    pub session: SessionDto,
+    pub findings: Vec<SecretFindingDto>,
+}
+
+impl From<SessionSecrets> for SessionSecretsDto {
+    fn from(r: SessionSecrets) -> Self {
+        SessionSecretsDto {
+            project_name: r.project_name,
+            session: SessionDto::from(r.session),
+            findings: r.findings.into_iter().map(SecretFindingDto::from).collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct RedactionOutcomeDto {
+    pub path: String,
+    pub backup_path: Option<String>,
+    pub redacted: usize,
+}
+
+impl From<RedactionOutcome> for RedactionOutcomeDto {
+    fn from(o: RedactionOutcome) -> Self {
+        RedactionOutcomeDto {
+            path: o.path.to_string_lossy().to_string(),
+            backup_path: o.backup_path.map(|p| p.to_string_lossy().to_string()),
+            redacted: o.redacted,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct DoctorFindingDto {
+    pub severity: String,
+    pub check: String,
+    pub message: String,
+    pub path: Option<String>,
+    pub suggestion: String,
+}
+
+impl From<DoctorFinding> for DoctorFindingDto {
+    fn from(f: DoctorFinding) -> Self {
+        DoctorFindingDto {
+            severity: f.severity.label().to_string(),
+            check: f.check.to_string(),
+            message: f.message,
+            path: f.path.map(|p| p.to_string_lossy().to_string()),
+            suggestion: f.suggestion,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ConversationEntryDto {
+    pub uuid: Option<String>,
+    pub parent_uuid: Option<String>,
+    pub line_number: usize,
+    pub role: String,
+    pub text: String,
+    pub is_sidechain: bool,
+}
+
+impl From<&transcript::TranscriptEntry> for ConversationEntryDto {
+    fn from(e: &transcript::TranscriptEntry) -> Self {
+        ConversationEntryDto {
+            uuid: e.uuid.clone(),
+            parent_uuid: e.parent_uuid.clone(),
+            line_number: e.line_number,
+            role: transcript::role_label(&e.value).to_string(),
+            text: transcript::extract_all_text(&e.value),
+            is_sidechain: e.is_sidechain,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct AbandonedBranchDto {
+    /// Line of the entry the branch forked from; `None` for a detached root.
+    pub fork_line: Option<usize>,
+    pub entries: Vec<ConversationEntryDto>,
+}
+
+#[derive(Serialize)]
+pub struct ConversationTreeDto {
+    pub total_entries: usize,
+    pub branch_points: usize,
+    pub active: Vec<ConversationEntryDto>,
+    pub abandoned_branches: Vec<AbandonedBranchDto>,
+}
+
+impl From<&ConversationTree> for ConversationTreeDto {
+    fn from(tree: &ConversationTree) -> Self {
+        let entry = |i: usize| ConversationEntryDto::from(&tree.nodes[i].entry);
+        ConversationTreeDto {
+            total_entries: tree.nodes.len(),
+            branch_points: tree.branch_point_count(),
+            active: tree.active_entries().into_iter().map(ConversationEntryDto::from).collect(),
+            abandoned_branches: tree
+                .abandoned_branches()
+                .into_iter()
+                .map(|branch| AbandonedBranchDto {
+                    fork_line: branch.fork_point.map(|i| tree.nodes[i].entry.line_number),
+                    entries: branch.nodes.into_iter().map(entry).collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct DiffMessageDto {
+    pub uuid: Option<String>,
+    pub line_number: usize,
+    pub role: String,
+    pub text: String,
+    pub timestamp: Option<String>,
+}
+
+impl From<&DiffMessage> for DiffMessageDto {
+    fn from(m: &DiffMessage) -> Self {
+        DiffMessageDto {
+            uuid: m.uuid.clone(),
+            line_number: m.line_number,
+            role: m.role.to_string(),
+            text: m.text.clone(),
+            timestamp: m.timestamp.map(|t| t.to_rfc3339()),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct DiffRowDto {
+    /// `shared`, `left` or `right`.
+    pub kind: String,
+    /// `uuid` or `content` for shared rows.
+    pub matched_by: Option<String>,
+    pub left: Option<DiffMessageDto>,
+    pub right: Option<DiffMessageDto>,
+}
+
+#[derive(Serialize)]
+pub struct SessionDiffDto {
+    pub shared_prefix: usize,
+    /// Row where the sessions first differ; `None` when they are identical.
+    pub divergence_row: Option<usize>,
+    pub left_divergence_line: Option<usize>,
+    pub right_divergence_line: Option<usize>,
+    pub shared_count: usize,
+    pub left_only_count: usize,
+    pub right_only_count: usize,
+    pub rows: Vec<DiffRowDto>,
+}
+
+impl From<&SessionDiff> for SessionDiffDto {
+    fn from(diff: &SessionDiff) -> Self {
+        let message = |side: &[DiffMessage], i: usize| Some(DiffMessageDto::from(&side[i]));
+        let rows = diff
+            .rows
+            .iter()
+            .map(|row| match *row {
+                DiffRow::Shared { left, right, by_uuid } => DiffRowDto {
+                    kind: "shared".to_string(),
+                    matched_by: Some(if by_uuid { "uuid" } else { "content" }.to_string()),
+                    left: message(&diff.left, left),
+                    right: message(&diff.right, right),
+                },
+                DiffRow::LeftOnly(left) => DiffRowDto {
+                    kind: "left".to_string(),
+                    matched_by: None,
+                    left: message(&diff.left, left),
+                    right: None,
+                },
+                DiffRow::RightOnly(right) => DiffRowDto {
+                    kind: "right".to_string(),
+                    matched_by: None,
+                    left: None,
+                    right: message(&diff.right, right),
+                },
+            })
+            .collect();
+        let (left_at, right_at) = diff.divergence_messages();
+        SessionDiffDto {
+            shared_prefix: diff.shared_prefix(),
+            divergence_row: diff.divergence(),
+            left_divergence_line: left_at.map(|m| m.line_number),
+            right_divergence_line: right_at.map(|m| m.line_number),
+            shared_count: diff.shared_count(),
+            left_only_count: diff.left_only_count(),
+            right_only_count: diff.right_only_count(),
+            rows,
+        }
+    }
+}
+
+fn parse_artifact_category(category: &str) -> Result<ArtifactCategory, String> {
+    ArtifactCategory::from_dir_name(category)
+        .ok_or_else(|| format!("Unknown artifact category: {}", category))
+}
+
+#[tauri::command]
+pub fn get_statistics() -> Result<StatisticsDto, String> {
+    let mut manager = SessionManager::new()
+        .map_err(|e| format!("Failed to create session manager: {}", e))?;
+    
+    let stats = manager.get_statistics()
+        .map_err(|e| format!("Failed to get statistics: {}", e))?;
+    
+    Ok(StatisticsDto {
+        total_projects: stats.total_projects,
+        total_sessions: stats.total_sessions,
+        total_size: stats.format_total_size(),
+        artifacts: stats
+            .artifact_usage
+            .iter()
+            .map(|u| ArtifactUsageDto {
+                category: u.category.dir_name().to_string(),
+                label: u.category.label().to_string(),
+                count: u.count,
+                size: u.format_size(),
+            })
+            .collect(),
+        total_artifacts_size: stats.format_total_artifacts_size(),
+        age_buckets: stats
+            .age_buckets
+            .iter()
+            .map(|b| AgeBucketDto {
+                label: b.label.to_string(),
+                count: b.count,
+                size: b.format_size(),
+            })
+            .collect(),
+        oldest_session: stats.oldest_session.map(ProjectSessionDto::from),
+        newest_session: stats.newest_session.map(ProjectSessionDto::from),
+        empty_projects: stats.empty_projects,
+        average_session_size: format_size(stats.average_session_size),
+        median_session_size: format_size(stats.median_session_size),
+        largest_project: stats.largest_project.as_ref().map(ProjectSummaryDto::from),
+    })
+}
+
+#[tauri::command]
+pub fn get_activity() -> Result<ActivityDto, String> {
+    let mut manager = SessionManager::new()
+        .map_err(|e| format!("Failed to create session manager: {}", e))?;
+
+    let activity = manager.get_activity()
+        .map_err(|e| format!("Failed to read activity: {}", e))?;
+
+    Ok(ActivityDto::from(activity))
+}
+
+/// The largest and fastest-growing sessions and the largest projects
+/// across the whole store.
+#[tauri::command]
+pub fn get_store_ranking(limit: usize) -> Result<StoreRankingDto, String> {
+    let mut manager = SessionManager::new()
+        .map_err(|e| format!("Failed to create session manager: {}", e))?;
+
+    let ranking = manager.store_ranking(limit)
+        .map_err(|e| format!("Failed to rank sessions: {}", e))?;
+
+    Ok(StoreRankingDto::from(ranking))
+}
+
+/// Tool calls and error rates across the store, with the `limit` projects
+/// and sessions that call tools most and the `limit` that fail most.
+#[tauri::command]
+pub fn get_tool_usage(limit: usize) -> Result<ToolReportDto, String> {
+    let mut manager = SessionManager::new()
+        .map_err(|e| format!("Failed to create session manager: {}", e))?;
+
+    let report = manager.tool_report()
+        .map_err(|e| format!("Failed to collect tool usage: {}", e))?;
+
+    Ok(ToolReportDto::new(&report, limit))
+}
+
+#[tauri::command]
+pub fn scan_projects() -> Result<Vec<ProjectDto>, String> {
+    let mut manager = SessionManager::new()
+        .map_err(|e| format!("Failed to create session manager: {}", e))?;
+    
+    let projects = manager.scan_projects()
+        .map_err(|e| format!("Failed to scan projects: {}", e))?;
+    
+    let dtos: Vec<ProjectDto> = projects
+        .into_iter()
+        .map(|p| {
+            let total_size = p.format_size();
+            let sessions: Vec<SessionDto> = p.sessions
+                .into_iter()
+                .map(SessionDto::from)
+                .collect();
+            ProjectDto {
+                name: p.name,
+                path: p.path.to_string_lossy().to_string(),
+                session_count: sessions.len(),
+                total_size,
+                sessions,
+            }
+        })
+        .collect();
+    
+    Ok(dtos)
+}
+
+#[tauri::command]
+pub fn get_project_sessions(project_path: String) -> Result<Vec<SessionDto>, String> {
+    let mut manager = SessionManager::new()
+        .map_err(|e| format!("Failed to create session manager: {}", e))?;
+    
+    let projects = manager.scan_projects()
+        .map_err(|e| format!("Failed to scan projects: {}", e))?;
+    
+    let path = PathBuf::from(project_path);
+    let project = projects
+        .into_iter()
+        .find(|p| p.path == path)
+        .ok_or_else(|| anyhow!("Project not found").to_string())?;
+    
+    let dtos: Vec<SessionDto> = project
+        .sessions
+        .into_iter()
+        .map(SessionDto::from)
+        .collect();
+    
+    Ok(dtos)
+}
+
+/// Sessions whose git branch no longer exists in the repository they ran in.
+#[tauri::command]
+pub fn find_stale_branch_sessions() -> Result<StaleBranchReportDto, String> {
+    let mut manager = SessionManager::new()
+        .map_err(|e| format!("Failed to create session manager: {}", e))?;
+
+    manager.find_stale_branch_sessions()
+        .map(StaleBranchReportDto::from)
+        .map_err(|e| format!("Failed to check branches: {}", e))
+}
+
+/// A project's sessions grouped by the git branch they were last on.
+#[tauri::command]
+pub fn get_branch_groups(project_path: String) -> Result<Vec<BranchGroupDto>, String> {
+    let manager = SessionManager::new()
+        .map_err(|e| format!("Failed to create session manager: {}", e))?;
+
+    let sessions = manager.scan_sessions(&PathBuf::from(project_path))
+        .map_err(|e| format!("Failed to scan sessions: {}", e))?;
+
+    let groups = group_by_branch(&sessions)
+        .into_iter()
+        .map(|group| BranchGroupDto {
+            branch: group.branch,
+            session_paths: group
+                .sessions
+                .iter()
+                .map(|&i| sessions[i].path.to_string_lossy().to_string())
+                .collect(),
+            size: format_size(group.size),
+            last_activity: DateTime::<Utc>::from(group.last_activity).to_rfc3339(),
+        })
+        .collect();
+
+    Ok(groups)
+}
+
+#[tauri::command]
+pub fn get_project_detail(project_path: String) -> Result<ProjectDetailDto, String> {
+    let mut manager = SessionManager::new()
+        .map_err(|e| format!("Failed to create session manager: {}", e))?;
+
+    let path = PathBuf::from(project_path);
+    let project = manager.scan_projects()
+        .map_err(|e| format!("Failed to scan projects: {}", e))?
+        .into_iter()
+        .find(|p| p.path == path)
+        .ok_or_else(|| "Project not found".to_string())?;
+
+    Ok(ProjectDetailDto::from(manager.project_detail(&project)))
+}
+
+#[tauri::command]
+pub fn get_session_chains(project_path: String) -> Result<Vec<SessionChainDto>, String> {
+    let manager = SessionManager::new()
+        .map_err(|e| format!("Failed to create session manager: {}", e))?;
+
+    let sessions = manager.scan_sessions(&PathBuf::from(project_path))
+        .map_err(|e| format!("Failed to scan sessions: {}", e))?;
+
+    let chains = manager.find_session_chains(&sessions);
+    Ok(chains.into_iter().map(SessionChainDto::from).collect())
+}
+
+/// Exports the given sessions, oldest first, into the user's download
+/// directory and returns the path written, with the anonymization report
+/// when `anonymize` is set.
+#[tauri::command]
+pub fn export_sessions(session_paths: Vec<String>, format: String, anonymize: bool) -> Result<ExportResultDto, String> {
+    let mut manager = SessionManager::new()
+        .map_err(|e| format!("Failed to create session manager: {}", e))?;
+    let format = ExportFormat::from_name(&format)
+        .ok_or_else(|| format!("Unknown export format: {}", format))?;
+
+    let mut sessions = Vec::new();
+    for p in &session_paths {
+        let path = PathBuf::from(p);
+        let parent = path.parent().map(|p| p.to_path_buf()).unwrap_or_default();
+        let session = manager.scan_sessions(&parent)
+            .unwrap_or_default()
+            .into_iter()
+            .find(|s| s.path == path)
+            .ok_or_else(|| format!("Session not found: {}", p))?;
+        sessions.push(session);
+    }
+
+    let name = sessions
+        .last()
+        .and_then(|s| s.path.file_stem())
+        .map(|stem| stem.to_string_lossy().to_string())
+        .ok_or_else(|| "No sessions to export".to_string())?;
+    let directory = dirs::download_dir()
+        .or_else(dirs::home_dir)
+        .ok_or_else(|| "Could not find a directory to export to".to_string())?;
+    let destination = directory.join(format!("{}.{}", name, format.extension()));
+
+    let mut anonymizer = match anonymize {
+        true => Some(manager.anonymizer().map_err(|e| format!("Failed to set up anonymization: {}", e))?),
+        false => None,
+    };
+    export::export_sessions(&sessions, format, &destination, anonymizer.as_mut())
+        .map_err(|e| format!("Failed to export sessions: {}", e))?;
+
+    Ok(ExportResultDto {
+        path: destination.to_string_lossy().to_string(),
+        replacements: anonymizer
+            .map(|a| a.report().iter().map(ReplacementDto::from).collect())
+            .unwrap_or_default(),
+    })
+}
+
+/// Rescans each project once so the sessions come with their sub-agents and
+/// session data. Paths that no longer exist are skipped.
+fn find_sessions(manager: &SessionManager, session_paths: Vec<String>) -> Vec<Session> {
+    let mut scanned: HashMap<PathBuf, Vec<Session>> = HashMap::new();
+    let mut sessions: Vec<Session> = Vec::new();
+    
+    for p in session_paths {
+        let path = PathBuf::from(&p);
+        if !path.exists() {
+            continue;
+        }
+        
+        let parent = path.parent().map(|p| p.to_path_buf()).unwrap_or_default();
+        let project_sessions = scanned
+            .entry(parent.clone())
+            .or_insert_with(|| manager.scan_sessions(&parent).unwrap_or_default());
+        
+        match project_sessions.iter().find(|s| s.path == path) {
+            Some(session) => sessions.push(session.clone()),
+            None => {
+                if let Ok(meta) = std::fs::metadata(&path) {
+                    sessions.push(Session::new(
+                        path.file_name()
+                            .and_then(|n| n.to_str())
+                            .unwrap_or("unknown")
+                            .to_string(),
+                        path.clone(),
+                        meta.len(),
+                        meta.modified().unwrap_or_else(|_| std::time::SystemTime::now()),
+                    ));
+                }
+            }
+        }
+    }
+
+    sessions
 }
 
 #[tauri::command]
-pub fn get_statistics() -> Result<StatisticsDto, String> {
+pub fn delete_sessions(session_paths: Vec<String>) -> Result<usize, String> {
     let mut manager = SessionManager::new()
         .map_err(|e| format!("Failed to create session manager: {}", e))?;
     
-    let stats = manager.get_statistics()
-        .map_err(|e| format!("Failed to get statistics: {}", e))?;
+    let sessions = find_sessions(&manager, session_paths);
+    let deleted_count = manager.delete_sessions(&sessions)
+        .map_err(|e| format!("Failed to delete sessions: {}", e))?;
     
-    Ok(StatisticsDto {
-        total_projects: stats.total_projects,
-        total_sessions: stats.total_sessions,
-        total_size: stats.format_total_size(),
-    })
+    Ok(deleted_count)
 }
 
+/// Moves sessions into the archive directory, keeping their artifacts.
 #[tauri::command]
-pub fn scan_projects() -> Result<Vec<ProjectDto>, String> {
+pub fn archive_sessions(session_paths: Vec<String>) -> Result<usize, String> {
     let mut manager = SessionManager::new()
         .map_err(|e| format!("Failed to create session manager: {}", e))?;
-    
-    let projects = manager.scan_projects()
-        .map_err(|e| format!("Failed to scan projects: {}", e))?;
-    
-    let dtos: Vec<ProjectDto> = projects
-        .into_iter()
-        .map(|p| {
-            let total_size = p.format_size();
-            let sessions: Vec<SessionDto> = p.sessions
-                .into_iter()
-                .map(|s| {
-                    let size = s.format_size();
-                    let age_days = s.get_age_days();
-                    SessionDto {
-                        name: s.name,
-                        path: s.path.to_string_lossy().to_string(),
-                        size,
-                        age_days,
-                        content_preview: s.content_preview,
-                    }
-                })
-                .collect();
-            ProjectDto {
-                name: p.name,
-                path: p.path.to_string_lossy().to_string(),
-                session_count: sessions.len(),
-                total_size,
-                sessions,
-            }
-        })
-        .collect();
-    
-    Ok(dtos)
+
+    let sessions = find_sessions(&manager, session_paths);
+    manager.archive_sessions(&sessions)
+        .map_err(|e| format!("Failed to archive sessions: {}", e))
 }
 
 #[tauri::command]
-pub fn get_project_sessions(project_path: String) -> Result<Vec<SessionDto>, String> {
+pub fn move_sessions(
+    session_paths: Vec<String>,
+    target_project_path: String,
+    new_cwd: Option<String>,
+) -> Result<Vec<MoveResultDto>, String> {
     let mut manager = SessionManager::new()
         .map_err(|e| format!("Failed to create session manager: {}", e))?;
-    
-    let projects = manager.scan_projects()
-        .map_err(|e| format!("Failed to scan projects: {}", e))?;
-    
-    let path = PathBuf::from(project_path);
-    let project = projects
-        .into_iter()
-        .find(|p| p.path == path)
-        .ok_or_else(|| anyhow!("Project not found").to_string())?;
-    
-    let dtos: Vec<SessionDto> = project
-        .sessions
+
+    let target = PathBuf::from(target_project_path);
+    let new_cwd = new_cwd.filter(|cwd| !cwd.trim().is_empty());
+    // A failure stops no other move; each session reports its own result
+    let results = find_sessions(&manager, session_paths)
         .into_iter()
-        .map(|s| {
-            let size = s.format_size();
-            let age_days = s.get_age_days();
-            SessionDto {
-                name: s.name,
-                path: s.path.to_string_lossy().to_string(),
-                size,
-                age_days,
-                content_preview: s.content_preview,
+        .map(|session| {
+            let result = manager.move_session(&session, &target, new_cwd.as_deref());
+            MoveResultDto {
+                path: session.path.to_string_lossy().to_string(),
+                name: session.name,
+                error: result.as_ref().err().map(|e| e.to_string()),
+                outcome: result.ok().map(MoveOutcomeDto::from),
             }
         })
         .collect();
-    
-    Ok(dtos)
+
+    Ok(results)
 }
 
+/// Deletes the originals of the moves being confirmed, given by the
+/// `trash_path` of their outcomes.
 #[tauri::command]
-pub fn delete_sessions(session_paths: Vec<String>) -> Result<usize, String> {
-    let manager = SessionManager::new()
+pub fn empty_trash(trash_paths: Vec<String>) -> Result<String, String> {
+    let mut manager = SessionManager::new()
         .map_err(|e| format!("Failed to create session manager: {}", e))?;
-    
-    let sessions: Vec<Session> = session_paths
-        .into_iter()
-        .filter_map(|p| {
-            let path = PathBuf::from(&p);
-            if path.exists() {
-                std::fs::metadata(&path).ok().map(|meta| Session {
-                    name: path.file_name()
-                        .and_then(|n| n.to_str())
-                        .unwrap_or("unknown")
-                        .to_string(),
-                    path,
-                    size: meta.len(),
-                    modified: meta.modified().unwrap_or_else(|_| std::time::SystemTime::now()),
-                    content_preview: None,
-                })
-            } else {
-                None
-            }
-        })
-        .collect();
-    
-    let deleted_count = manager.delete_sessions(&sessions)
-        .map_err(|e| format!("Failed to delete sessions: {}", e))?;
-    
-    Ok(deleted_count)
+
+    let trash_paths: Vec<PathBuf> = trash_paths.into_iter().map(PathBuf::from).collect();
+    manager.empty_trash(&trash_paths)
+        .map(format_size)
+        .map_err(|e| format!("Failed to empty the trash: {}", e))
+}
+
+/// Reports what relocating a project would change, without writing anything.
+#[tauri::command]
+pub fn preview_relocation(old_cwd: String, new_cwd: String) -> Result<RelocationReportDto, String> {
+    let mut manager = SessionManager::new()
+        .map_err(|e| format!("Failed to create session manager: {}", e))?;
+
+    manager.preview_relocation(&old_cwd, &new_cwd)
+        .map(RelocationReportDto::from)
+        .map_err(|e| format!("Failed to preview relocation: {}", e))
+}
+
+/// Carries out a preview from `preview_relocation`; refused when the project
+/// files changed since.
+#[tauri::command]
+pub fn apply_relocation(preview: RelocationReportDto) -> Result<RelocationReportDto, String> {
+    let mut manager = SessionManager::new()
+        .map_err(|e| format!("Failed to create session manager: {}", e))?;
+
+    manager.apply_relocation(&RelocationReport::from(preview))
+        .map(RelocationReportDto::from)
+        .map_err(|e| format!("Failed to relocate project: {}", e))
 }
 
 #[tauri::command]
@@ -187,17 +1369,7 @@ pub fn filter_sessions_by_age(days: u64) -> Result<Vec<SessionDto>, String> {
     
     let dtos: Vec<SessionDto> = all_sessions
         .into_iter()
-        .map(|(_, s)| {
-            let size = s.format_size();
-            let age_days = s.get_age_days();
-            SessionDto {
-                name: s.name,
-                path: s.path.to_string_lossy().to_string(),
-                size,
-                age_days,
-                content_preview: s.content_preview,
-            }
-        })
+        .map(|(_, s)| SessionDto::from(s))
         .collect();
     
     Ok(dtos)
@@ -227,201 +1399,208 @@ pub fn delete_old_sessions(days: u64) -> Result<usize, String> {
 }
 
 #[tauri::command]
-pub fn get_session_content(session_path: String) -> Result<String, String> {
-    let content = std::fs::read_to_string(&session_path)
-        .map_err(|e| format!("Failed to read session content: {}", e))?;
-    
-    let formatted = format_session_content(&content);
-    Ok(formatted)
+pub fn filter_artifacts_by_age(category: String, days: u64) -> Result<Vec<ArtifactDto>, String> {
+    let category = parse_artifact_category(&category)?;
+    let mut manager = SessionManager::new()
+        .map_err(|e| format!("Failed to create session manager: {}", e))?;
+
+    let artifacts = manager.scan_artifacts(category)
+        .map_err(|e| format!("Failed to scan artifacts: {}", e))?;
+
+    let dtos: Vec<ArtifactDto> = manager
+        .filter_artifacts_by_age(&artifacts, days)
+        .into_iter()
+        .cloned()
+        .map(ArtifactDto::from)
+        .collect();
+
+    Ok(dtos)
 }
 
-fn format_session_content(content: &str) -> String {
-    let mut result = String::new();
-    let mut has_any_content = false;
-    
-    for line in content.lines() {
-        let line = line.trim();
-        if line.is_empty() {
-            continue;
-        }
-        
-        match serde_json::from_str::<serde_json::Value>(line) {
-            Ok(json) => {
-                let msg_type = json.get("type").and_then(|v| v.as_str()).unwrap_or("unknown");
-                let role = json.get("message").and_then(|m| m.get("role")).and_then(|v| v.as_str()).unwrap_or("entry");
-                
-                let role_label = match (msg_type, role) {
-                    ("user", _) => "👤 USER",
-                    ("assistant", _) => "🤖 CLAUDE",
-                    ("system", _) => "⚙️ SYSTEM",
-                    ("tool", _) => "🛠️ TOOL",
-                    ("function", _) => "📌 FUNCTION",
-                    _ => match role {
-                        "user" => "👤 USER",
-                        "assistant" => "🤖 CLAUDE",
-                        "system" => "⚙️ SYSTEM",
-                        "tool" => "🛠️ TOOL",
-                        "function" => "📌 FUNCTION",
-                        _ => "📝 ENTRY",
-                    },
-                };
-                
-                let extracted = extract_all_text(&json);
-                
-                if extracted.is_empty() {
-                    continue;
-                }
-                
-                has_any_content = true;
-                result.push_str(&format!("─── {} ───\n", role_label));
-                result.push_str(&extracted);
-                result.push_str("\n\n");
-            }
-            Err(_) => {
-                if !line.is_empty() {
-                    has_any_content = true;
-                    result.push_str(line);
-                    result.push_str("\n\n");
-                }
-            }
-        }
-    }
-    
-    if !has_any_content {
-        "No content available".to_string()
-    } else {
-        result.trim_end().to_string()
-    }
+#[tauri::command]
+pub fn delete_artifacts(category: String, artifact_paths: Vec<String>) -> Result<usize, String> {
+    let category = parse_artifact_category(&category)?;
+    let mut manager = SessionManager::new()
+        .map_err(|e| format!("Failed to create session manager: {}", e))?;
+
+    // Only delete paths that a fresh scan of the category still reports
+    let artifacts: Vec<Artifact> = manager.scan_artifacts(category)
+        .map_err(|e| format!("Failed to scan artifacts: {}", e))?
+        .into_iter()
+        .filter(|a| artifact_paths.iter().any(|p| a.path == std::path::Path::new(p)))
+        .collect();
+
+    manager.delete_artifacts(&artifacts)
+        .map_err(|e| format!("Failed to delete artifacts: {}", e))
+}
+
+#[tauri::command]
+pub fn delete_old_artifacts(category: String, days: u64) -> Result<usize, String> {
+    let category = parse_artifact_category(&category)?;
+    let mut manager = SessionManager::new()
+        .map_err(|e| format!("Failed to create session manager: {}", e))?;
+
+    let artifacts = manager.scan_artifacts(category)
+        .map_err(|e| format!("Failed to scan artifacts: {}", e))?;
+
+    let old_artifacts: Vec<Artifact> = manager
+        .filter_artifacts_by_age(&artifacts, days)
+        .into_iter()
+        .cloned()
+        .collect();
+
+    manager.delete_artifacts(&old_artifacts)
+        .map_err(|e| format!("Failed to delete artifacts: {}", e))
 }
 
-fn extract_all_text(value: &serde_json::Value) -> String {
-    let mut texts = Vec::new();
-    extract_text_recursive(value, &mut texts, 0);
-    texts.join("\n\n").trim().to_string()
+#[tauri::command]
+pub fn find_orphaned_artifacts() -> Result<Vec<ArtifactDto>, String> {
+    let mut manager = SessionManager::new()
+        .map_err(|e| format!("Failed to create session manager: {}", e))?;
+
+    let orphans = manager.find_orphaned_artifacts()
+        .map_err(|e| format!("Failed to find orphaned artifacts: {}", e))?;
+
+    Ok(orphans.into_iter().map(ArtifactDto::from).collect())
 }
 
-fn extract_text_recursive(value: &serde_json::Value, texts: &mut Vec<String>, depth: usize) {
-    if depth > 15 {
-        return;
-    }
-    
-    if let Some(message) = value.get("message") {
-        if let Some(content_val) = message.get("content") {
-            if let Some(content_arr) = content_val.as_array() {
-                for block in content_arr {
-                    extract_content_block(block, texts, depth);
-                }
-                return;
-            }
-            if let Some(content_str) = content_val.as_str() {
-                let converted = content_str.replace("\\n", "\n").trim().to_string();
-                if !converted.is_empty() && converted != "{}" {
-                    texts.push(converted);
-                }
-            }
-        }
-    }
-    
-    if let Some(content_val) = value.get("content") {
-        if let Some(content_arr) = content_val.as_array() {
-            for block in content_arr {
-                extract_content_block(block, texts, depth);
-            }
-            return;
-        }
-    }
-    
-    match value {
-        serde_json::Value::String(s) => {
-            if !s.trim().is_empty() {
-                let converted = s.replace("\\n", "\n");
-                let trimmed = converted.trim();
-                if !trimmed.is_empty() && trimmed != "{}" && !trimmed.starts_with("{") {
-                    texts.push(trimmed.to_string());
-                }
-            }
-        }
-        serde_json::Value::Object(obj) => {
-            for (key, val) in obj.iter() {
-                match key.as_str() {
-                    "text" | "content" | "message" | "output" | "result" | "input" | "thought" | "reasoning" => {
-                        extract_text_recursive(val, texts, depth + 1);
-                    }
-                    _ => {}
-                }
-            }
-        }
-        serde_json::Value::Array(arr) => {
-            for item in arr.iter() {
-                extract_content_block(item, texts, depth);
-            }
-        }
-        _ => {}
+#[tauri::command]
+pub fn delete_orphaned_artifacts() -> Result<usize, String> {
+    let mut manager = SessionManager::new()
+        .map_err(|e| format!("Failed to create session manager: {}", e))?;
+
+    let orphans = manager.find_orphaned_artifacts()
+        .map_err(|e| format!("Failed to find orphaned artifacts: {}", e))?;
+
+    manager.delete_artifacts(&orphans)
+        .map_err(|e| format!("Failed to delete artifacts: {}", e))
+}
+
+#[tauri::command]
+pub fn find_duplicate_sessions() -> Result<Vec<ProjectDuplicatesDto>, String> {
+    let mut manager = SessionManager::new()
+        .map_err(|e| format!("Failed to create session manager: {}", e))?;
+
+    let projects = manager.find_duplicate_sessions()
+        .map_err(|e| format!("Failed to find duplicate sessions: {}", e))?;
+
+    Ok(projects.into_iter().map(ProjectDuplicatesDto::from).collect())
+}
+
+#[tauri::command]
+pub fn run_doctor() -> Result<Vec<DoctorFindingDto>, String> {
+    let mut manager = SessionManager::new()
+        .map_err(|e| format!("Failed to create session manager: {}", e))?;
+
+    Ok(manager.doctor().into_iter().map(DoctorFindingDto::from).collect())
+}
+
+#[tauri::command]
+pub fn check_integrity() -> Result<Vec<SessionIntegrityDto>, String> {
+    let mut manager = SessionManager::new()
+        .map_err(|e| format!("Failed to create session manager: {}", e))?;
+
+    let reports = manager.check_integrity()
+        .map_err(|e| format!("Failed to check transcripts: {}", e))?;
+
+    Ok(reports.into_iter().map(SessionIntegrityDto::from).collect())
+}
+
+#[tauri::command]
+pub fn repair_sessions(session_paths: Vec<String>) -> Result<Vec<RepairOutcomeDto>, String> {
+    let mut manager = SessionManager::new()
+        .map_err(|e| format!("Failed to create session manager: {}", e))?;
+
+    let mut outcomes = Vec::new();
+    for p in session_paths {
+        let outcome = manager.repair_session(&PathBuf::from(&p))
+            .map_err(|e| format!("Failed to repair {}: {}", p, e))?;
+        outcomes.push(RepairOutcomeDto::from(outcome));
     }
+
+    Ok(outcomes)
 }
 
-fn extract_content_block(block: &serde_json::Value, texts: &mut Vec<String>, depth: usize) {
-    if let Some(block_obj) = block.as_object() {
-        if let Some(text) = block_obj.get("text").and_then(|v| v.as_str()) {
-            let converted = text.replace("\\n", "\n").trim().to_string();
-            if !converted.is_empty() {
-                texts.push(converted);
-            }
-        }
-        
-        if let Some(thinking) = block_obj.get("thinking").and_then(|v| v.as_str()) {
-            let converted = thinking.replace("\\n", "\n").trim().to_string();
-            if !converted.is_empty() {
-                texts.push(format!("[Thinking]\n{}", converted));
-            }
-        }
-        
-        if let Some(tool_name) = block_obj.get("name").and_then(|v| v.as_str()) {
-            let mut tool_text = format!("[Tool: {}]", tool_name);
-            if let Some(input_val) = block_obj.get("input") {
-                if let Some(input) = input_val.as_object() {
-                    let input_str = format!("{:?}", input);
-                    if input_str.len() > 10 {
-                        tool_text.push_str(&format!("\n{}", input_str));
-                    }
-                }
-            }
-            texts.push(tool_text);
-        }
-        
-        if let Some(result) = block_obj.get("content") {
-            match result {
-                serde_json::Value::String(s) => {
-                    let converted = s.replace("\\n", "\n").trim().to_string();
-                    if !converted.is_empty() {
-                        texts.push(converted);
-                    }
-                }
-                serde_json::Value::Object(obj) => {
-                    if let Some(text) = obj.get("text").and_then(|v| v.as_str()) {
-                        let converted = text.replace("\\n", "\n").trim().to_string();
-                        if !converted.is_empty() {
-                            texts.push(converted);
-                        }
-                    }
-                }
-                _ => {}
-            }
-        }
-        
-        if let Some(nested_val) = block_obj.get("content") {
-            if let Some(nested) = nested_val.as_array() {
-                for item in nested {
-                    extract_content_block(item, texts, depth + 1);
-                }
-            }
-        }
-        
-        if let Some(content_str) = block_obj.get("content").and_then(|v| v.as_str()) {
-            let converted = content_str.replace("\\n", "\n").trim().to_string();
-            if !converted.is_empty() {
-                texts.push(converted);
-            }
-        }
+/// Builds a scanner from the config file plus the patterns entered in the UI.
+fn secret_scanner(manager: &mut SessionManager, extra_patterns: Vec<String>) -> Result<SecretScanner, String> {
+    let extra: Vec<SecretPatternConfig> = extra_patterns
+        .into_iter()
+        .filter(|pattern| !pattern.trim().is_empty())
+        .enumerate()
+        .map(|(i, pattern)| SecretPatternConfig {
+            name: format!("custom-{}", i + 1),
+            pattern,
+        })
+        .collect();
+
+    manager.secret_scanner(&extra).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn scan_secrets(extra_patterns: Vec<String>) -> Result<Vec<SessionSecretsDto>, String> {
+    let mut manager = SessionManager::new()
+        .map_err(|e| format!("Failed to create session manager: {}", e))?;
+    let scanner = secret_scanner(&mut manager, extra_patterns)?;
+
+    let results = manager.scan_secrets(&scanner)
+        .map_err(|e| format!("Failed to scan for secrets: {}", e))?;
+
+    Ok(results.into_iter().map(SessionSecretsDto::from).collect())
+}
+
+#[tauri::command]
+pub fn redact_secrets(session_paths: Vec<String>, extra_patterns: Vec<String>) -> Result<Vec<RedactionOutcomeDto>, String> {
+    let mut manager = SessionManager::new()
+        .map_err(|e| format!("Failed to create session manager: {}", e))?;
+    let scanner = secret_scanner(&mut manager, extra_patterns)?;
+
+    let mut outcomes = Vec::new();
+    for p in session_paths {
+        let outcome = manager.redact_secrets(&PathBuf::from(&p), &scanner)
+            .map_err(|e| format!("Failed to redact {}: {}", p, e))?;
+        outcomes.push(RedactionOutcomeDto::from(outcome));
     }
+
+    Ok(outcomes)
+}
+
+#[tauri::command]
+pub fn get_conversation_tree(session_path: String) -> Result<ConversationTreeDto, String> {
+    let manager = SessionManager::new()
+        .map_err(|e| format!("Failed to create session manager: {}", e))?;
+
+    let tree = manager.load_conversation(&PathBuf::from(session_path))
+        .map_err(|e| format!("Failed to read session: {}", e))?;
+
+    Ok(ConversationTreeDto::from(&tree))
+}
+
+#[tauri::command]
+pub fn diff_sessions(left_path: String, right_path: String) -> Result<SessionDiffDto, String> {
+    let manager = SessionManager::new()
+        .map_err(|e| format!("Failed to create session manager: {}", e))?;
+
+    let diff = manager.diff_sessions(&PathBuf::from(left_path), &PathBuf::from(right_path))
+        .map_err(|e| format!("Failed to compare sessions: {}", e))?;
+
+    Ok(SessionDiffDto::from(&diff))
+}
+
+#[tauri::command]
+pub fn get_resume_command(session_path: String) -> Result<ResumeCommandDto, String> {
+    let mut manager = SessionManager::new()
+        .map_err(|e| format!("Failed to create session manager: {}", e))?;
+
+    manager.resume_command(&PathBuf::from(session_path))
+        .map(ResumeCommandDto::from)
+        .map_err(|e| format!("Cannot resume session: {}", e))
+}
+
+#[tauri::command]
+pub fn get_session_content(session_path: String) -> Result<String, String> {
+    let content = std::fs::read_to_string(&session_path)
+        .map_err(|e| format!("Failed to read session content: {}", e))?;
+    
+    let formatted = transcript::format_session_content(&content);
+    Ok(formatted)
 }
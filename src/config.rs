@@ -0,0 +1,83 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A named regular expression added to the built-in secret detectors.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecretPatternConfig {
+    pub name: String,
+    pub pattern: String,
+}
+
+/// A custom anonymization rule: every match of `pattern` becomes a
+/// placeholder named after `label`, e.g. `<TICKET_1>`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnonymizeRuleConfig {
+    pub label: String,
+    pub pattern: String,
+}
+
+/// What an anonymized export replaces. Usernames found in home paths are
+/// always added to `usernames`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AnonymizeConfig {
+    pub home_paths: bool,
+    pub emails: bool,
+    pub secrets: bool,
+    pub usernames: Vec<String>,
+    pub hostnames: Vec<String>,
+    pub rules: Vec<AnonymizeRuleConfig>,
+}
+
+impl Default for AnonymizeConfig {
+    fn default() -> Self {
+        Self {
+            home_paths: true,
+            emails: true,
+            secrets: true,
+            usernames: Vec::new(),
+            hostnames: Vec::new(),
+            rules: Vec::new(),
+        }
+    }
+}
+
+/// User settings, read from `<claude dir>/cc-session-manager/config.json`.
+/// Every field is optional in the file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub secret_patterns: Vec<SecretPatternConfig>,
+    pub anonymize: AnonymizeConfig,
+    /// Program run to resume a session, looked up on `PATH` unless it is a path.
+    pub claude_binary: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            secret_patterns: Vec::new(),
+            anonymize: AnonymizeConfig::default(),
+            claude_binary: "claude".to_string(),
+        }
+    }
+}
+
+impl Config {
+    pub fn path(claude_dir: &Path) -> PathBuf {
+        claude_dir.join("cc-session-manager/config.json")
+    }
+
+    /// Loads the config file, falling back to the defaults when it does not exist.
+    pub fn load(claude_dir: &Path) -> Result<Self> {
+        let path = Self::path(claude_dir);
+        match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|e| anyhow!("Invalid config file {}: {}", path.display(), e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
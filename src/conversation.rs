@@ -0,0 +1,265 @@
+use crate::transcript::{format_entry_block, TranscriptEntry};
+use std::collections::{HashMap, HashSet};
+
+/// An entry placed in the conversation tree.
+#[derive(Debug, Clone)]
+pub struct ConversationNode {
+    pub entry: TranscriptEntry,
+    pub parent: Option<usize>,
+    pub children: Vec<usize>,
+    pub on_active_branch: bool,
+}
+
+/// A subtree that the conversation moved away from after a rewind, edit or retry.
+#[derive(Debug, Clone)]
+pub struct AbandonedBranch {
+    /// Node the branch forked from, or `None` for a detached root.
+    pub fork_point: Option<usize>,
+    /// Nodes of the branch in file order.
+    pub nodes: Vec<usize>,
+}
+
+/// The message tree of a session, built from `uuid`/`parentUuid` links.
+#[derive(Debug, Clone, Default)]
+pub struct ConversationTree {
+    /// Nodes in file order; entries without a `uuid` are not part of the tree.
+    pub nodes: Vec<ConversationNode>,
+    pub roots: Vec<usize>,
+}
+
+impl ConversationTree {
+    pub fn build(entries: Vec<TranscriptEntry>) -> Self {
+        let mut index_by_uuid: HashMap<String, usize> = HashMap::new();
+        let mut nodes: Vec<ConversationNode> = Vec::new();
+
+        for entry in entries {
+            let uuid = match &entry.uuid {
+                Some(uuid) if !index_by_uuid.contains_key(uuid) => uuid.clone(),
+                _ => continue,
+            };
+            index_by_uuid.insert(uuid, nodes.len());
+            nodes.push(ConversationNode {
+                entry,
+                parent: None,
+                children: Vec::new(),
+                on_active_branch: false,
+            });
+        }
+
+        for i in 0..nodes.len() {
+            // Compaction boundaries restart the chain but keep a logical link
+            let parent_uuid = nodes[i].entry.parent_uuid.clone().or_else(|| {
+                nodes[i]
+                    .entry
+                    .value
+                    .get("logicalParentUuid")
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string())
+            });
+
+            if let Some(&parent) = parent_uuid.as_ref().and_then(|uuid| index_by_uuid.get(uuid)) {
+                if parent != i {
+                    nodes[i].parent = Some(parent);
+                    nodes[parent].children.push(i);
+                }
+            }
+        }
+
+        let roots = (0..nodes.len()).filter(|&i| nodes[i].parent.is_none()).collect();
+        let mut tree = Self { nodes, roots };
+        tree.mark_active_branch();
+        tree
+    }
+
+    /// The active branch runs from the root to the last main-thread entry
+    /// written to the file (or the last entry at all in a sub-agent
+    /// transcript, where every entry is a sidechain). Tool results of parallel
+    /// tool calls hang off the same assistant turn as side leaves, so they are
+    /// treated as active too.
+    fn mark_active_branch(&mut self) {
+        let main_leaf = self.nodes.iter().rposition(|n| !n.entry.is_sidechain);
+        let leaf = match main_leaf.or(self.nodes.len().checked_sub(1)) {
+            Some(leaf) => leaf,
+            None => return,
+        };
+
+        let mut visited = HashSet::new();
+        let mut current = Some(leaf);
+        while let Some(i) = current {
+            // Guard against parent cycles in corrupted files
+            if !visited.insert(i) {
+                break;
+            }
+            self.nodes[i].on_active_branch = true;
+            current = self.nodes[i].parent;
+        }
+
+        for i in 0..self.nodes.len() {
+            let attached_to_active = self.nodes[i]
+                .parent
+                .is_some_and(|p| self.nodes[p].on_active_branch && !visited.contains(&i));
+            if attached_to_active && self.subtree(i).iter().all(|&n| self.nodes[n].entry.is_tool_result()) {
+                for n in self.subtree(i) {
+                    self.nodes[n].on_active_branch = true;
+                }
+            }
+        }
+    }
+
+    /// Returns `root` and all of its descendants in file order.
+    pub fn subtree(&self, root: usize) -> Vec<usize> {
+        let mut result = Vec::new();
+        let mut visited = HashSet::new();
+        let mut stack = vec![root];
+        while let Some(i) = stack.pop() {
+            if visited.insert(i) {
+                result.push(i);
+                stack.extend(self.nodes[i].children.iter().copied());
+            }
+        }
+        result.sort_unstable();
+        result
+    }
+
+    /// Entries of the conversation that actually happened, in file order.
+    pub fn active_entries(&self) -> Vec<&TranscriptEntry> {
+        self.nodes
+            .iter()
+            .filter(|n| n.on_active_branch)
+            .map(|n| &n.entry)
+            .collect()
+    }
+
+    pub fn abandoned_branches(&self) -> Vec<AbandonedBranch> {
+        (0..self.nodes.len())
+            .filter(|&i| !self.nodes[i].on_active_branch)
+            .filter(|&i| match self.nodes[i].parent {
+                Some(parent) => self.nodes[parent].on_active_branch,
+                None => true,
+            })
+            .map(|i| AbandonedBranch {
+                fork_point: self.nodes[i].parent,
+                nodes: self.subtree(i),
+            })
+            .collect()
+    }
+
+    /// Number of nodes where the conversation forked into a real alternative
+    /// (ignoring parallel tool results).
+    pub fn branch_point_count(&self) -> usize {
+        let forks: HashSet<usize> = self
+            .abandoned_branches()
+            .iter()
+            .filter_map(|branch| branch.fork_point)
+            .collect();
+        forks.len()
+    }
+
+    /// Separator shown above the `index`-th abandoned branch.
+    pub fn branch_heading(&self, index: usize, branch: &AbandonedBranch) -> String {
+        let fork = match branch.fork_point {
+            Some(fork) => format!("forked after line {}", self.nodes[fork].entry.line_number),
+            None => "detached".to_string(),
+        };
+        format!("═══ Abandoned branch {} ({}, {} entries) ═══", index + 1, fork, branch.nodes.len())
+    }
+
+    /// Renders the active branch, optionally followed by every abandoned branch.
+    pub fn format(&self, include_abandoned: bool) -> String {
+        let mut blocks: Vec<String> = self
+            .active_entries()
+            .into_iter()
+            .filter_map(|entry| format_entry_block(&entry.value))
+            .collect();
+
+        if include_abandoned {
+            for (index, branch) in self.abandoned_branches().iter().enumerate() {
+                blocks.push(self.branch_heading(index, branch));
+                blocks.extend(
+                    branch
+                        .nodes
+                        .iter()
+                        .filter_map(|&i| format_entry_block(&self.nodes[i].entry.value)),
+                );
+            }
+        }
+
+        if blocks.is_empty() {
+            "No content available".to_string()
+        } else {
+            blocks.join("\n\n")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn entry(line: usize, uuid: &str, parent: Option<&str>, content: serde_json::Value) -> TranscriptEntry {
+        TranscriptEntry::from_value(
+            line,
+            json!({
+                "type": if line % 2 == 1 { "user" } else { "assistant" },
+                "uuid": uuid,
+                "parentUuid": parent,
+                "message": { "content": content },
+            }),
+        )
+    }
+
+    #[test]
+    fn test_rewind_creates_abandoned_branch() {
+        let tree = ConversationTree::build(vec![
+            entry(1, "u1", None, json!("hello")),
+            entry(2, "a1", Some("u1"), json!("hi")),
+            entry(3, "u2", Some("a1"), json!("first attempt")),
+            entry(4, "a2", Some("u2"), json!("answer")),
+            // The user rewound to a1 and asked again
+            entry(5, "u3", Some("a1"), json!("second attempt")),
+            entry(6, "a3", Some("u3"), json!("better answer")),
+        ]);
+
+        let active: Vec<_> = tree.active_entries().iter().map(|e| e.uuid.clone().unwrap()).collect();
+        assert_eq!(active, vec!["u1", "a1", "u3", "a3"]);
+
+        let branches = tree.abandoned_branches();
+        assert_eq!(branches.len(), 1);
+        assert_eq!(branches[0].fork_point, Some(1));
+        assert_eq!(branches[0].nodes, vec![2, 3]);
+        assert_eq!(tree.branch_point_count(), 1);
+
+        assert!(!tree.format(false).contains("first attempt"));
+        assert!(tree.format(true).contains("Abandoned branch 1"));
+    }
+
+    #[test]
+    fn test_parallel_tool_results_stay_active() {
+        let tool_result = |id: &str| json!([{ "type": "tool_result", "tool_use_id": id, "content": "ok" }]);
+        let tree = ConversationTree::build(vec![
+            entry(1, "u1", None, json!("run two tools")),
+            entry(2, "a1", Some("u1"), json!([{ "type": "tool_use", "id": "t1", "name": "Read" }])),
+            entry(4, "a2", Some("a1"), json!([{ "type": "tool_use", "id": "t2", "name": "Read" }])),
+            entry(3, "r1", Some("a1"), tool_result("t1")),
+            entry(5, "r2", Some("a2"), tool_result("t2")),
+        ]);
+
+        assert_eq!(tree.active_entries().len(), 5);
+        assert!(tree.abandoned_branches().is_empty());
+        assert_eq!(tree.branch_point_count(), 0);
+    }
+
+    #[test]
+    fn test_subagent_transcript_has_active_branch() {
+        let sidechain = |line: usize, uuid: &str, parent: Option<&str>| {
+            let mut e = entry(line, uuid, parent, json!("work"));
+            e.is_sidechain = true;
+            e
+        };
+        let tree = ConversationTree::build(vec![sidechain(1, "s1", None), sidechain(2, "s2", Some("s1"))]);
+
+        assert_eq!(tree.active_entries().len(), 2);
+        assert!(tree.abandoned_branches().is_empty());
+    }
+}
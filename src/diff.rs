@@ -0,0 +1,283 @@
+use crate::conversation::ConversationTree;
+use crate::transcript::{extract_all_text, role_label, TranscriptEntry};
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+
+/// A user or assistant message on the active branch of one side of a diff.
+#[derive(Debug, Clone)]
+pub struct DiffMessage {
+    pub uuid: Option<String>,
+    pub line_number: usize,
+    pub role: &'static str,
+    pub text: String,
+    pub timestamp: Option<DateTime<Utc>>,
+}
+
+impl DiffMessage {
+    fn from_entry(entry: &TranscriptEntry) -> Option<Self> {
+        if !matches!(entry.entry_type.as_str(), "user" | "assistant") {
+            return None;
+        }
+        let text = extract_all_text(&entry.value);
+        if text.is_empty() {
+            return None;
+        }
+        Some(DiffMessage {
+            uuid: entry.uuid.clone(),
+            line_number: entry.line_number,
+            role: role_label(&entry.value),
+            text,
+            timestamp: entry.timestamp,
+        })
+    }
+
+    /// The first non-empty line of the text.
+    pub fn first_line(&self) -> &str {
+        self.text.lines().map(str::trim).find(|line| !line.is_empty()).unwrap_or("")
+    }
+}
+
+/// One row of a side-by-side diff, indexing into `SessionDiff::left` and `right`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DiffRow {
+    /// The message is on both sides; `by_uuid` is false when only the
+    /// content matched, e.g. after a resume rewrote the UUIDs.
+    Shared { left: usize, right: usize, by_uuid: bool },
+    LeftOnly(usize),
+    RightOnly(usize),
+}
+
+/// Two conversations aligned message by message: by `uuid` where both
+/// sides have it, by role and text otherwise.
+#[derive(Debug, Clone, Default)]
+pub struct SessionDiff {
+    pub left: Vec<DiffMessage>,
+    pub right: Vec<DiffMessage>,
+    pub rows: Vec<DiffRow>,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum Key {
+    Uuid(String),
+    Content(String),
+}
+
+impl SessionDiff {
+    /// Compares the active branches of two conversations.
+    pub fn compare(left: &ConversationTree, right: &ConversationTree) -> Self {
+        let messages = |tree: &ConversationTree| -> Vec<DiffMessage> {
+            tree.active_entries().into_iter().filter_map(DiffMessage::from_entry).collect()
+        };
+        Self::from_messages(messages(left), messages(right))
+    }
+
+    pub fn from_messages(left: Vec<DiffMessage>, right: Vec<DiffMessage>) -> Self {
+        let left_keys = keys(&left, &right);
+        let right_keys = keys(&right, &left);
+        let mut rows = Vec::new();
+        align(&left_keys, &right_keys, 0, 0, &mut rows);
+        SessionDiff { left, right, rows }
+    }
+
+    /// Number of rows both sides share before they first differ.
+    pub fn shared_prefix(&self) -> usize {
+        self.rows.iter().take_while(|row| matches!(row, DiffRow::Shared { .. })).count()
+    }
+
+    /// Row where the sessions first differ, `None` when they are identical.
+    pub fn divergence(&self) -> Option<usize> {
+        Some(self.shared_prefix()).filter(|&row| row < self.rows.len())
+    }
+
+    /// The first message on each side after the shared prefix; `None` for a
+    /// side that ends there.
+    pub fn divergence_messages(&self) -> (Option<&DiffMessage>, Option<&DiffMessage>) {
+        let rows = &self.rows[self.shared_prefix()..];
+        let left = rows.iter().find_map(|row| match *row {
+            DiffRow::Shared { left, .. } | DiffRow::LeftOnly(left) => Some(&self.left[left]),
+            DiffRow::RightOnly(_) => None,
+        });
+        let right = rows.iter().find_map(|row| match *row {
+            DiffRow::Shared { right, .. } | DiffRow::RightOnly(right) => Some(&self.right[right]),
+            DiffRow::LeftOnly(_) => None,
+        });
+        (left, right)
+    }
+
+    pub fn shared_count(&self) -> usize {
+        self.rows.iter().filter(|row| matches!(row, DiffRow::Shared { .. })).count()
+    }
+
+    pub fn left_only_count(&self) -> usize {
+        self.rows.iter().filter(|row| matches!(row, DiffRow::LeftOnly(_))).count()
+    }
+
+    pub fn right_only_count(&self) -> usize {
+        self.rows.iter().filter(|row| matches!(row, DiffRow::RightOnly(_))).count()
+    }
+}
+
+/// Alignment keys for `messages`: the UUID when `other` has the same one,
+/// the role and whitespace-normalized text otherwise.
+fn keys(messages: &[DiffMessage], other: &[DiffMessage]) -> Vec<Key> {
+    let other_uuids: HashSet<&str> = other.iter().filter_map(|m| m.uuid.as_deref()).collect();
+    messages
+        .iter()
+        .map(|message| match message.uuid.as_deref() {
+            Some(uuid) if other_uuids.contains(uuid) => Key::Uuid(uuid.to_string()),
+            _ => {
+                let text: Vec<&str> = message.text.split_whitespace().collect();
+                Key::Content(format!("{}\n{}", message.role, text.join(" ")))
+            }
+        })
+        .collect()
+}
+
+/// Row for the `i`th key of two runs that match key for key.
+fn shared(a: &[Key], i: usize, offset_a: usize, offset_b: usize) -> DiffRow {
+    DiffRow::Shared {
+        left: offset_a + i,
+        right: offset_b + i,
+        by_uuid: matches!(a[i], Key::Uuid(_)),
+    }
+}
+
+/// Patience alignment: keeps the common prefix and suffix, anchors the rest
+/// on keys that occur exactly once on each side and recurses between anchors.
+fn align(a: &[Key], b: &[Key], offset_a: usize, offset_b: usize, rows: &mut Vec<DiffRow>) {
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    for i in 0..prefix {
+        rows.push(shared(a, i, offset_a, offset_b));
+    }
+    let (a_rest, b_rest) = (&a[prefix..], &b[prefix..]);
+    let suffix = a_rest.iter().rev().zip(b_rest.iter().rev()).take_while(|(x, y)| x == y).count();
+    let a_mid = &a_rest[..a_rest.len() - suffix];
+    let b_mid = &b_rest[..b_rest.len() - suffix];
+    let (mid_a, mid_b) = (offset_a + prefix, offset_b + prefix);
+
+    let anchors = unique_anchors(a_mid, b_mid);
+    if anchors.is_empty() {
+        rows.extend((0..a_mid.len()).map(|i| DiffRow::LeftOnly(mid_a + i)));
+        rows.extend((0..b_mid.len()).map(|j| DiffRow::RightOnly(mid_b + j)));
+    } else {
+        let (mut next_a, mut next_b) = (0, 0);
+        for (i, j) in anchors {
+            align(&a_mid[next_a..i], &b_mid[next_b..j], mid_a + next_a, mid_b + next_b, rows);
+            rows.push(DiffRow::Shared {
+                left: mid_a + i,
+                right: mid_b + j,
+                by_uuid: matches!(a_mid[i], Key::Uuid(_)),
+            });
+            next_a = i + 1;
+            next_b = j + 1;
+        }
+        align(&a_mid[next_a..], &b_mid[next_b..], mid_a + next_a, mid_b + next_b, rows);
+    }
+
+    let tail_a = offset_a + a.len() - suffix;
+    let tail_b = offset_b + b.len() - suffix;
+    for i in 0..suffix {
+        rows.push(shared(&a[a.len() - suffix..], i, tail_a, tail_b));
+    }
+}
+
+/// Pairs of positions of keys unique on both sides, reduced to the longest
+/// run that is increasing on both.
+fn unique_anchors(a: &[Key], b: &[Key]) -> Vec<(usize, usize)> {
+    // Occurrences and first position of each key
+    fn count(keys: &[Key]) -> HashMap<&Key, (usize, usize)> {
+        let mut counts: HashMap<&Key, (usize, usize)> = HashMap::new();
+        for (i, key) in keys.iter().enumerate() {
+            counts.entry(key).or_insert((0, i)).0 += 1;
+        }
+        counts
+    }
+    let (counts_a, counts_b) = (count(a), count(b));
+    let mut pairs: Vec<(usize, usize)> = counts_a
+        .iter()
+        .filter(|(_, &(n, _))| n == 1)
+        .filter_map(|(key, &(_, i))| match counts_b.get(key) {
+            Some(&(1, j)) => Some((i, j)),
+            _ => None,
+        })
+        .collect();
+    pairs.sort_unstable();
+
+    // Longest increasing subsequence of the right-hand positions
+    let mut tails: Vec<usize> = Vec::new();
+    let mut previous: Vec<Option<usize>> = vec![None; pairs.len()];
+    for (p, &(_, j)) in pairs.iter().enumerate() {
+        let slot = tails.partition_point(|&t| pairs[t].1 < j);
+        previous[p] = slot.checked_sub(1).map(|s| tails[s]);
+        match tails.get_mut(slot) {
+            Some(tail) => *tail = p,
+            None => tails.push(p),
+        }
+    }
+    let mut anchors = Vec::new();
+    let mut current = tails.last().copied();
+    while let Some(p) = current {
+        anchors.push(pairs[p]);
+        current = previous[p];
+    }
+    anchors.reverse();
+    anchors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(uuid: &str, role: &'static str, text: &str) -> DiffMessage {
+        DiffMessage {
+            uuid: Some(uuid.to_string()),
+            line_number: 0,
+            role,
+            text: text.to_string(),
+            timestamp: None,
+        }
+    }
+
+    #[test]
+    fn test_diff_aligns_by_uuid_then_content() {
+        let (user, claude) = ("👤 USER", "🤖 CLAUDE");
+        let left = vec![
+            message("u1", user, "fix the bug"),
+            message("a1", claude, "Looking."),
+            message("u2", user, "try again"),
+            message("a2", claude, "Done  with\nthe fix"),
+            message("u3", user, "thanks"),
+        ];
+        // A resumed copy: same prefix, a different reply, then a content-equal
+        // message under a new UUID and one extra message
+        let right = vec![
+            message("u1", user, "fix the bug"),
+            message("a1", claude, "Looking."),
+            message("u2", user, "try again"),
+            message("b2", claude, "Another approach"),
+            message("b3", claude, "Done with the fix"),
+            message("b4", user, "ship it"),
+        ];
+
+        let diff = SessionDiff::from_messages(left, right);
+        assert_eq!(diff.shared_prefix(), 3);
+        assert_eq!(diff.divergence(), Some(3));
+        let (left_at, right_at) = diff.divergence_messages();
+        assert_eq!(left_at.map(|m| m.text.as_str()), Some("Done  with\nthe fix"));
+        assert_eq!(right_at.map(|m| m.text.as_str()), Some("Another approach"));
+        assert_eq!(
+            diff.rows[3..],
+            [
+                DiffRow::RightOnly(3),
+                DiffRow::Shared { left: 3, right: 4, by_uuid: false },
+                DiffRow::LeftOnly(4),
+                DiffRow::RightOnly(5),
+            ]
+        );
+        assert_eq!((diff.shared_count(), diff.left_only_count(), diff.right_only_count()), (4, 1, 2));
+
+        let same = SessionDiff::from_messages(diff.left.clone(), diff.left.clone());
+        assert_eq!(same.divergence(), None);
+        assert!(same.rows.iter().all(|row| matches!(row, DiffRow::Shared { by_uuid: true, .. })));
+    }
+}
@@ -0,0 +1,141 @@
+use crate::anonymize::Anonymizer;
+use crate::conversation::ConversationTree;
+use crate::models::Session;
+use crate::transcript::{read_entries, TranscriptEntry};
+use anyhow::{anyhow, Result};
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    /// The readable active branch of each session.
+    Markdown,
+    /// The raw transcript entries, concatenated in order.
+    Jsonl,
+}
+
+impl ExportFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "md" | "markdown" => Some(ExportFormat::Markdown),
+            "jsonl" => Some(ExportFormat::Jsonl),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Jsonl => "jsonl",
+        }
+    }
+}
+
+/// A session transcript loaded for export.
+#[derive(Debug, Clone)]
+pub struct ExportedTranscript {
+    pub session: Session,
+    pub entries: Vec<TranscriptEntry>,
+}
+
+pub fn load_transcripts(sessions: &[Session]) -> Result<Vec<ExportedTranscript>> {
+    sessions
+        .iter()
+        .map(|session| {
+            let entries = read_entries(&session.path)
+                .map_err(|e| anyhow!("Failed to read {}: {}", session.path.display(), e))?;
+            Ok(ExportedTranscript { session: session.clone(), entries })
+        })
+        .collect()
+}
+
+/// Renders one or more transcripts as a single document. Several transcripts
+/// are treated as consecutive parts of one conversation.
+pub fn render(transcripts: &[ExportedTranscript], format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Jsonl => {
+            let mut output = String::new();
+            for entry in transcripts.iter().flat_map(|t| &t.entries) {
+                output.push_str(&entry.value.to_string());
+                output.push('\n');
+            }
+            output
+        }
+        ExportFormat::Markdown => {
+            let mut sections = vec!["# Claude Code conversation".to_string()];
+            for (number, transcript) in transcripts.iter().enumerate() {
+                if transcripts.len() > 1 {
+                    sections.push(format!(
+                        "## Part {} of {}: {}",
+                        number + 1,
+                        transcripts.len(),
+                        transcript.session.name
+                    ));
+                }
+                sections.push(ConversationTree::build(transcript.entries.clone()).format(false));
+            }
+            sections.join("\n\n") + "\n"
+        }
+    }
+}
+
+/// Writes the sessions, oldest first, to `destination` and returns the number
+/// of entries exported. With an anonymizer, its report afterwards lists every
+/// value replaced in the written file.
+pub fn export_sessions(
+    sessions: &[Session],
+    format: ExportFormat,
+    destination: &Path,
+    anonymizer: Option<&mut Anonymizer>,
+) -> Result<usize> {
+    let mut transcripts = load_transcripts(sessions)?;
+    if let Some(anonymizer) = anonymizer {
+        anonymizer.apply(&mut transcripts)?;
+    }
+    fs::write(destination, render(&transcripts, format))?;
+    Ok(transcripts.iter().map(|t| t.entries.len()).sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::path::PathBuf;
+    use std::time::SystemTime;
+
+    fn transcript(name: &str, texts: &[&str]) -> ExportedTranscript {
+        let entries = texts
+            .iter()
+            .enumerate()
+            .map(|(i, text)| {
+                let parent = if i == 0 { None } else { Some(format!("{}-{}", name, i - 1)) };
+                TranscriptEntry::from_value(
+                    i + 1,
+                    json!({
+                        "type": "user",
+                        "uuid": format!("{}-{}", name, i),
+                        "parentUuid": parent,
+                        "message": { "role": "user", "content": text },
+                    }),
+                )
+            })
+            .collect();
+        ExportedTranscript {
+            session: Session::new(name.to_string(), PathBuf::from(name), 0, SystemTime::now()),
+            entries,
+        }
+    }
+
+    #[test]
+    fn test_render_chain_in_order() {
+        let transcripts = vec![transcript("first", &["one", "two"]), transcript("second", &["three"])];
+
+        let jsonl = render(&transcripts, ExportFormat::Jsonl);
+        assert_eq!(jsonl.lines().count(), 3);
+        assert!(jsonl.find("one").unwrap() < jsonl.find("three").unwrap());
+
+        let markdown = render(&transcripts, ExportFormat::Markdown);
+        assert!(markdown.contains("## Part 2 of 2: second"));
+        assert!(markdown.find("two").unwrap() < markdown.find("three").unwrap());
+    }
+}
@@ -0,0 +1,132 @@
+use anyhow::{anyhow, Result};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The working tree root of the git repository containing `dir`: the first
+/// directory at or above it that holds a `.git` directory or file.
+pub fn find_repository(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors().find(|d| d.join(".git").exists()).map(|d| d.to_path_buf())
+}
+
+/// Names of the branches that exist in the repository at `root`, read from
+/// its files without running git: local branches, remote-tracking branches
+/// without their remote's name, and the branch HEAD is on even before its
+/// first commit. Linked worktrees and submodules, whose `.git` is a file
+/// pointing elsewhere, are followed. Repositories using the reftable
+/// format cannot be read and return an error.
+pub fn branch_names(root: &Path) -> Result<HashSet<String>> {
+    let dot_git = root.join(".git");
+    let git_dir = if dot_git.is_file() {
+        let content = fs::read_to_string(&dot_git)?;
+        let target = content
+            .trim()
+            .strip_prefix("gitdir:")
+            .ok_or_else(|| anyhow!("{} does not point to a git directory", dot_git.display()))?;
+        root.join(target.trim())
+    } else {
+        dot_git
+    };
+    // Worktrees keep their HEAD but share the refs of the main repository
+    let common_dir = match fs::read_to_string(git_dir.join("commondir")) {
+        Ok(common) => git_dir.join(common.trim()),
+        Err(_) => git_dir.clone(),
+    };
+    if common_dir.join("reftable").is_dir() {
+        return Err(anyhow!("{} stores its refs in the reftable format", root.display()));
+    }
+    if !common_dir.join("refs").is_dir() {
+        return Err(anyhow!("{} is not a git repository", root.display()));
+    }
+
+    let mut names = HashSet::new();
+    let mut add = |reference: &str| {
+        if let Some(branch) = branch_of(reference) {
+            names.insert(branch.to_string());
+        }
+    };
+
+    if let Ok(head) = fs::read_to_string(git_dir.join("HEAD")) {
+        if let Some(reference) = head.trim().strip_prefix("ref:") {
+            add(reference.trim());
+        }
+    }
+    if let Ok(packed) = fs::read_to_string(common_dir.join("packed-refs")) {
+        packed
+            .lines()
+            .filter(|line| !line.starts_with(['#', '^']))
+            .filter_map(|line| line.split_whitespace().nth(1))
+            .for_each(&mut add);
+    }
+    let mut loose = Vec::new();
+    list_refs(&common_dir.join("refs"), "refs", &mut loose)?;
+    loose.iter().for_each(|reference| add(reference));
+
+    Ok(names)
+}
+
+/// The branch a full ref name stands for: `refs/heads/<branch>` or
+/// `refs/remotes/<remote>/<branch>`, except a remote's `HEAD`.
+fn branch_of(reference: &str) -> Option<&str> {
+    if let Some(branch) = reference.strip_prefix("refs/heads/") {
+        return Some(branch);
+    }
+    let (_, branch) = reference.strip_prefix("refs/remotes/")?.split_once('/')?;
+    (branch != "HEAD").then_some(branch)
+}
+
+/// Collects the full names of the loose refs stored as files under `dir`.
+fn list_refs(dir: &Path, prefix: &str, refs: &mut Vec<String>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = format!("{}/{}", prefix, entry.file_name().to_string_lossy());
+        if entry.file_type()?.is_dir() {
+            list_refs(&entry.path(), &name, refs)?;
+        } else {
+            refs.push(name);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_branch_names_reads_loose_packed_and_worktree_refs() {
+        let root = std::env::temp_dir().join(format!("cc-session-manager-git-{}", std::process::id()));
+        let git_dir = root.join("repo/.git");
+        fs::create_dir_all(git_dir.join("refs/heads/feat")).unwrap();
+        fs::create_dir_all(git_dir.join("refs/remotes/origin")).unwrap();
+        fs::write(git_dir.join("HEAD"), "ref: refs/heads/unborn\n").unwrap();
+        fs::write(git_dir.join("refs/heads/main"), "0000\n").unwrap();
+        fs::write(git_dir.join("refs/heads/feat/login"), "0000\n").unwrap();
+        fs::write(git_dir.join("refs/remotes/origin/HEAD"), "ref: refs/remotes/origin/main\n").unwrap();
+        fs::write(
+            git_dir.join("packed-refs"),
+            "# pack-refs with: peeled fully-peeled sorted\n0000 refs/remotes/origin/fix/typo\n^1111\n0000 refs/tags/v1\n",
+        )
+        .unwrap();
+
+        let mut names: Vec<String> = branch_names(&root.join("repo")).unwrap().into_iter().collect();
+        names.sort();
+        assert_eq!(names, ["feat/login", "fix/typo", "main", "unborn"]);
+
+        // A linked worktree: its own HEAD, the main repository's refs
+        let worktree_git = git_dir.join("worktrees/wt");
+        fs::create_dir_all(&worktree_git).unwrap();
+        fs::write(worktree_git.join("HEAD"), "ref: refs/heads/wip\n").unwrap();
+        fs::write(worktree_git.join("commondir"), "../..\n").unwrap();
+        fs::create_dir_all(root.join("wt/src")).unwrap();
+        fs::write(root.join("wt/.git"), format!("gitdir: {}\n", worktree_git.display())).unwrap();
+
+        let worktree = find_repository(&root.join("wt/src")).unwrap();
+        assert_eq!(worktree, root.join("wt"));
+        let names = branch_names(&worktree).unwrap();
+        assert!(names.contains("wip") && names.contains("main") && !names.contains("unborn"));
+
+        assert!(branch_names(&root).is_err());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,150 @@
+use crate::models::IntegrityIssue;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+/// One non-blank line of a transcript, classified for validation.
+struct Line<'a> {
+    number: usize,
+    text: &'a str,
+    value: Option<Value>,
+}
+
+fn parse_lines(content: &str) -> Vec<Line<'_>> {
+    content
+        .split('\n')
+        .enumerate()
+        .filter(|(_, text)| !text.trim().is_empty())
+        .map(|(index, text)| Line {
+            number: index + 1,
+            text,
+            value: serde_json::from_str::<Value>(text.trim()).ok().filter(|v| v.is_object()),
+        })
+        .collect()
+}
+
+fn field<'a>(value: &'a Value, name: &str) -> Option<&'a str> {
+    value.get(name).and_then(|v| v.as_str())
+}
+
+/// Checks a transcript and returns the number of valid entries together with
+/// every issue found, in line order per kind.
+pub fn validate(content: &str) -> (usize, Vec<IntegrityIssue>) {
+    let lines = parse_lines(content);
+    let mut issues = Vec::new();
+
+    let last_line = lines.last().map(|line| line.number);
+    for line in lines.iter().filter(|line| line.value.is_none()) {
+        if Some(line.number) == last_line && !content.ends_with('\n') {
+            issues.push(IntegrityIssue::TruncatedTail { line: line.number });
+        } else {
+            issues.push(IntegrityIssue::InvalidLine { line: line.number });
+        }
+    }
+
+    let valid: Vec<(usize, &Value)> = lines
+        .iter()
+        .filter_map(|line| line.value.as_ref().map(|value| (line.number, value)))
+        .collect();
+
+    let mut lines_by_uuid: HashMap<&str, Vec<usize>> = HashMap::new();
+    let mut uuid_order = Vec::new();
+    for (number, value) in &valid {
+        if let Some(uuid) = field(value, "uuid") {
+            let lines = lines_by_uuid.entry(uuid).or_default();
+            if lines.is_empty() {
+                uuid_order.push(uuid);
+            }
+            lines.push(*number);
+        }
+    }
+    for uuid in uuid_order {
+        let lines = &lines_by_uuid[uuid];
+        if lines.len() > 1 {
+            issues.push(IntegrityIssue::DuplicateUuid {
+                uuid: uuid.to_string(),
+                lines: lines.clone(),
+            });
+        }
+    }
+
+    for (number, value) in &valid {
+        if let Some(parent_uuid) = field(value, "parentUuid") {
+            if !lines_by_uuid.contains_key(parent_uuid) {
+                issues.push(IntegrityIssue::BrokenParent {
+                    line: *number,
+                    parent_uuid: parent_uuid.to_string(),
+                });
+            }
+        }
+    }
+
+    (valid.len(), issues)
+}
+
+/// Rebuilds a transcript from its valid entries only: invalid and truncated
+/// lines are dropped, and only the first line of a repeated `uuid` is kept.
+/// Returns the new content and the number of lines removed.
+pub fn repair(content: &str) -> (String, usize) {
+    let lines = parse_lines(content);
+    let mut seen = HashSet::new();
+    let mut output = String::new();
+    let mut removed = 0;
+
+    for line in &lines {
+        let keep = match &line.value {
+            Some(value) => match field(value, "uuid") {
+                Some(uuid) => seen.insert(uuid.to_string()),
+                None => true,
+            },
+            None => false,
+        };
+        if keep {
+            output.push_str(line.text.trim_end_matches('\r'));
+            output.push('\n');
+        } else {
+            removed += 1;
+        }
+    }
+
+    (output, removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = concat!(
+        r#"{"type":"user","uuid":"u1"}"#, "\n",
+        "not json\n",
+        r#"{"type":"assistant","uuid":"a1","parentUuid":"u1"}"#, "\n",
+        r#"{"type":"assistant","uuid":"a1","parentUuid":"u1"}"#, "\n",
+        r#"{"type":"user","uuid":"u2","parentUuid":"missing"}"#, "\n",
+        r#"{"type":"assistant","uuid":"a2","par"#,
+    );
+
+    #[test]
+    fn test_validate_reports_each_issue() {
+        let (valid, issues) = validate(CONTENT);
+
+        assert_eq!(valid, 4);
+        assert_eq!(
+            issues,
+            vec![
+                IntegrityIssue::InvalidLine { line: 2 },
+                IntegrityIssue::TruncatedTail { line: 6 },
+                IntegrityIssue::DuplicateUuid { uuid: "a1".to_string(), lines: vec![3, 4] },
+                IntegrityIssue::BrokenParent { line: 5, parent_uuid: "missing".to_string() },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_repair_keeps_valid_entries() {
+        let (repaired, removed) = repair(CONTENT);
+
+        assert_eq!(removed, 3);
+        assert_eq!(repaired.lines().count(), 3);
+        let (_, issues) = validate(&repaired);
+        assert!(issues.iter().all(|issue| !issue.is_repairable()));
+    }
+}
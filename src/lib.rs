@@ -1,12 +1,32 @@
 #[cfg(feature = "cli")]
+pub mod tui;
+#[cfg(feature = "cli")]
 pub mod ui;
+#[cfg(feature = "cli")]
+pub mod viewer;
 
+pub mod activity;
+pub mod anonymize;
+pub mod config;
+pub mod conversation;
+pub mod diff;
+pub mod export;
+pub mod git;
+pub mod integrity;
+pub mod listing;
 pub mod models;
+pub mod ranking;
+pub mod secrets;
 pub mod session_manager;
+pub mod tools;
+pub mod transcript;
 pub mod utils;
 
 #[cfg(feature = "gui")]
 pub mod commands;
 
-pub use models::{MenuChoice, Project, Session, Statistics};
+pub use models::{
+    Artifact, ArtifactCategory, MenuChoice, Project, ProjectAction, RankingAction, Session, SessionAction,
+    Statistics,
+};
 pub use session_manager::SessionManager;
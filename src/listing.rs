@@ -0,0 +1,365 @@
+use crate::models::{Project, Session};
+use crate::utils::parse_size;
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use std::cmp::Reverse;
+use std::time::SystemTime;
+
+/// Orders for the CLI project and session lists.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SortKey {
+    /// Alphabetical.
+    Name,
+    /// Largest first.
+    Size,
+    /// Oldest first: by the oldest session for projects.
+    Age,
+    /// Most sessions first; projects only.
+    SessionCount,
+    /// Most recently written first.
+    LastActivity,
+}
+
+impl SortKey {
+    pub const PROJECT_KEYS: [SortKey; 5] = [
+        SortKey::Name,
+        SortKey::Size,
+        SortKey::Age,
+        SortKey::SessionCount,
+        SortKey::LastActivity,
+    ];
+    pub const SESSION_KEYS: [SortKey; 4] = [SortKey::Name, SortKey::Size, SortKey::Age, SortKey::LastActivity];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SortKey::Name => "name",
+            SortKey::Size => "size",
+            SortKey::Age => "age",
+            SortKey::SessionCount => "session count",
+            SortKey::LastActivity => "last activity",
+        }
+    }
+
+    /// The key typed to choose this order, e.g. `s` for size.
+    pub fn shortcut(&self) -> char {
+        match self {
+            SortKey::Name => 'n',
+            SortKey::Size => 's',
+            SortKey::Age => 'a',
+            SortKey::SessionCount => 'c',
+            SortKey::LastActivity => 'l',
+        }
+    }
+}
+
+/// Narrows a list by text, git branch and size and age ranges, parsed from
+/// input like `api* branch:feat/* size>1M age<30`. Words without a prefix are
+/// matched as a case-insensitive substring, or as a glob when they contain
+/// `*` or `?`. `branch:` takes a branch name or glob, or `-` for sessions
+/// without a recorded branch.
+#[derive(Debug, Clone, Default)]
+pub struct ListFilter {
+    /// The input the filter was parsed from.
+    pub text: String,
+    patterns: Vec<TextPattern>,
+    branches: Vec<BranchPattern>,
+    min_size: Option<u64>,
+    max_size: Option<u64>,
+    min_age_days: Option<u64>,
+    max_age_days: Option<u64>,
+}
+
+#[derive(Debug, Clone)]
+enum TextPattern {
+    Substring(String),
+    Glob(Regex),
+}
+
+impl TextPattern {
+    fn matches(&self, text: &str) -> bool {
+        match self {
+            TextPattern::Substring(needle) => text.to_lowercase().contains(needle),
+            TextPattern::Glob(regex) => regex.is_match(text),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum BranchPattern {
+    None,
+    Name(Regex),
+}
+
+impl BranchPattern {
+    fn matches(&self, branch: Option<&str>) -> bool {
+        match (self, branch) {
+            (BranchPattern::None, branch) => branch.is_none(),
+            (BranchPattern::Name(regex), Some(branch)) => regex.is_match(branch),
+            (BranchPattern::Name(_), None) => false,
+        }
+    }
+}
+
+impl ListFilter {
+    /// A filter for the sessions of one branch, `None` for those without one.
+    pub fn for_branch(branch: Option<&str>) -> Result<Self> {
+        Self::parse(&format!("branch:{}", branch.unwrap_or("-")))
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut filter = ListFilter {
+            text: text.trim().to_string(),
+            ..Default::default()
+        };
+
+        for word in text.split_whitespace() {
+            let lower = word.to_lowercase();
+            // Only `size` or `age` directly followed by a bound is a range; `agent` is text
+            let range = |name: &str| lower.strip_prefix(name).filter(|rest| rest.starts_with(['<', '>']));
+            if let Some(range) = range("size") {
+                let (bound, value) = split_bound(range).ok_or_else(|| anyhow!("Expected size>N or size<N: {}", word))?;
+                let size = parse_size(value).ok_or_else(|| anyhow!("Invalid size: {}", value))?;
+                match bound {
+                    '>' => filter.min_size = Some(size),
+                    _ => filter.max_size = Some(size),
+                }
+            } else if let Some(range) = range("age") {
+                let (bound, value) = split_bound(range).ok_or_else(|| anyhow!("Expected age>N or age<N: {}", word))?;
+                let days = value.parse().map_err(|_| anyhow!("Invalid number of days: {}", value))?;
+                match bound {
+                    '>' => filter.min_age_days = Some(days),
+                    _ => filter.max_age_days = Some(days),
+                }
+            } else if let Some(branch) = word.strip_prefix("branch:") {
+                filter.branches.push(match branch {
+                    "" => return Err(anyhow!("Expected branch:NAME or branch:-")),
+                    "-" => BranchPattern::None,
+                    _ => BranchPattern::Name(glob(branch)?),
+                });
+            } else if word.contains(['*', '?']) {
+                filter.patterns.push(TextPattern::Glob(glob(word)?));
+            } else {
+                filter.patterns.push(TextPattern::Substring(lower));
+            }
+        }
+
+        Ok(filter)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Matches a session by file name, title or path, git branch, total size
+    /// and days since it was last written.
+    pub fn matches_session(&self, session: &Session) -> bool {
+        let path = session.path.to_string_lossy();
+        let texts = [Some(session.name.as_str()), session.content_preview.as_deref(), Some(path.as_ref())];
+        let branch = session.metadata.git_branch.as_deref();
+        self.branches.iter().all(|pattern| pattern.matches(branch))
+            && self.matches(&texts, session.total_size(), session.get_age_days())
+    }
+
+    /// Matches a project by directory name, the real path it was run in, its
+    /// latest title, total size and days since its last activity. A branch
+    /// matches when any of the project's sessions is on it.
+    pub fn matches_project(&self, project: &Project, real_path: Option<&str>) -> bool {
+        let texts = [Some(project.name.as_str()), real_path, project.latest_content.as_deref()];
+        let age_days = last_activity(project)
+            .map(|modified| SystemTime::now().duration_since(modified).unwrap_or_default().as_secs() / (60 * 60 * 24))
+            .unwrap_or(0);
+        let on_branch = |pattern: &BranchPattern| {
+            project.sessions.iter().any(|s| pattern.matches(s.metadata.git_branch.as_deref()))
+        };
+        self.branches.iter().all(on_branch) && self.matches(&texts, project.total_size, age_days)
+    }
+
+    fn matches(&self, texts: &[Option<&str>], size: u64, age_days: u64) -> bool {
+        let within =
+            |value: u64, min: Option<u64>, max: Option<u64>| min.unwrap_or(0) <= value && value <= max.unwrap_or(u64::MAX);
+        self.patterns
+            .iter()
+            .all(|pattern| texts.iter().flatten().any(|text| pattern.matches(text)))
+            && within(size, self.min_size, self.max_size)
+            && within(age_days, self.min_age_days, self.max_age_days)
+    }
+}
+
+/// A case-insensitive regex matching all of `word`, with `*` and `?` as wildcards.
+fn glob(word: &str) -> Result<Regex> {
+    let pattern = regex::escape(word).replace(r"\*", ".*").replace(r"\?", ".");
+    Ok(Regex::new(&format!("(?i)^{}$", pattern))?)
+}
+
+fn split_bound(range: &str) -> Option<(char, &str)> {
+    let bound = range.chars().next().filter(|c| matches!(c, '>' | '<'))?;
+    let value = range[1..].trim_start_matches('=');
+    (!value.is_empty()).then_some((bound, value))
+}
+
+fn last_activity(project: &Project) -> Option<SystemTime> {
+    project.sessions.iter().map(|s| s.modified).max()
+}
+
+/// A project's sessions on one git branch.
+#[derive(Debug, Clone)]
+pub struct BranchGroup {
+    /// `None` for sessions that recorded no branch.
+    pub branch: Option<String>,
+    /// Indices into the project's sessions, most recently written first.
+    pub sessions: Vec<usize>,
+    pub size: u64,
+    pub last_activity: SystemTime,
+}
+
+/// Groups sessions by the branch they were last on, most recently active
+/// branch first, with sessions without a branch last.
+pub fn group_by_branch(sessions: &[Session]) -> Vec<BranchGroup> {
+    let mut groups: Vec<BranchGroup> = Vec::new();
+    for i in sort_sessions(sessions, SortKey::LastActivity) {
+        let session = &sessions[i];
+        let branch = session.metadata.git_branch.as_ref();
+        match groups.iter_mut().find(|group| group.branch.as_ref() == branch) {
+            Some(group) => {
+                group.sessions.push(i);
+                group.size += session.total_size();
+            }
+            None => groups.push(BranchGroup {
+                branch: branch.cloned(),
+                sessions: vec![i],
+                size: session.total_size(),
+                last_activity: session.modified,
+            }),
+        }
+    }
+    // Stable, so the branches keep their order by last activity
+    groups.sort_by_key(|group| group.branch.is_none());
+    groups
+}
+
+/// Indices of the sessions in the given order.
+pub fn sort_sessions(sessions: &[Session], key: SortKey) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..sessions.len()).collect();
+    match key {
+        SortKey::Name => order.sort_by(|&a, &b| sessions[a].name.cmp(&sessions[b].name)),
+        SortKey::Size => order.sort_by_key(|&i| Reverse(sessions[i].total_size())),
+        SortKey::Age => order.sort_by_key(|&i| sessions[i].modified),
+        SortKey::SessionCount | SortKey::LastActivity => order.sort_by_key(|&i| Reverse(sessions[i].modified)),
+    }
+    order
+}
+
+/// Indices of the projects in the given order. Projects without sessions
+/// sort last by age and activity.
+pub fn sort_projects(projects: &[Project], key: SortKey) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..projects.len()).collect();
+    match key {
+        SortKey::Name => order.sort_by(|&a, &b| projects[a].name.cmp(&projects[b].name)),
+        SortKey::Size => order.sort_by_key(|&i| Reverse(projects[i].total_size)),
+        SortKey::Age => order.sort_by_key(|&i| {
+            let oldest = projects[i].sessions.iter().map(|s| s.modified).min();
+            (oldest.is_none(), oldest)
+        }),
+        SortKey::SessionCount => order.sort_by_key(|&i| Reverse(projects[i].sessions.len())),
+        SortKey::LastActivity => order.sort_by_key(|&i| Reverse(last_activity(&projects[i]))),
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    #[test]
+    fn test_filter_and_sort() {
+        let days_ago = |days: u64| SystemTime::now() - Duration::from_secs(days * 24 * 60 * 60 + 60);
+        let session = |name: &str, size, days, preview: &str| {
+            let mut session = Session::new(name.to_string(), PathBuf::from(name), size, days_ago(days));
+            session.content_preview = Some(preview.to_string());
+            session
+        };
+        let sessions = vec![
+            session("b.jsonl", 2048, 40, "Fix the API client"),
+            session("a.jsonl", 100, 1, "Write docs"),
+            session("c.jsonl", 5 << 20, 10, "Refactor api routes for the agent"),
+        ];
+
+        let matching = |text: &str| -> Vec<&str> {
+            let filter = ListFilter::parse(text).unwrap();
+            sessions.iter().filter(|s| filter.matches_session(s)).map(|s| s.name.as_str()).collect()
+        };
+        assert_eq!(matching("api"), ["b.jsonl", "c.jsonl"]);
+        assert_eq!(matching("api size>1K age<=30"), ["c.jsonl"]);
+        assert_eq!(matching("?.jsonl age>5 size<4KB"), ["b.jsonl"]);
+        assert_eq!(matching("write*"), ["a.jsonl"]);
+        assert!(ListFilter::parse("size>lots").is_err());
+        assert!(ListFilter::parse("age=5").is_ok_and(|f| f.min_age_days.is_none() && f.max_age_days.is_none()));
+        // Words that merely start with `size` or `age` are searched as text
+        assert_eq!(matching("agent"), ["c.jsonl"]);
+        assert_eq!(matching("Agenda"), Vec::<&str>::new());
+        assert_eq!(matching("sizing ages"), Vec::<&str>::new());
+        assert!(ListFilter::parse("").unwrap().is_empty());
+
+        assert_eq!(sort_sessions(&sessions, SortKey::Name), [1, 0, 2]);
+        assert_eq!(sort_sessions(&sessions, SortKey::Size), [2, 0, 1]);
+        assert_eq!(sort_sessions(&sessions, SortKey::Age), [0, 2, 1]);
+        assert_eq!(sort_sessions(&sessions, SortKey::LastActivity), [1, 2, 0]);
+
+        let mut busy = Project::new("busy".to_string(), PathBuf::from("busy"));
+        busy.add_session(sessions[1].clone());
+        busy.add_session(sessions[0].clone());
+        let empty = Project::new("empty".to_string(), PathBuf::from("empty"));
+        let mut big = Project::new("big".to_string(), PathBuf::from("big"));
+        big.add_session(sessions[2].clone());
+        let projects = [busy, empty, big];
+
+        assert_eq!(sort_projects(&projects, SortKey::SessionCount), [0, 2, 1]);
+        assert_eq!(sort_projects(&projects, SortKey::Age), [0, 2, 1]);
+        assert_eq!(sort_projects(&projects, SortKey::LastActivity), [0, 2, 1]);
+        assert_eq!(sort_projects(&projects, SortKey::Size), [2, 0, 1]);
+
+        let filter = ListFilter::parse("/home/*/app").unwrap();
+        assert!(filter.matches_project(&projects[2], Some("/home/user/app")));
+        assert!(!filter.matches_project(&projects[2], None));
+    }
+
+    #[test]
+    fn test_group_and_filter_by_branch() {
+        let session = |name: &str, size, minutes: u64, branch: Option<&str>| {
+            let modified = SystemTime::now() - Duration::from_secs(minutes * 60);
+            let mut session = Session::new(name.to_string(), PathBuf::from(name), size, modified);
+            session.metadata.git_branch = branch.map(|b| b.to_string());
+            session
+        };
+        let sessions = vec![
+            session("a.jsonl", 100, 30, Some("main")),
+            session("b.jsonl", 200, 5, Some("feat/login")),
+            session("c.jsonl", 300, 1, None),
+            session("d.jsonl", 400, 10, Some("main")),
+        ];
+
+        let groups = group_by_branch(&sessions);
+        let summary: Vec<(Option<&str>, &[usize], u64)> =
+            groups.iter().map(|g| (g.branch.as_deref(), g.sessions.as_slice(), g.size)).collect();
+        assert_eq!(
+            summary,
+            [(Some("feat/login"), &[1][..], 200), (Some("main"), &[3, 0][..], 500), (None, &[2][..], 300)]
+        );
+        assert_eq!(groups[1].last_activity, sessions[3].modified);
+
+        let matching = |filter: ListFilter| -> Vec<&str> {
+            sessions.iter().filter(|s| filter.matches_session(s)).map(|s| s.name.as_str()).collect()
+        };
+        assert_eq!(matching(ListFilter::parse("branch:feat/*").unwrap()), ["b.jsonl"]);
+        assert_eq!(matching(ListFilter::for_branch(Some("main")).unwrap()), ["a.jsonl", "d.jsonl"]);
+        assert_eq!(matching(ListFilter::for_branch(None).unwrap()), ["c.jsonl"]);
+        assert!(ListFilter::parse("branch:").is_err());
+
+        let mut project = Project::new("app".to_string(), PathBuf::from("app"));
+        project.add_session(sessions[0].clone());
+        assert!(ListFilter::parse("branch:MAIN").unwrap().matches_project(&project, None));
+        assert!(!ListFilter::parse("branch:feat/*").unwrap().matches_project(&project, None));
+    }
+}
@@ -1,16 +1,19 @@
-mod models;
-mod session_manager;
-mod ui;
-mod utils;
-
 use anyhow::Result;
-use models::MenuChoice;
-use session_manager::SessionManager;
-use ui::UI;
+use cc_session_manager::export;
+use cc_session_manager::transcript;
+use cc_session_manager::utils::format_size;
+use cc_session_manager::tui::{self, TuiExit};
+use cc_session_manager::ui::UI;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use cc_session_manager::{MenuChoice, Project, ProjectAction, RankingAction, Session, SessionAction, SessionManager};
+
+/// How many sessions and projects each list of the store-wide ranking shows.
+const LARGEST_LIMIT: usize = 15;
 
 fn main() -> Result<()> {
     let mut session_manager = SessionManager::new()?;
-    let ui = UI::new();
+    let mut ui = UI::new();
 
     // Check if Claude directory exists
     if session_manager.find_claude_directory()?.is_none() {
@@ -18,6 +21,17 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
+    // The full-screen browser needs a real terminal; `--menu` or piped input
+    // goes straight to the numbered menus
+    let interactive = std::io::stdin().is_terminal() && std::io::stdout().is_terminal();
+    if interactive && !std::env::args().any(|arg| arg == "--menu") {
+        match tui::run(&mut session_manager) {
+            Ok(TuiExit::Quit) => return Ok(()),
+            Ok(TuiExit::Menu) => {}
+            Err(e) => ui.show_error(&format!("Full-screen mode is unavailable, using menus: {}", e)),
+        }
+    }
+
     loop {
         match ui.show_main_menu()? {
             MenuChoice::Statistics => {
@@ -26,12 +40,79 @@ fn main() -> Result<()> {
             }
             MenuChoice::ManageProjects => {
                 let projects = session_manager.scan_projects()?;
-                if let Some(project_index) = ui.show_projects(&projects)? {
+                if let Some(project_index) = choose_project(&mut ui, &session_manager, &projects)? {
                     let project = &projects[project_index];
                     let mut ui_clone = ui.clone();
-                    match ui_clone.show_sessions(project) {
-                        Ok(selected_indices) => {
-                            if !selected_indices.is_empty() {
+                    let chains = session_manager.find_session_chains(&project.sessions);
+                    loop {
+                        match ui_clone.show_sessions(project, &chains) {
+                            Ok(SessionAction::View(index)) => {
+                                let session = &project.sessions[index];
+                                match session_manager.load_conversation(&session.path) {
+                                    Ok(tree) => ui.show_conversation(session, &tree)?,
+                                    Err(e) => {
+                                        ui.show_error(&format!("Failed to read session: {}", e));
+                                    }
+                                }
+                            }
+                            Ok(SessionAction::Resume(index)) => {
+                                let session = &project.sessions[index];
+                                let command = match session_manager.resume_command(&session.path) {
+                                    Ok(command) => command,
+                                    Err(e) => {
+                                        ui.show_error(&format!("Cannot resume session: {}", e));
+                                        continue;
+                                    }
+                                };
+                                if ui.confirm_resume(session, &command)? {
+                                    match command.run() {
+                                        Ok(status) if status.success() => {}
+                                        Ok(status) => ui.show_error(&format!("{} exited with {}", command.program, status)),
+                                        Err(e) => ui.show_error(&format!("Failed to run {}: {}", command.program, e)),
+                                    }
+                                }
+                            }
+                            Ok(SessionAction::Diff(left, right)) => {
+                                let (left, right) = (&project.sessions[left], &project.sessions[right]);
+                                match session_manager.diff_sessions(&left.path, &right.path) {
+                                    Ok(diff) => ui.show_session_diff(left, right, &diff)?,
+                                    Err(e) => {
+                                        ui.show_error(&format!("Failed to compare sessions: {}", e));
+                                    }
+                                }
+                            }
+                            Ok(SessionAction::Export(indices)) => {
+                                let sessions: Vec<_> =
+                                    indices.iter().map(|&i| project.sessions[i].clone()).collect();
+                                let default_name = sessions
+                                    .last()
+                                    .and_then(|s| s.path.file_stem())
+                                    .map(|stem| stem.to_string_lossy().to_string())
+                                    .unwrap_or_else(|| "session".to_string());
+                                if let Some((format, path, anonymize)) = ui.prompt_export(&default_name)? {
+                                    let mut anonymizer = None;
+                                    if anonymize {
+                                        match session_manager.anonymizer() {
+                                            Ok(a) => anonymizer = Some(a),
+                                            Err(e) => {
+                                                ui.show_error(&format!("Failed to export: {}", e));
+                                                continue;
+                                            }
+                                        }
+                                    }
+                                    match export::export_sessions(&sessions, format, &path, anonymizer.as_mut()) {
+                                        Ok(count) => match &anonymizer {
+                                            Some(anonymizer) => {
+                                                println!("✅ Exported {} anonymized entries to {}", count, path.display());
+                                                ui.show_anonymization_report(anonymizer.report())?;
+                                            }
+                                            None => ui.show_export_result(count, &path),
+                                        },
+                                        Err(e) => ui.show_error(&format!("Failed to export: {}", e)),
+                                    }
+                                }
+                            }
+                            Ok(SessionAction::Delete(selected_indices)) => {
                                 let selected_sessions: Vec<_> = selected_indices
                                     .iter()
                                     .map(|&i| project.sessions[i].clone())
@@ -44,10 +125,58 @@ fn main() -> Result<()> {
                                         ui.show_error(&format!("Failed to delete sessions: {}", e));
                                     }
                                 }
+                                break;
+                            }
+                            Ok(SessionAction::Archive(indices)) => {
+                                let sessions: Vec<Session> = indices.iter().map(|&i| project.sessions[i].clone()).collect();
+                                match session_manager.archive_sessions(&sessions) {
+                                    Ok(archived_count) => {
+                                        println!("✅ Archived {} sessions", archived_count);
+                                        std::thread::sleep(std::time::Duration::from_millis(2000));
+                                    }
+                                    Err(e) => ui.show_error(&format!("Failed to archive sessions: {}", e)),
+                                }
+                                break;
+                            }
+                            Ok(SessionAction::Move(indices)) => {
+                                let targets: Vec<Project> =
+                                    projects.iter().filter(|p| p.path != project.path).cloned().collect();
+                                let target = match choose_project(&mut ui, &session_manager, &targets)? {
+                                    Some(index) => &targets[index],
+                                    None => continue,
+                                };
+                                let suggested = target.sessions.iter().find_map(|s| transcript::read_cwd(&s.path));
+                                let new_cwd = match ui.prompt_move_cwd(indices.len(), target, suggested.as_deref())? {
+                                    Some(new_cwd) => new_cwd,
+                                    None => continue,
+                                };
+                                let results: Vec<_> = indices
+                                    .iter()
+                                    .map(|&i| {
+                                        let session = project.sessions[i].clone();
+                                        let result = session_manager.move_session(&session, &target.path, new_cwd.as_deref());
+                                        (session, result)
+                                    })
+                                    .collect();
+                                if ui.show_move_results(&results)? {
+                                    let trash_paths: Vec<PathBuf> = results
+                                        .iter()
+                                        .filter_map(|(_, result)| result.as_ref().ok())
+                                        .map(|outcome| outcome.trash_path.clone())
+                                        .collect();
+                                    match session_manager.empty_trash(&trash_paths) {
+                                        Ok(freed) => println!("✅ Deleted the originals ({})", format_size(freed)),
+                                        Err(e) => ui.show_error(&format!("Failed to empty the trash: {}", e)),
+                                    }
+                                    std::thread::sleep(std::time::Duration::from_millis(2000));
+                                }
+                                break;
+                            }
+                            Ok(SessionAction::Back) => break,
+                            Err(e) => {
+                                ui.show_error(&format!("An error occurred: {}", e));
+                                break;
                             }
-                        }
-                        Err(e) => {
-                            ui.show_error(&format!("An error occurred: {}", e));
                         }
                     }
                 }
@@ -97,9 +226,9 @@ fn main() -> Result<()> {
             }
             MenuChoice::DeleteProject => {
                 let projects = session_manager.scan_projects()?;
-                if let Some(project_index) = ui.show_projects(&projects)? {
+                if let Some(project_index) = choose_project(&mut ui, &session_manager, &projects)? {
                     let project = &projects[project_index];
-                    if ui.confirm_project_deletion(&project.name) {
+                    if ui.confirm_project_deletion(project) {
                         match session_manager.delete_project(project) {
                             Ok(_) => {
                                 ui.show_deletion_result(1, "project");
@@ -114,6 +243,283 @@ fn main() -> Result<()> {
                     }
                 }
             }
+            MenuChoice::CleanArtifacts => {
+                let usage = session_manager.get_artifact_usage()?;
+                if let Some(category) = ui.show_artifact_categories(&usage)? {
+                    let days = ui.prompt_artifact_age_days(category)?;
+                    let artifacts = session_manager.scan_artifacts(category)?;
+                    let old_artifacts = session_manager.filter_artifacts_by_age(&artifacts, days);
+
+                    if old_artifacts.is_empty() {
+                        ui.clear_screen()?;
+                        println!("No {} older than {} days found", category.dir_name(), days);
+                        std::thread::sleep(std::time::Duration::from_millis(2000));
+                        continue;
+                    }
+
+                    match ui.show_old_artifacts(&old_artifacts, category, days) {
+                        Ok(selected_indices) => {
+                            if !selected_indices.is_empty() {
+                                let selected_artifacts: Vec<_> = selected_indices
+                                    .iter()
+                                    .map(|&i| old_artifacts[i].clone())
+                                    .collect();
+                                match session_manager.delete_artifacts(&selected_artifacts) {
+                                    Ok(deleted_count) => {
+                                        ui.show_deletion_result(deleted_count, category.dir_name());
+                                    }
+                                    Err(e) => {
+                                        ui.show_error(&format!("Failed to delete artifacts: {}", e));
+                                    }
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            ui.show_error(&format!("An error occurred: {}", e));
+                        }
+                    }
+                }
+            }
+            MenuChoice::SweepOrphans => {
+                let orphans = session_manager.find_orphaned_artifacts()?;
+                if orphans.is_empty() {
+                    ui.clear_screen()?;
+                    println!("No orphaned artifacts found");
+                    std::thread::sleep(std::time::Duration::from_millis(2000));
+                    continue;
+                }
+
+                let orphan_refs: Vec<_> = orphans.iter().collect();
+                match ui.show_artifacts("Artifacts without a session", &orphan_refs) {
+                    Ok(selected_indices) => {
+                        if !selected_indices.is_empty() {
+                            let selected_artifacts: Vec<_> = selected_indices
+                                .iter()
+                                .map(|&i| orphans[i].clone())
+                                .collect();
+                            match session_manager.delete_artifacts(&selected_artifacts) {
+                                Ok(deleted_count) => {
+                                    ui.show_deletion_result(deleted_count, "orphaned artifacts");
+                                }
+                                Err(e) => {
+                                    ui.show_error(&format!("Failed to delete artifacts: {}", e));
+                                }
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        ui.show_error(&format!("An error occurred: {}", e));
+                    }
+                }
+            }
+            MenuChoice::FindDuplicates => {
+                let projects = session_manager.find_duplicate_sessions()?;
+                if projects.is_empty() {
+                    ui.clear_screen()?;
+                    println!("No duplicate sessions found");
+                    std::thread::sleep(std::time::Duration::from_millis(2000));
+                    continue;
+                }
+
+                match ui.show_duplicates(&projects) {
+                    Ok(selected_indices) => {
+                        if !selected_indices.is_empty() {
+                            let redundant: Vec<_> = projects
+                                .iter()
+                                .flat_map(|p| p.duplicates.iter())
+                                .map(|d| d.redundant.clone())
+                                .collect();
+                            let selected_sessions: Vec<_> = selected_indices
+                                .iter()
+                                .map(|&i| redundant[i].clone())
+                                .collect();
+                            match session_manager.delete_sessions(&selected_sessions) {
+                                Ok(deleted_count) => {
+                                    ui.show_deletion_result(deleted_count, "redundant sessions");
+                                }
+                                Err(e) => {
+                                    ui.show_error(&format!("Failed to delete sessions: {}", e));
+                                }
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        ui.show_error(&format!("An error occurred: {}", e));
+                    }
+                }
+            }
+            MenuChoice::CheckIntegrity => {
+                let reports = session_manager.check_integrity()?;
+                if reports.is_empty() {
+                    ui.clear_screen()?;
+                    println!("All transcripts are valid");
+                    std::thread::sleep(std::time::Duration::from_millis(2000));
+                    continue;
+                }
+
+                match ui.show_integrity_report(&reports) {
+                    Ok(selected_indices) => {
+                        let mut outcomes = Vec::new();
+                        for i in selected_indices {
+                            match session_manager.repair_session(&reports[i].session.path) {
+                                Ok(outcome) => outcomes.push(outcome),
+                                Err(e) => {
+                                    ui.show_error(&format!("Failed to repair {}: {}", reports[i].session.name, e));
+                                }
+                            }
+                        }
+                        if !outcomes.is_empty() {
+                            ui.show_repair_results(&outcomes);
+                        }
+                    }
+                    Err(e) => {
+                        ui.show_error(&format!("An error occurred: {}", e));
+                    }
+                }
+            }
+            MenuChoice::Doctor => {
+                let findings = session_manager.doctor();
+                ui.show_doctor_report(&findings)?;
+            }
+            MenuChoice::Activity => match session_manager.get_activity() {
+                Ok(activity) => ui.show_activity(&activity)?,
+                Err(e) => ui.show_error(&format!("Failed to read activity: {}", e)),
+            },
+            MenuChoice::ScanSecrets => {
+                let extra: Vec<_> = ui.prompt_secret_pattern()?.into_iter().collect();
+                let scanner = match session_manager.secret_scanner(&extra) {
+                    Ok(scanner) => scanner,
+                    Err(e) => {
+                        ui.show_error(&e.to_string());
+                        continue;
+                    }
+                };
+
+                let results = session_manager.scan_secrets(&scanner)?;
+                if results.is_empty() {
+                    ui.clear_screen()?;
+                    println!("No secrets found");
+                    std::thread::sleep(std::time::Duration::from_millis(2000));
+                    continue;
+                }
+
+                match ui.show_secrets(&results) {
+                    Ok(selected_indices) => {
+                        let mut outcomes = Vec::new();
+                        for i in selected_indices {
+                            match session_manager.redact_secrets(&results[i].session.path, &scanner) {
+                                Ok(outcome) => outcomes.push(outcome),
+                                Err(e) => {
+                                    ui.show_error(&format!("Failed to redact {}: {}", results[i].session.name, e));
+                                }
+                            }
+                        }
+                        if !outcomes.is_empty() {
+                            ui.show_redaction_results(&outcomes);
+                        }
+                    }
+                    Err(e) => {
+                        ui.show_error(&format!("An error occurred: {}", e));
+                    }
+                }
+            }
+            MenuChoice::Largest => {
+                let ranking = match session_manager.store_ranking(LARGEST_LIMIT) {
+                    Ok(ranking) => ranking,
+                    Err(e) => {
+                        ui.show_error(&format!("Failed to rank sessions: {}", e));
+                        continue;
+                    }
+                };
+                let entries: Vec<_> = ranking.largest_sessions.iter().chain(&ranking.fastest_growing).collect();
+                let pick = |indices: Vec<usize>| {
+                    let mut sessions: Vec<Session> = Vec::new();
+                    for i in indices {
+                        if !sessions.iter().any(|s| s.path == entries[i].session.path) {
+                            sessions.push(entries[i].session.clone());
+                        }
+                    }
+                    sessions
+                };
+
+                match ui.show_ranking(&ranking) {
+                    Ok(RankingAction::Back) => {}
+                    Ok(RankingAction::Delete(indices)) => match session_manager.delete_sessions(&pick(indices)) {
+                        Ok(deleted_count) => ui.show_deletion_result(deleted_count, "sessions"),
+                        Err(e) => ui.show_error(&format!("Failed to delete sessions: {}", e)),
+                    },
+                    Ok(RankingAction::Archive(indices)) => match session_manager.archive_sessions(&pick(indices)) {
+                        Ok(archived_count) => {
+                            println!("✅ Archived {} sessions", archived_count);
+                            std::thread::sleep(std::time::Duration::from_millis(2000));
+                        }
+                        Err(e) => ui.show_error(&format!("Failed to archive sessions: {}", e)),
+                    },
+                    Err(e) => {
+                        ui.show_error(&format!("An error occurred: {}", e));
+                    }
+                }
+            }
+            MenuChoice::Relocate => {
+                let (old_cwd, new_cwd) = match ui.prompt_relocation()? {
+                    Some(cwds) => cwds,
+                    None => continue,
+                };
+                // Always preview first; apply only what the user has seen
+                let preview = match session_manager.preview_relocation(&old_cwd, &new_cwd) {
+                    Ok(preview) => preview,
+                    Err(e) => {
+                        ui.show_error(&format!("Failed to relocate: {}", e));
+                        continue;
+                    }
+                };
+                if ui.show_relocation_report(&preview) {
+                    match session_manager.apply_relocation(&preview) {
+                        Ok(report) => {
+                            ui.show_relocation_report(&report);
+                        }
+                        Err(e) => ui.show_error(&format!("Failed to relocate: {}", e)),
+                    }
+                }
+            }
+            MenuChoice::StaleBranches => {
+                let report = match session_manager.find_stale_branch_sessions() {
+                    Ok(report) => report,
+                    Err(e) => {
+                        ui.show_error(&format!("Failed to check git branches: {}", e));
+                        continue;
+                    }
+                };
+                let pick = |indices: Vec<usize>| -> Vec<Session> {
+                    indices.into_iter().map(|i| report.sessions[i].session.clone()).collect()
+                };
+
+                match ui.show_stale_branches(&report) {
+                    Ok(RankingAction::Back) => {}
+                    Ok(RankingAction::Delete(indices)) => match session_manager.delete_sessions(&pick(indices)) {
+                        Ok(deleted_count) => ui.show_deletion_result(deleted_count, "sessions"),
+                        Err(e) => ui.show_error(&format!("Failed to delete sessions: {}", e)),
+                    },
+                    Ok(RankingAction::Archive(indices)) => match session_manager.archive_sessions(&pick(indices)) {
+                        Ok(archived_count) => {
+                            println!("✅ Archived {} sessions", archived_count);
+                            std::thread::sleep(std::time::Duration::from_millis(2000));
+                        }
+                        Err(e) => ui.show_error(&format!("Failed to archive sessions: {}", e)),
+                    },
+                    Err(e) => {
+                        ui.show_error(&format!("An error occurred: {}", e));
+                    }
+                }
+            }
+            MenuChoice::Browse => match tui::run(&mut session_manager) {
+                Ok(TuiExit::Quit) => {
+                    println!("Goodbye!");
+                    break;
+                }
+                Ok(TuiExit::Menu) => {}
+                Err(e) => ui.show_error(&format!("Full-screen mode is unavailable: {}", e)),
+            },
             MenuChoice::Exit => {
                 println!("Goodbye!");
                 break;
@@ -123,3 +529,18 @@ fn main() -> Result<()> {
 
     Ok(())
 }
+
+/// Shows the project list until a project is picked, opening the detail
+/// report for any project the user asks about on the way.
+fn choose_project(ui: &mut UI, session_manager: &SessionManager, projects: &[Project]) -> Result<Option<usize>> {
+    loop {
+        match ui.show_projects(projects)? {
+            ProjectAction::Select(index) => return Ok(Some(index)),
+            ProjectAction::Details(index) => {
+                let detail = session_manager.project_detail(&projects[index]);
+                ui.show_project_detail(&detail)?;
+            }
+            ProjectAction::Back => return Ok(None),
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use chrono::{DateTime, Local, Utc};
+use crate::tools::ToolReport;
+use crate::utils::{format_size, is_session_id};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -10,6 +13,45 @@ pub struct Session {
     pub size: u64,
     pub modified: SystemTime,
     pub content_preview: Option<String>,
+    /// Bytes held by artifacts (todos, file history, ...) keyed by this session's ID.
+    pub linked_size: u64,
+    /// Sub-agent (sidechain) transcripts spawned from this session.
+    pub subagents: Vec<Session>,
+    /// Bytes in the session's `<id>/` directory besides sub-agent transcripts,
+    /// such as saved tool results.
+    pub extra_size: u64,
+    /// Facts read from the transcript's entries when it was scanned.
+    pub metadata: SessionMetadata,
+}
+
+/// What a transcript records about its session, gathered in one pass over
+/// the file so listings and reports need not read it again.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SessionMetadata {
+    /// The `sessionId` of the entries; for sub-agents, the parent session's.
+    pub session_id: Option<String>,
+    /// Working directory Claude Code ran in, from the first entry that has one.
+    pub cwd: Option<String>,
+    /// Git branch checked out when the session was last written to.
+    pub git_branch: Option<String>,
+    /// Git branches recorded on entries, in order of first appearance.
+    pub git_branches: Vec<String>,
+    /// Claude Code version that last wrote to the session.
+    pub version: Option<String>,
+    pub first_timestamp: Option<DateTime<Utc>>,
+    pub last_timestamp: Option<DateTime<Utc>>,
+    /// User prompts, not counting entries that only return tool results.
+    pub user_messages: usize,
+    pub assistant_messages: usize,
+    /// User entries that only carry tool results.
+    pub tool_messages: usize,
+    /// Models that answered, in order of first use.
+    pub models: Vec<String>,
+    /// Tokens of the assistant's responses. Claude Code writes one entry per
+    /// content block, each repeating the usage, so a response counts once.
+    pub tokens: TokenUsage,
+    /// Tool calls made in this transcript and how many of them failed.
+    pub tools: ToolUsage,
 }
 
 impl Session {
@@ -20,9 +62,34 @@ impl Session {
             size,
             modified,
             content_preview: None,
+            linked_size: 0,
+            subagents: Vec::new(),
+            extra_size: 0,
+            metadata: SessionMetadata::default(),
         }
     }
 
+    /// Size of the transcript plus everything deleted along with it in the
+    /// project directory: sub-agent transcripts and the session's data directory.
+    pub fn total_size(&self) -> u64 {
+        self.size + self.extra_size + self.subagents.iter().map(|s| s.total_size()).sum::<u64>()
+    }
+
+    /// Tool calls of the session and its sub-agents.
+    pub fn tool_usage(&self) -> ToolUsage {
+        let mut usage = self.metadata.tools.clone();
+        self.subagents.iter().for_each(|subagent| usage.add(&subagent.tool_usage()));
+        usage
+    }
+
+    /// The session ID Claude Code uses for this transcript, i.e. the file stem.
+    pub fn session_id(&self) -> Option<&str> {
+        self.path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .filter(|stem| is_session_id(stem))
+    }
+
     pub fn get_modified_datetime(&self) -> Result<DateTime<Local>, chrono::ParseError> {
         let datetime: DateTime<Utc> = self.modified.into();
         Ok(datetime.with_timezone(&Local))
@@ -35,20 +102,7 @@ impl Session {
     }
 
     pub fn format_size(&self) -> String {
-        const UNITS: &[&str] = &["B", "KB", "MB", "GB"];
-        let mut size = self.size as f64;
-        let mut unit_index = 0;
-
-        while size >= 1024.0 && unit_index < UNITS.len() - 1 {
-            size /= 1024.0;
-            unit_index += 1;
-        }
-
-        if unit_index == 0 {
-            format!("{} {}", size as u64, UNITS[unit_index])
-        } else {
-            format!("{:.1} {}", size, UNITS[unit_index])
-        }
+        format_size(self.total_size())
     }
 }
 
@@ -73,74 +127,811 @@ impl Project {
     }
 
     pub fn add_session(&mut self, session: Session) {
-        self.total_size += session.size;
+        self.total_size += session.total_size();
         self.sessions.push(session);
     }
 
     pub fn format_size(&self) -> String {
-        const UNITS: &[&str] = &["B", "KB", "MB", "GB"];
-        let mut size = self.total_size as f64;
-        let mut unit_index = 0;
+        format_size(self.total_size)
+    }
+
+    pub fn linked_size(&self) -> u64 {
+        self.sessions.iter().map(|s| s.linked_size).sum()
+    }
+}
 
-        while size >= 1024.0 && unit_index < UNITS.len() - 1 {
-            size /= 1024.0;
-            unit_index += 1;
+/// Directories under `~/.claude` that Claude Code fills alongside the session transcripts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ArtifactCategory {
+    Todos,
+    FileHistory,
+    ShellSnapshots,
+    Debug,
+    Statsig,
+}
+
+impl ArtifactCategory {
+    pub const ALL: [ArtifactCategory; 5] = [
+        ArtifactCategory::Todos,
+        ArtifactCategory::FileHistory,
+        ArtifactCategory::ShellSnapshots,
+        ArtifactCategory::Debug,
+        ArtifactCategory::Statsig,
+    ];
+
+    pub fn dir_name(&self) -> &'static str {
+        match self {
+            ArtifactCategory::Todos => "todos",
+            ArtifactCategory::FileHistory => "file-history",
+            ArtifactCategory::ShellSnapshots => "shell-snapshots",
+            ArtifactCategory::Debug => "debug",
+            ArtifactCategory::Statsig => "statsig",
         }
+    }
 
-        if unit_index == 0 {
-            format!("{} {}", size as u64, UNITS[unit_index])
+    pub fn from_dir_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|category| category.dir_name() == name)
+    }
+
+    /// Extracts the owning session ID from an entry name, for the categories
+    /// that key their entries by session (`todos/<id>-agent-<agent>.json`,
+    /// `file-history/<id>/`, `debug/<id>.txt`).
+    pub fn session_id_from_name(&self, name: &str) -> Option<String> {
+        let candidate = match self {
+            ArtifactCategory::Todos => name.split("-agent-").next()?,
+            ArtifactCategory::FileHistory => name,
+            ArtifactCategory::Debug => name.strip_suffix(".txt")?,
+            ArtifactCategory::ShellSnapshots | ArtifactCategory::Statsig => return None,
+        };
+
+        if is_session_id(candidate) {
+            Some(candidate.to_string())
         } else {
-            format!("{:.1} {}", size, UNITS[unit_index])
+            None
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ArtifactCategory::Todos => "Todo lists",
+            ArtifactCategory::FileHistory => "File history",
+            ArtifactCategory::ShellSnapshots => "Shell snapshots",
+            ArtifactCategory::Debug => "Debug logs",
+            ArtifactCategory::Statsig => "Statsig cache",
+        }
+    }
+}
+
+/// A top-level entry (file or directory) inside one of the artifact directories.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Artifact {
+    pub category: ArtifactCategory,
+    pub name: String,
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified: SystemTime,
+    pub session_id: Option<String>,
+}
+
+impl Artifact {
+    pub fn new(
+        category: ArtifactCategory,
+        name: String,
+        path: PathBuf,
+        size: u64,
+        modified: SystemTime,
+    ) -> Self {
+        let session_id = category.session_id_from_name(&name);
+        Self {
+            category,
+            name,
+            path,
+            size,
+            modified,
+            session_id,
+        }
+    }
+
+    pub fn get_age_days(&self) -> u64 {
+        let now = SystemTime::now();
+        let duration = now.duration_since(self.modified).unwrap_or_default();
+        duration.as_secs() / (60 * 60 * 24)
+    }
+
+    pub fn format_size(&self) -> String {
+        format_size(self.size)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ArtifactUsage {
+    pub category: ArtifactCategory,
+    pub count: usize,
+    pub size: u64,
+}
+
+impl ArtifactUsage {
+    pub fn format_size(&self) -> String {
+        format_size(self.size)
+    }
+}
+
+/// How a redundant session relates to the session that is kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DuplicateKind {
+    /// Both transcripts contain exactly the same entries.
+    Identical,
+    /// The redundant transcript is the beginning of the kept one (a resumed copy).
+    Prefix,
+    /// Every entry of the redundant transcript also appears in the kept one.
+    Subset,
+}
+
+impl DuplicateKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            DuplicateKind::Identical => "identical",
+            DuplicateKind::Prefix => "prefix",
+            DuplicateKind::Subset => "subset",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DuplicateSession {
+    pub redundant: Session,
+    pub kept: Session,
+    pub kind: DuplicateKind,
+    pub shared_entries: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct ProjectDuplicates {
+    pub project_name: String,
+    pub project_path: PathBuf,
+    pub duplicates: Vec<DuplicateSession>,
+}
+
+impl ProjectDuplicates {
+    pub fn redundant_bytes(&self) -> u64 {
+        self.duplicates.iter().map(|d| d.redundant.total_size()).sum()
+    }
+
+    pub fn format_redundant_bytes(&self) -> String {
+        format_size(self.redundant_bytes())
+    }
+}
+
+/// A problem found in a session transcript. Line numbers are 1-based.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IntegrityIssue {
+    /// A line that is not a JSON object.
+    InvalidLine { line: usize },
+    /// The last line was cut off while being written: it is not valid JSON
+    /// and the file does not end with a newline.
+    TruncatedTail { line: usize },
+    /// The same `uuid` appears on more than one line.
+    DuplicateUuid { uuid: String, lines: Vec<usize> },
+    /// `parentUuid` points at an entry that is not in the file.
+    BrokenParent { line: usize, parent_uuid: String },
+}
+
+impl IntegrityIssue {
+    /// Broken parent links cannot be fixed without losing entries, so repair
+    /// leaves them alone.
+    pub fn is_repairable(&self) -> bool {
+        !matches!(self, IntegrityIssue::BrokenParent { .. })
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            IntegrityIssue::InvalidLine { line } => format!("line {}: not a JSON object", line),
+            IntegrityIssue::TruncatedTail { line } => format!("line {}: truncated last line", line),
+            IntegrityIssue::DuplicateUuid { uuid, lines } => {
+                let lines: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+                format!("uuid {} repeated on lines {}", uuid, lines.join(", "))
+            }
+            IntegrityIssue::BrokenParent { line, parent_uuid } => {
+                format!("line {}: parentUuid {} not found", line, parent_uuid)
+            }
+        }
+    }
+}
+
+/// Integrity check result for one transcript that has at least one issue.
+#[derive(Debug, Clone)]
+pub struct SessionIntegrity {
+    pub project_name: String,
+    pub session: Session,
+    pub valid_entries: usize,
+    pub issues: Vec<IntegrityIssue>,
+}
+
+impl SessionIntegrity {
+    pub fn is_repairable(&self) -> bool {
+        self.issues.iter().any(|issue| issue.is_repairable())
+    }
+}
+
+/// What a repair did to one transcript.
+#[derive(Debug, Clone)]
+pub struct RepairOutcome {
+    pub path: PathBuf,
+    /// Copy of the original file, or `None` when nothing had to change.
+    pub backup_path: Option<PathBuf>,
+    pub removed_lines: usize,
+}
+
+/// A secret found in a transcript. The secret itself is never stored, only
+/// a masked form that is safe to display.
+#[derive(Debug, Clone)]
+pub struct SecretFinding {
+    /// 1-based JSONL line, i.e. the message the secret was found in.
+    pub line: usize,
+    /// 1-based character column within the line.
+    pub column: usize,
+    pub entry_uuid: Option<String>,
+    pub entry_type: String,
+    pub detector: String,
+    pub masked: String,
+}
+
+/// Secrets found in one transcript.
+#[derive(Debug, Clone)]
+pub struct SessionSecrets {
+    pub project_name: String,
+    pub session: Session,
+    pub findings: Vec<SecretFinding>,
+}
+
+/// What a redaction did to one transcript.
+#[derive(Debug, Clone)]
+pub struct RedactionOutcome {
+    pub path: PathBuf,
+    /// Copy of the original file, or `None` when nothing had to change.
+    pub backup_path: Option<PathBuf>,
+    pub redacted: usize,
+}
+
+/// A session copied into another project, with the original set aside.
+#[derive(Debug, Clone)]
+pub struct MoveOutcome {
+    pub path: PathBuf,
+    /// Where the transcript now lives.
+    pub target_path: PathBuf,
+    /// Trash directory holding the original files until the move is confirmed.
+    pub trash_path: PathBuf,
+    /// Entries whose `cwd` was pointed at the new directory.
+    pub rewritten_cwd: usize,
+}
+
+/// A session last written on a git branch that its repository no longer has.
+#[derive(Debug, Clone)]
+pub struct StaleBranchSession {
+    pub project_name: String,
+    pub session: Session,
+    pub branch: String,
+    /// Working tree root of the repository the session ran in.
+    pub repository: PathBuf,
+}
+
+/// Sessions whose branch is gone, from every project run inside a local git repository.
+#[derive(Debug, Clone, Default)]
+pub struct StaleBranchReport {
+    /// Largest first.
+    pub sessions: Vec<StaleBranchSession>,
+    /// Repositories whose refs were read.
+    pub repositories: usize,
+    /// Sessions with a branch that could not be checked: their directory is
+    /// gone, is not in a repository, or the refs were unreadable.
+    pub unchecked: usize,
+}
+
+impl StaleBranchReport {
+    pub fn total_size(&self) -> u64 {
+        self.sessions.iter().map(|s| s.session.total_size()).sum()
+    }
+}
+
+/// How to continue a session in Claude Code: `<program> --resume <id>`, run
+/// in the directory the session was started from.
+#[derive(Debug, Clone)]
+pub struct ResumeCommand {
+    pub session_id: String,
+    pub cwd: PathBuf,
+    pub program: String,
+}
+
+impl ResumeCommand {
+    pub fn args(&self) -> [&str; 2] {
+        ["--resume", &self.session_id]
+    }
+
+    /// The command as a POSIX shell line, e.g. `cd /repo && claude --resume <id>`.
+    pub fn shell_line(&self) -> String {
+        let words: Vec<String> = [self.program.as_str()]
+            .into_iter()
+            .chain(self.args())
+            .map(shell_quote)
+            .collect();
+        format!("cd {} && {}", shell_quote(&self.cwd.to_string_lossy()), words.join(" "))
+    }
+
+    /// Runs the command with the terminal handed over and waits for it to exit.
+    pub fn run(&self) -> std::io::Result<std::process::ExitStatus> {
+        std::process::Command::new(&self.program).args(self.args()).current_dir(&self.cwd).status()
+    }
+}
+
+/// Quotes `word` for a POSIX shell unless it only holds characters that need none.
+fn shell_quote(word: &str) -> String {
+    let plain = |c: char| c.is_ascii_alphanumeric() || "/._-+:@%,=".contains(c);
+    if !word.is_empty() && word.chars().all(plain) {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', "'\\''"))
+    }
+}
+
+/// What relocating a project from one working directory to another changes,
+/// or changed when it was applied.
+#[derive(Debug, Clone)]
+pub struct RelocationReport {
+    pub old_cwd: String,
+    pub new_cwd: String,
+    /// Project directory of the old working directory.
+    pub source: PathBuf,
+    /// Project directory of the new working directory.
+    pub target: PathBuf,
+    /// The target already exists and the sessions are merged into it.
+    pub merge: bool,
+    pub sessions: usize,
+    /// Transcripts, sub-agents included, with at least one `cwd` to rewrite.
+    pub transcripts: usize,
+    pub rewritten_entries: usize,
+    /// Files present in both projects, relative to them; relocation refuses
+    /// to run while there are any.
+    pub conflicts: Vec<PathBuf>,
+    /// Identifies the files of both projects when the report was made, so a
+    /// preview is only applied while they are unchanged.
+    pub fingerprint: String,
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+/// One result of the `doctor` health check.
+#[derive(Debug, Clone)]
+pub struct DoctorFinding {
+    pub severity: Severity,
+    /// Which check produced the finding: `root`, `projects`, `permissions`,
+    /// `symlinks` or `disk`.
+    pub check: &'static str,
+    pub message: String,
+    pub path: Option<PathBuf>,
+    pub suggestion: String,
+}
+
+impl DoctorFinding {
+    pub fn new(severity: Severity, check: &'static str, message: String, path: Option<&Path>, suggestion: &str) -> Self {
+        Self {
+            severity,
+            check,
+            message,
+            path: path.map(|p| p.to_path_buf()),
+            suggestion: suggestion.to_string(),
+        }
+    }
+}
+
+/// Sessions of one project linked by `summary` entries whose `leafUuid`
+/// points into another file, i.e. a conversation continued across files.
+#[derive(Debug, Clone)]
+pub struct SessionChain {
+    /// Members from the oldest to the newest file.
+    pub sessions: Vec<Session>,
+    /// Timestamp of the first message in the oldest member.
+    pub started: Option<DateTime<Utc>>,
+    /// Timestamp of the last message in the newest member.
+    pub ended: Option<DateTime<Utc>>,
+}
+
+impl SessionChain {
+    pub fn total_size(&self) -> u64 {
+        self.sessions.iter().map(|s| s.total_size()).sum()
+    }
+
+    pub fn format_size(&self) -> String {
+        format_size(self.total_size())
+    }
+
+    pub fn contains(&self, session: &Session) -> bool {
+        self.sessions.iter().any(|s| s.path == session.path)
+    }
+
+    pub fn duration(&self) -> Option<chrono::Duration> {
+        Some(self.ended? - self.started?)
+    }
+
+    pub fn format_duration(&self) -> String {
+        match self.duration() {
+            Some(duration) if duration.num_days() > 0 => {
+                format!("{}d {}h", duration.num_days(), duration.num_hours() % 24)
+            }
+            Some(duration) if duration.num_hours() > 0 => {
+                format!("{}h {}m", duration.num_hours(), duration.num_minutes() % 60)
+            }
+            Some(duration) => format!("{}m", duration.num_minutes().max(0)),
+            None => "unknown".to_string(),
         }
     }
 }
 
-#[derive(Debug)]
+/// Upper bounds (exclusive, in days) of the age distribution buckets; the
+/// last bucket is open-ended.
+const AGE_BUCKETS: &[(&str, Option<u64>)] = &[
+    ("< 1 day", Some(1)),
+    ("1-7 days", Some(7)),
+    ("7-30 days", Some(30)),
+    ("30-90 days", Some(90)),
+    ("> 90 days", None),
+];
+
+/// Sessions whose age falls into one bucket of the age distribution.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AgeBucket {
+    pub label: &'static str,
+    pub count: usize,
+    pub size: u64,
+}
+
+impl AgeBucket {
+    pub fn format_size(&self) -> String {
+        format_size(self.size)
+    }
+}
+
+/// A session together with the project it belongs to.
+#[derive(Debug, Clone)]
+pub struct ProjectSession {
+    pub project_name: String,
+    pub session: Session,
+}
+
+#[derive(Debug, Default)]
 pub struct Statistics {
     pub total_projects: usize,
     pub total_sessions: usize,
     pub total_size: u64,
+    pub artifact_usage: Vec<ArtifactUsage>,
+    pub age_buckets: Vec<AgeBucket>,
+    pub oldest_session: Option<ProjectSession>,
+    pub newest_session: Option<ProjectSession>,
+    /// Projects without any session left.
+    pub empty_projects: usize,
+    pub average_session_size: u64,
+    pub median_session_size: u64,
+    pub largest_project: Option<Project>,
+    pub tools: ToolReport,
 }
 
 impl Statistics {
     #[cfg(test)]
     pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Computes the session statistics of `projects`. Sizes include each
+    /// session's sub-agents and data directory.
+    pub fn from_projects(projects: &[Project], artifact_usage: Vec<ArtifactUsage>) -> Self {
+        let sessions: Vec<(&Project, &Session)> = projects
+            .iter()
+            .flat_map(|p| p.sessions.iter().map(move |s| (p, s)))
+            .collect();
+
+        let mut age_buckets: Vec<AgeBucket> = AGE_BUCKETS
+            .iter()
+            .map(|&(label, _)| AgeBucket { label, count: 0, size: 0 })
+            .collect();
+        for (_, session) in &sessions {
+            let age = session.get_age_days();
+            let index = AGE_BUCKETS
+                .iter()
+                .position(|&(_, limit)| match limit {
+                    Some(limit) => age < limit,
+                    None => true,
+                })
+                .unwrap_or(AGE_BUCKETS.len() - 1);
+            age_buckets[index].count += 1;
+            age_buckets[index].size += session.total_size();
+        }
+
+        let project_session = |(project, session): &(&Project, &Session)| ProjectSession {
+            project_name: project.name.clone(),
+            session: (*session).clone(),
+        };
+        let oldest_session = sessions.iter().min_by_key(|(_, s)| s.modified).map(project_session);
+        let newest_session = sessions.iter().max_by_key(|(_, s)| s.modified).map(project_session);
+
+        let mut sizes: Vec<u64> = sessions.iter().map(|(_, s)| s.total_size()).collect();
+        sizes.sort_unstable();
+        let total_size: u64 = projects.iter().map(|p| p.total_size).sum();
+        let median_session_size = match sizes.len() {
+            0 => 0,
+            n if n % 2 == 1 => sizes[n / 2],
+            n => (sizes[n / 2 - 1] + sizes[n / 2]) / 2,
+        };
+
         Self {
-            total_projects: 0,
-            total_sessions: 0,
-            total_size: 0,
+            total_projects: projects.len(),
+            total_sessions: sessions.len(),
+            total_size,
+            artifact_usage,
+            age_buckets,
+            oldest_session,
+            newest_session,
+            empty_projects: projects.iter().filter(|p| p.sessions.is_empty()).count(),
+            average_session_size: total_size.checked_div(sessions.len() as u64).unwrap_or(0),
+            median_session_size,
+            largest_project: projects
+                .iter()
+                .filter(|p| !p.sessions.is_empty())
+                .max_by_key(|p| p.total_size)
+                .cloned(),
+            tools: ToolReport::from_projects(projects),
         }
     }
 
     pub fn format_total_size(&self) -> String {
-        const UNITS: &[&str] = &["B", "KB", "MB", "GB"];
-        let mut size = self.total_size as f64;
-        let mut unit_index = 0;
+        format_size(self.total_size)
+    }
+
+    pub fn total_artifacts_size(&self) -> u64 {
+        self.artifact_usage.iter().map(|usage| usage.size).sum()
+    }
+
+    pub fn format_total_artifacts_size(&self) -> String {
+        format_size(self.total_artifacts_size())
+    }
+}
+
+/// Token counts reported by the API for assistant messages.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct TokenUsage {
+    pub input: u64,
+    pub output: u64,
+    pub cache_creation: u64,
+    pub cache_read: u64,
+}
+
+impl TokenUsage {
+    pub fn add(&mut self, other: &TokenUsage) {
+        self.input += other.input;
+        self.output += other.output;
+        self.cache_creation += other.cache_creation;
+        self.cache_read += other.cache_read;
+    }
+
+    pub fn total(&self) -> u64 {
+        self.input + self.output + self.cache_creation + self.cache_read
+    }
+}
+
+/// Calls of a tool and how many of them returned an error.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolCounts {
+    pub calls: usize,
+    pub errors: usize,
+}
+
+impl ToolCounts {
+    pub fn add(&mut self, other: &ToolCounts) {
+        self.calls += other.calls;
+        self.errors += other.errors;
+    }
+
+    /// Share of the calls that failed, from 0 to 1.
+    pub fn error_rate(&self) -> f64 {
+        if self.calls == 0 {
+            return 0.0;
+        }
+        self.errors as f64 / self.calls as f64
+    }
+}
 
-        while size >= 1024.0 && unit_index < UNITS.len() - 1 {
-            size /= 1024.0;
-            unit_index += 1;
+/// `tool_use` calls by tool name, with the calls whose `tool_result` was
+/// flagged `is_error`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ToolUsage {
+    pub tools: HashMap<String, ToolCounts>,
+}
+
+impl ToolUsage {
+    pub fn record_call(&mut self, tool: &str) {
+        self.tools.entry(tool.to_string()).or_default().calls += 1;
+    }
+
+    pub fn record_error(&mut self, tool: &str) {
+        self.tools.entry(tool.to_string()).or_default().errors += 1;
+    }
+
+    pub fn add(&mut self, other: &ToolUsage) {
+        for (tool, counts) in &other.tools {
+            self.tools.entry(tool.clone()).or_default().add(counts);
         }
+    }
 
-        if unit_index == 0 {
-            format!("{} {}", size as u64, UNITS[unit_index])
-        } else {
-            format!("{:.1} {}", size, UNITS[unit_index])
+    pub fn total(&self) -> ToolCounts {
+        let mut total = ToolCounts::default();
+        self.tools.values().for_each(|counts| total.add(counts));
+        total
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tools.is_empty()
+    }
+
+    /// Tools by number of calls, most used first.
+    pub fn ranked(&self) -> Vec<(&str, ToolCounts)> {
+        rank_counts(self.tools.iter().map(|(tool, counts)| (tool.as_str(), *counts)).collect())
+    }
+
+    /// MCP tool calls summed by the server the tools belong to, most used first.
+    pub fn mcp_servers(&self) -> Vec<(&str, ToolCounts)> {
+        let mut servers: HashMap<&str, ToolCounts> = HashMap::new();
+        for (tool, counts) in &self.tools {
+            if let Some(server) = mcp_server(tool) {
+                servers.entry(server).or_default().add(counts);
+            }
+        }
+        rank_counts(servers.into_iter().collect())
+    }
+}
+
+fn rank_counts(mut counts: Vec<(&str, ToolCounts)>) -> Vec<(&str, ToolCounts)> {
+    counts.sort_by(|a, b| b.1.calls.cmp(&a.1.calls).then_with(|| a.0.cmp(b.0)));
+    counts
+}
+
+/// The server of an MCP tool, named `mcp__<server>__<tool>`.
+pub fn mcp_server(tool: &str) -> Option<&str> {
+    let (server, _) = tool.strip_prefix("mcp__")?.split_once("__")?;
+    Some(server)
+}
+
+/// The part of a transcript's metadata that reports sum up.
+#[derive(Debug, Clone, Default)]
+pub struct TranscriptSummary {
+    /// Working directory Claude Code ran in, from the first entry that has one.
+    pub cwd: Option<String>,
+    /// Git branches recorded on entries, in order of first appearance.
+    pub git_branches: Vec<String>,
+    pub first_timestamp: Option<DateTime<Utc>>,
+    pub last_timestamp: Option<DateTime<Utc>>,
+    /// User prompts, not counting entries that only return tool results.
+    pub user_messages: usize,
+    pub assistant_messages: usize,
+    pub tokens: TokenUsage,
+    /// Tool calls by tool name.
+    pub tool_uses: HashMap<String, usize>,
+}
+
+impl TranscriptSummary {
+    pub fn message_count(&self) -> usize {
+        self.user_messages + self.assistant_messages
+    }
+}
+
+impl From<SessionMetadata> for TranscriptSummary {
+    fn from(metadata: SessionMetadata) -> Self {
+        Self {
+            cwd: metadata.cwd,
+            git_branches: metadata.git_branches,
+            first_timestamp: metadata.first_timestamp,
+            last_timestamp: metadata.last_timestamp,
+            user_messages: metadata.user_messages,
+            assistant_messages: metadata.assistant_messages,
+            tokens: metadata.tokens,
+            tool_uses: metadata.tools.tools.into_iter().map(|(name, counts)| (name, counts.calls)).collect(),
         }
     }
 }
 
+/// Everything known about one project, for the detail view.
+#[derive(Debug, Clone)]
+pub struct ProjectDetail {
+    pub project: Project,
+    /// The working directory most sessions ran in, which the encoded
+    /// directory name cannot always be decoded back to.
+    pub cwd: Option<String>,
+    pub first_activity: Option<DateTime<Utc>>,
+    pub last_activity: Option<DateTime<Utc>>,
+    pub message_count: usize,
+    pub tokens: TokenUsage,
+    /// Most used tools with their call counts, most used first.
+    pub top_tools: Vec<(String, usize)>,
+    /// Git branches with the number of sessions that ran on them.
+    pub git_branches: Vec<(String, usize)>,
+    /// Largest sessions first.
+    pub largest_sessions: Vec<Session>,
+}
+
+impl ProjectDetail {
+    pub fn average_session_size(&self) -> u64 {
+        self.project.total_size.checked_div(self.project.sessions.len() as u64).unwrap_or(0)
+    }
+}
+
+/// What the user chose to do from the project list.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProjectAction {
+    Back,
+    Select(usize),
+    Details(usize),
+}
+
+/// What the user chose to do from a project's session list.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SessionAction {
+    Back,
+    Delete(Vec<usize>),
+    View(usize),
+    /// Export the given sessions, oldest first, as one transcript.
+    Export(Vec<usize>),
+    /// Compare two sessions side by side.
+    Diff(usize, usize),
+    /// Move the given sessions into another project.
+    Move(Vec<usize>),
+    /// Continue the session in Claude Code.
+    Resume(usize),
+    /// Move the given sessions into the archive.
+    Archive(Vec<usize>),
+}
+
+/// What the user chose to do with the sessions picked from a store-wide list.
+/// For the ranking, indices run over the largest sessions, then the fastest-growing.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RankingAction {
+    Back,
+    Delete(Vec<usize>),
+    Archive(Vec<usize>),
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum MenuChoice {
     Statistics,
     ManageProjects,
     DeleteByAge,
     DeleteProject,
+    CleanArtifacts,
+    SweepOrphans,
+    FindDuplicates,
+    CheckIntegrity,
+    Doctor,
+    ScanSecrets,
+    Activity,
+    Largest,
+    Relocate,
+    StaleBranches,
+    Browse,
     Exit,
 }
 
-
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -206,12 +997,137 @@ mod tests {
         assert_eq!(project.total_size, 1024);
     }
 
+    #[test]
+    fn test_session_total_size_includes_subagents() {
+        let mut session = Session::new(
+            "session1.jsonl".to_string(),
+            PathBuf::from("/test/test-project/session1.jsonl"),
+            1024,
+            SystemTime::now(),
+        );
+        session.extra_size = 256;
+        session.subagents.push(Session::new(
+            "agent-1.jsonl".to_string(),
+            PathBuf::from("/test/test-project/session1/subagents/agent-1.jsonl"),
+            256,
+            SystemTime::now(),
+        ));
+        assert_eq!(session.total_size(), 1536);
+        assert_eq!(session.format_size(), "1.5 KB");
+
+        let mut project = Project::new("test-project".to_string(), PathBuf::from("/test/test-project"));
+        project.add_session(session);
+        assert_eq!(project.total_size, 1536);
+    }
+
     #[test]
     fn test_statistics_new() {
         let stats = Statistics::new();
         assert_eq!(stats.total_projects, 0);
         assert_eq!(stats.total_sessions, 0);
         assert_eq!(stats.total_size, 0);
+        assert!(stats.artifact_usage.is_empty());
+    }
+
+    #[test]
+    fn test_artifact_category_dir_names() {
+        for category in ArtifactCategory::ALL {
+            assert_eq!(ArtifactCategory::from_dir_name(category.dir_name()), Some(category));
+        }
+        assert_eq!(ArtifactCategory::from_dir_name("projects"), None);
+    }
+
+    #[test]
+    fn test_artifact_session_id_from_name() {
+        let id = "eb0b90af-e843-4f18-a24b-5fab783b7fd2";
+        assert_eq!(
+            ArtifactCategory::Todos.session_id_from_name(&format!("{}-agent-{}.json", id, id)),
+            Some(id.to_string())
+        );
+        assert_eq!(ArtifactCategory::FileHistory.session_id_from_name(id), Some(id.to_string()));
+        assert_eq!(
+            ArtifactCategory::Debug.session_id_from_name(&format!("{}.txt", id)),
+            Some(id.to_string())
+        );
+        assert_eq!(ArtifactCategory::Debug.session_id_from_name("latest"), None);
+        assert_eq!(ArtifactCategory::Statsig.session_id_from_name(id), None);
+    }
+
+    #[test]
+    fn test_session_id() {
+        let session = Session::new(
+            "eb0b90af-e843-4f18-a24b-5fab783b7fd2.jsonl".to_string(),
+            PathBuf::from("/test/eb0b90af-e843-4f18-a24b-5fab783b7fd2.jsonl"),
+            0,
+            SystemTime::now(),
+        );
+        assert_eq!(session.session_id(), Some("eb0b90af-e843-4f18-a24b-5fab783b7fd2"));
+
+        let session = Session::new(
+            "notes.jsonl".to_string(),
+            PathBuf::from("/test/notes.jsonl"),
+            0,
+            SystemTime::now(),
+        );
+        assert_eq!(session.session_id(), None);
+    }
+
+    #[test]
+    fn test_statistics_artifacts_size() {
+        let mut stats = Statistics::new();
+        stats.artifact_usage.push(ArtifactUsage {
+            category: ArtifactCategory::Todos,
+            count: 2,
+            size: 512,
+        });
+        stats.artifact_usage.push(ArtifactUsage {
+            category: ArtifactCategory::Debug,
+            count: 1,
+            size: 1024,
+        });
+        assert_eq!(stats.total_artifacts_size(), 1536);
+        assert_eq!(stats.format_total_artifacts_size(), "1.5 KB");
+    }
+
+    #[test]
+    fn test_statistics_from_projects() {
+        let days_ago = |days: u64| SystemTime::now() - std::time::Duration::from_secs(days * 24 * 60 * 60 + 60);
+        let mut app = Project::new("app".to_string(), PathBuf::from("/p/app"));
+        app.add_session(Session::new("new".to_string(), PathBuf::from("/p/app/new.jsonl"), 100, days_ago(0)));
+        app.add_session(Session::new("week".to_string(), PathBuf::from("/p/app/week.jsonl"), 300, days_ago(3)));
+        let mut lib = Project::new("lib".to_string(), PathBuf::from("/p/lib"));
+        lib.add_session(Session::new("old".to_string(), PathBuf::from("/p/lib/old.jsonl"), 1000, days_ago(120)));
+        let empty = Project::new("empty".to_string(), PathBuf::from("/p/empty"));
+
+        let stats = Statistics::from_projects(&[app, lib, empty], Vec::new());
+
+        assert_eq!(stats.total_sessions, 3);
+        let counts: Vec<usize> = stats.age_buckets.iter().map(|b| b.count).collect();
+        assert_eq!(counts, vec![1, 1, 0, 0, 1]);
+        assert_eq!(stats.age_buckets[4].size, 1000);
+        assert_eq!(stats.oldest_session.unwrap().session.name, "old");
+        assert_eq!(stats.newest_session.unwrap().project_name, "app");
+        assert_eq!(stats.empty_projects, 1);
+        assert_eq!(stats.average_session_size, 466);
+        assert_eq!(stats.median_session_size, 300);
+        assert_eq!(stats.largest_project.unwrap().name, "lib");
+    }
+
+    #[test]
+    fn test_session_chain_size_and_duration() {
+        let session = |name: &str, size: u64| {
+            Session::new(name.to_string(), PathBuf::from(name), size, SystemTime::now())
+        };
+        let started = DateTime::parse_from_rfc3339("2025-01-01T10:00:00Z").unwrap().with_timezone(&Utc);
+        let chain = SessionChain {
+            sessions: vec![session("a.jsonl", 1024), session("b.jsonl", 512)],
+            started: Some(started),
+            ended: Some(started + chrono::Duration::minutes(26 * 60 + 5)),
+        };
+
+        assert_eq!(chain.format_size(), "1.5 KB");
+        assert_eq!(chain.format_duration(), "1d 2h");
+        assert!(chain.contains(&session("b.jsonl", 0)));
     }
 
     #[test]
@@ -0,0 +1,141 @@
+use crate::models::{Project, Session};
+use std::time::{Duration, SystemTime};
+
+/// Sessions at least this many times their project's median size are flagged as outliers.
+pub const OUTLIER_FACTOR: u64 = 5;
+/// Projects need this many sessions before a median says anything about them.
+pub const OUTLIER_MIN_SESSIONS: usize = 3;
+/// Sessions younger than this are ranked by growth as if they were this old,
+/// so a freshly started session does not top the list on a few kilobytes.
+const MIN_GROWTH_WINDOW: Duration = Duration::from_secs(60 * 60);
+const SECONDS_PER_DAY: f64 = 24.0 * 60.0 * 60.0;
+
+/// A session placed in a store-wide ranking, with the context to judge it.
+#[derive(Debug, Clone)]
+pub struct RankedSession {
+    pub project_name: String,
+    pub session: Session,
+    /// Median total size of the sessions in the same project.
+    pub project_median: u64,
+    pub is_outlier: bool,
+    /// Bytes written per day between the first message and the last write,
+    /// when the first message's time is known.
+    pub growth_per_day: Option<u64>,
+}
+
+impl RankedSession {
+    /// How many times larger than its project's median this session is.
+    pub fn median_ratio(&self) -> Option<f64> {
+        if self.project_median == 0 {
+            return None;
+        }
+        Some(self.session.total_size() as f64 / self.project_median as f64)
+    }
+}
+
+/// The largest and fastest-growing sessions and the largest projects across
+/// every project in the store.
+#[derive(Debug, Clone, Default)]
+pub struct StoreRanking {
+    pub largest_sessions: Vec<RankedSession>,
+    pub fastest_growing: Vec<RankedSession>,
+    pub largest_projects: Vec<Project>,
+}
+
+impl StoreRanking {
+    /// Ranks the sessions and projects, keeping the top `limit` of each list.
+    /// `started` returns when a session's first message was written.
+    pub fn from_projects<F>(projects: &[Project], limit: usize, mut started: F) -> Self
+    where
+        F: FnMut(&Session) -> Option<SystemTime>,
+    {
+        let mut ranked = Vec::new();
+        for project in projects {
+            let mut sizes: Vec<u64> = project.sessions.iter().map(|s| s.total_size()).collect();
+            sizes.sort_unstable();
+            let median = sizes.get(sizes.len() / 2).copied().unwrap_or(0);
+
+            for session in &project.sessions {
+                let is_outlier = project.sessions.len() >= OUTLIER_MIN_SESSIONS
+                    && median > 0
+                    && session.total_size() >= median * OUTLIER_FACTOR;
+                let growth_per_day = started(session).map(|start| {
+                    let age = session.modified.duration_since(start).unwrap_or_default().max(MIN_GROWTH_WINDOW);
+                    (session.total_size() as f64 * SECONDS_PER_DAY / age.as_secs_f64()) as u64
+                });
+                ranked.push(RankedSession {
+                    project_name: project.name.clone(),
+                    session: session.clone(),
+                    project_median: median,
+                    is_outlier,
+                    growth_per_day,
+                });
+            }
+        }
+
+        let mut fastest_growing: Vec<RankedSession> =
+            ranked.iter().filter(|r| r.growth_per_day.is_some()).cloned().collect();
+        fastest_growing.sort_by_key(|r| std::cmp::Reverse(r.growth_per_day));
+        fastest_growing.truncate(limit);
+
+        let mut largest_sessions = ranked;
+        largest_sessions.sort_by_key(|r| std::cmp::Reverse(r.session.total_size()));
+        largest_sessions.truncate(limit);
+
+        let mut largest_projects = projects.to_vec();
+        largest_projects.sort_by_key(|p| std::cmp::Reverse(p.total_size));
+        largest_projects.truncate(limit);
+
+        Self {
+            largest_sessions,
+            fastest_growing,
+            largest_projects,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_ranking_and_outliers() {
+        let now = SystemTime::now();
+        let session = |name: &str, size| Session::new(name.to_string(), PathBuf::from(name), size, now);
+        let mut big = Project::new("big".to_string(), PathBuf::from("big"));
+        for (name, size) in [("a", 100), ("b", 120), ("c", 110), ("huge", 1000)] {
+            big.add_session(session(name, size));
+        }
+        let mut small = Project::new("small".to_string(), PathBuf::from("small"));
+        for (name, size) in [("x", 10), ("lonely", 900)] {
+            small.add_session(session(name, size));
+        }
+
+        let ranking = StoreRanking::from_projects(&[small, big], 3, |s| match s.name.as_str() {
+            // 900 bytes over two days grows slower than 120 bytes within the first hour
+            "lonely" => Some(now - Duration::from_secs(2 * 24 * 60 * 60)),
+            "b" => Some(now - Duration::from_secs(60)),
+            _ => None,
+        });
+
+        let names: Vec<&str> = ranking.largest_sessions.iter().map(|r| r.session.name.as_str()).collect();
+        assert_eq!(names, ["huge", "lonely", "b"]);
+        let huge = &ranking.largest_sessions[0];
+        assert_eq!(huge.project_median, 120);
+        assert!(huge.is_outlier);
+        // Too few sessions in its project to call it an outlier
+        assert!(!ranking.largest_sessions[1].is_outlier);
+        assert!(!ranking.largest_sessions[2].is_outlier);
+
+        let growing: Vec<(&str, Option<u64>)> = ranking
+            .fastest_growing
+            .iter()
+            .map(|r| (r.session.name.as_str(), r.growth_per_day))
+            .collect();
+        assert_eq!(growing, [("b", Some(2880)), ("lonely", Some(450))]);
+
+        let projects: Vec<&str> = ranking.largest_projects.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(projects, ["big", "small"]);
+    }
+}
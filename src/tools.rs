@@ -0,0 +1,116 @@
+use crate::models::{Project, Session, ToolCounts, ToolUsage};
+use std::cmp::Reverse;
+use std::path::PathBuf;
+
+/// The tool calls of one project's sessions.
+#[derive(Debug, Clone)]
+pub struct ProjectToolUsage {
+    pub project_name: String,
+    pub project_path: PathBuf,
+    pub usage: ToolUsage,
+}
+
+/// The tool calls of one session, its sub-agents included.
+#[derive(Debug, Clone)]
+pub struct SessionToolUsage {
+    pub project_name: String,
+    pub session: Session,
+    pub usage: ToolUsage,
+}
+
+/// Tool calls and failures across the store, by project and by session.
+/// Projects and sessions that never called a tool are left out.
+#[derive(Debug, Clone, Default)]
+pub struct ToolReport {
+    pub overall: ToolUsage,
+    /// Projects with the most tool calls first.
+    pub projects: Vec<ProjectToolUsage>,
+    /// Sessions with the most tool calls first.
+    pub sessions: Vec<SessionToolUsage>,
+}
+
+impl ToolReport {
+    /// Sums the tool calls recorded in the sessions' metadata, so no
+    /// transcript is read again.
+    pub fn from_projects(projects: &[Project]) -> Self {
+        let mut report = Self::default();
+        for project in projects {
+            let mut project_usage = ToolUsage::default();
+            for session in &project.sessions {
+                let usage = session.tool_usage();
+                if usage.is_empty() {
+                    continue;
+                }
+                project_usage.add(&usage);
+                report.sessions.push(SessionToolUsage {
+                    project_name: project.name.clone(),
+                    session: session.clone(),
+                    usage,
+                });
+            }
+            if project_usage.is_empty() {
+                continue;
+            }
+            report.overall.add(&project_usage);
+            report.projects.push(ProjectToolUsage {
+                project_name: project.name.clone(),
+                project_path: project.path.clone(),
+                usage: project_usage,
+            });
+        }
+
+        report.projects.sort_by_key(|p| Reverse(p.usage.total().calls));
+        report.sessions.sort_by_key(|s| Reverse(s.usage.total().calls));
+        report
+    }
+
+    pub fn total(&self) -> ToolCounts {
+        self.overall.total()
+    }
+
+    /// Sessions with failed tool calls, the most failures first.
+    pub fn sessions_by_errors(&self) -> Vec<&SessionToolUsage> {
+        let mut sessions: Vec<&SessionToolUsage> =
+            self.sessions.iter().filter(|s| s.usage.total().errors > 0).collect();
+        sessions.sort_by_key(|s| Reverse(s.usage.total().errors));
+        sessions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::SystemTime;
+
+    fn session(name: &str, tools: &[(&str, usize, usize)]) -> Session {
+        let mut session = Session::new(name.to_string(), PathBuf::from(name), 0, SystemTime::now());
+        for &(tool, calls, errors) in tools {
+            session.metadata.tools.tools.insert(tool.to_string(), ToolCounts { calls, errors });
+        }
+        session
+    }
+
+    #[test]
+    fn test_tool_report_sums_sessions_subagents_and_projects() {
+        let mut main = session("a.jsonl", &[("Bash", 4, 1), ("mcp__github__create_issue", 1, 1)]);
+        main.subagents.push(session("agent-1.jsonl", &[("Read", 3, 0), ("mcp__github__get_pr", 2, 0)]));
+        let mut app = Project::new("app".to_string(), PathBuf::from("-app"));
+        app.add_session(main);
+        app.add_session(session("b.jsonl", &[]));
+        let mut lib = Project::new("lib".to_string(), PathBuf::from("-lib"));
+        lib.add_session(session("c.jsonl", &[("Bash", 20, 0), ("Edit", 2, 1)]));
+        let empty = Project::new("empty".to_string(), PathBuf::from("-empty"));
+
+        let report = ToolReport::from_projects(&[app, lib, empty]);
+        assert_eq!(report.total(), ToolCounts { calls: 32, errors: 3 });
+        assert_eq!(report.overall.ranked()[0], ("Bash", ToolCounts { calls: 24, errors: 1 }));
+        assert_eq!(report.overall.mcp_servers(), [("github", ToolCounts { calls: 3, errors: 1 })]);
+
+        let projects: Vec<&str> = report.projects.iter().map(|p| p.project_name.as_str()).collect();
+        assert_eq!(projects, ["lib", "app"]);
+        assert_eq!(report.projects[1].usage.total(), ToolCounts { calls: 10, errors: 2 });
+        assert_eq!(report.sessions.len(), 2);
+        assert_eq!(report.sessions_by_errors()[0].session.name, "a.jsonl");
+        assert!((report.projects[0].usage.total().error_rate() - 1.0 / 22.0).abs() < 1e-9);
+    }
+}
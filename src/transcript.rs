@@ -0,0 +1,478 @@
+use crate::models::{SessionMetadata, TokenUsage};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// One parsed line of a Claude Code session transcript.
+#[derive(Debug, Clone)]
+pub struct TranscriptEntry {
+    /// 1-based line number in the JSONL file.
+    pub line_number: usize,
+    pub entry_type: String,
+    pub uuid: Option<String>,
+    pub parent_uuid: Option<String>,
+    pub session_id: Option<String>,
+    /// Set on entries written by a sub-agent rather than the main conversation.
+    pub is_sidechain: bool,
+    pub timestamp: Option<DateTime<Utc>>,
+    pub value: Value,
+}
+
+impl TranscriptEntry {
+    pub fn from_value(line_number: usize, value: Value) -> Self {
+        let field = |name: &str| value.get(name).and_then(|v| v.as_str()).map(|s| s.to_string());
+        Self {
+            line_number,
+            entry_type: field("type").unwrap_or_else(|| "unknown".to_string()),
+            uuid: field("uuid"),
+            parent_uuid: field("parentUuid"),
+            session_id: field("sessionId"),
+            is_sidechain: value.get("isSidechain").and_then(|v| v.as_bool()).unwrap_or(false),
+            timestamp: field("timestamp")
+                .and_then(|ts| DateTime::parse_from_rfc3339(&ts).ok())
+                .map(|ts| ts.with_timezone(&Utc)),
+            value,
+        }
+    }
+
+    /// The `leafUuid` of a `summary` entry: the last message of the
+    /// conversation the summary was written for.
+    pub fn leaf_uuid(&self) -> Option<&str> {
+        if self.entry_type != "summary" {
+            return None;
+        }
+        self.value.get("leafUuid").and_then(|v| v.as_str())
+    }
+
+    /// True for user entries that only carry `tool_result` blocks.
+    pub fn is_tool_result(&self) -> bool {
+        let content = self.value.get("message").and_then(|m| m.get("content")).and_then(|c| c.as_array());
+        match content {
+            Some(blocks) if !blocks.is_empty() => blocks
+                .iter()
+                .all(|b| b.get("type").and_then(|t| t.as_str()) == Some("tool_result")),
+            _ => false,
+        }
+    }
+}
+
+/// Reads every line of a transcript that parses as a JSON object, skipping
+/// blank and malformed lines.
+pub fn read_entries(path: &Path) -> Result<Vec<TranscriptEntry>> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut entries = Vec::new();
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        if let Ok(value @ Value::Object(_)) = serde_json::from_str::<Value>(trimmed) {
+            entries.push(TranscriptEntry::from_value(index + 1, value));
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Reads a transcript line by line and collects its session metadata without
+/// keeping the entries. Blank and malformed lines are skipped.
+pub fn read_metadata(path: &Path) -> Result<SessionMetadata> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut collector = MetadataCollector::default();
+
+    for line in reader.lines() {
+        let line = line?;
+        if let Ok(value @ Value::Object(_)) = serde_json::from_str::<Value>(line.trim()) {
+            collector.add(&TranscriptEntry::from_value(0, value));
+        }
+    }
+
+    Ok(collector.metadata)
+}
+
+/// Builds `SessionMetadata` one entry at a time.
+#[derive(Default)]
+struct MetadataCollector {
+    metadata: SessionMetadata,
+    /// Tool names by `tool_use` ID, to attribute the errors in tool results.
+    tool_names: HashMap<String, String>,
+    /// IDs of the responses whose token usage is already counted.
+    counted_messages: HashSet<String>,
+}
+
+impl MetadataCollector {
+    fn add(&mut self, entry: &TranscriptEntry) {
+        let metadata = &mut self.metadata;
+        let field = |name: &str| entry.value.get(name).and_then(|v| v.as_str()).filter(|s| !s.is_empty());
+
+        if metadata.session_id.is_none() {
+            metadata.session_id = entry.session_id.clone().filter(|id| !id.is_empty());
+        }
+        if metadata.cwd.is_none() {
+            metadata.cwd = field("cwd").map(|s| s.to_string());
+        }
+        if let Some(branch) = field("gitBranch") {
+            metadata.git_branch = Some(branch.to_string());
+            if !metadata.git_branches.iter().any(|b| b == branch) {
+                metadata.git_branches.push(branch.to_string());
+            }
+        }
+        if let Some(version) = field("version") {
+            metadata.version = Some(version.to_string());
+        }
+
+        let message = entry.value.get("message");
+        let blocks = message.and_then(|m| m.get("content")).and_then(|c| c.as_array());
+        for block in blocks.into_iter().flatten() {
+            let text = |name: &str| block.get(name).and_then(|v| v.as_str());
+            match text("type") {
+                Some("tool_use") => {
+                    let name = text("name").unwrap_or("unknown");
+                    // Split responses may repeat a block; count each call once
+                    let is_new = match text("id") {
+                        Some(id) => self.tool_names.insert(id.to_string(), name.to_string()).is_none(),
+                        None => true,
+                    };
+                    if is_new {
+                        metadata.tools.record_call(name);
+                    }
+                }
+                Some("tool_result") if block.get("is_error").and_then(|e| e.as_bool()) == Some(true) => {
+                    // Results of calls made before a compaction have no call to count against
+                    if let Some(name) = text("tool_use_id").and_then(|id| self.tool_names.get(id)) {
+                        metadata.tools.record_error(name);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        match entry.entry_type.as_str() {
+            "user" if entry.is_tool_result() => metadata.tool_messages += 1,
+            "user" => metadata.user_messages += 1,
+            "assistant" => {
+                metadata.assistant_messages += 1;
+                // Errors and interruptions are written under a `<synthetic>` model
+                let model = message.and_then(|m| m.get("model")).and_then(|m| m.as_str());
+                if let Some(model) = model.filter(|m| !m.is_empty() && !m.starts_with('<')) {
+                    if !metadata.models.iter().any(|m| m == model) {
+                        metadata.models.push(model.to_string());
+                    }
+                }
+                if let Some(usage) = message.and_then(|m| m.get("usage")) {
+                    let is_new = match message.and_then(|m| m.get("id")).and_then(|id| id.as_str()) {
+                        Some(id) => self.counted_messages.insert(id.to_string()),
+                        None => true,
+                    };
+                    if is_new {
+                        let tokens = |name: &str| usage.get(name).and_then(|v| v.as_u64()).unwrap_or(0);
+                        metadata.tokens.add(&TokenUsage {
+                            input: tokens("input_tokens"),
+                            output: tokens("output_tokens"),
+                            cache_creation: tokens("cache_creation_input_tokens"),
+                            cache_read: tokens("cache_read_input_tokens"),
+                        });
+                    }
+                }
+            }
+            _ => return,
+        }
+        if let Some(timestamp) = entry.timestamp {
+            metadata.first_timestamp = Some(metadata.first_timestamp.map_or(timestamp, |t| t.min(timestamp)));
+            metadata.last_timestamp = Some(metadata.last_timestamp.map_or(timestamp, |t| t.max(timestamp)));
+        }
+    }
+}
+
+/// Returns the `sessionId` recorded in the first entries of a transcript. For
+/// sub-agent transcripts this is the ID of the parent session.
+pub fn read_session_id(path: &Path) -> Option<String> {
+    let reader = BufReader::new(File::open(path).ok()?);
+
+    reader
+        .lines()
+        .take(10)
+        .map_while(|line| line.ok())
+        .filter_map(|line| serde_json::from_str::<Value>(line.trim()).ok())
+        .find_map(|value| value.get("sessionId").and_then(|v| v.as_str()).map(|s| s.to_string()))
+}
+
+/// Returns the timestamp of the first user or assistant message without
+/// reading the rest of the transcript.
+pub fn read_first_timestamp(path: &Path) -> Option<DateTime<Utc>> {
+    let reader = BufReader::new(File::open(path).ok()?);
+
+    reader
+        .lines()
+        .map_while(|line| line.ok())
+        .filter_map(|line| serde_json::from_str::<Value>(line.trim()).ok())
+        .map(|value| TranscriptEntry::from_value(0, value))
+        .filter(|entry| entry.entry_type == "user" || entry.entry_type == "assistant")
+        .find_map(|entry| entry.timestamp)
+}
+
+/// Returns the working directory recorded in the first entries of a transcript.
+pub fn read_cwd(path: &Path) -> Option<String> {
+    let reader = BufReader::new(File::open(path).ok()?);
+
+    reader
+        .lines()
+        .take(10)
+        .map_while(|line| line.ok())
+        .filter_map(|line| serde_json::from_str::<Value>(line.trim()).ok())
+        .find_map(|value| value.get("cwd").and_then(|v| v.as_str()).filter(|s| !s.is_empty()).map(|s| s.to_string()))
+}
+
+/// Points the `cwd` of every entry that ran in `from`, or below it, at the
+/// same place under `to`. Lines are edited in place so that key order and
+/// formatting survive; returns the new content and the number of lines changed.
+pub fn rewrite_cwd(content: &str, from: &str, to: &str) -> (String, usize) {
+    // The JSON string without its closing quote, so subdirectories match too
+    let field = |path: &str| {
+        let quoted = serde_json::to_string(path).unwrap_or_default();
+        format!("\"cwd\":{}", &quoted[..quoted.len() - 1])
+    };
+    let (old_field, new_field) = (field(from), field(to));
+    let cwd_of = |line: &str| -> Option<String> {
+        let value = serde_json::from_str::<Value>(line.trim()).ok()?;
+        value.get("cwd").and_then(|v| v.as_str()).map(|s| s.to_string())
+    };
+
+    let mut rewritten = String::with_capacity(content.len());
+    let mut changed = 0;
+    for line in content.split_inclusive('\n') {
+        let expected = cwd_of(line).and_then(|cwd| {
+            let rest = cwd.strip_prefix(from)?;
+            (rest.is_empty() || rest.starts_with(['/', '\\'])).then(|| format!("{}{}", to, rest))
+        });
+        match expected {
+            Some(expected) if line.contains(&old_field) => {
+                let new_line = line.replacen(&old_field, &new_field, 1);
+                // A nested `cwd` may have matched first; leave such lines alone
+                if cwd_of(&new_line).as_deref() == Some(expected.as_str()) {
+                    rewritten.push_str(&new_line);
+                    changed += 1;
+                } else {
+                    rewritten.push_str(line);
+                }
+            }
+            _ => rewritten.push_str(line),
+        }
+    }
+    (rewritten, changed)
+}
+
+/// Returns the `uuid`s of a transcript's entries in file order.
+pub fn read_entry_uuids(path: &Path) -> Result<Vec<String>> {
+    Ok(read_entries(path)?.into_iter().filter_map(|e| e.uuid).collect())
+}
+
+/// Renders a raw transcript as readable text, one block per entry in file order.
+pub fn format_session_content(content: &str) -> String {
+    let mut result = String::new();
+    let mut has_any_content = false;
+    
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        
+        match serde_json::from_str::<Value>(line) {
+            Ok(json) => {
+                if let Some(block) = format_entry_block(&json) {
+                    has_any_content = true;
+                    result.push_str(&block);
+                    result.push_str("\n\n");
+                }
+            }
+            Err(_) => {
+                // Flag corrupt lines instead of passing them off as content
+                has_any_content = true;
+                result.push_str(&format!("─── ⚠️ INVALID LINE {} ───\n{}", index + 1, line));
+                result.push_str("\n\n");
+            }
+        }
+    }
+    
+    if !has_any_content {
+        "No content available".to_string()
+    } else {
+        result.trim_end().to_string()
+    }
+}
+
+/// Returns the display label for an entry, based on its `type` and message role.
+pub fn role_label(json: &Value) -> &'static str {
+    let msg_type = json.get("type").and_then(|v| v.as_str()).unwrap_or("unknown");
+    let role = json.get("message").and_then(|m| m.get("role")).and_then(|v| v.as_str()).unwrap_or("entry");
+    
+    match (msg_type, role) {
+        ("user", _) => "👤 USER",
+        ("assistant", _) => "🤖 CLAUDE",
+        ("system", _) => "⚙️ SYSTEM",
+        ("tool", _) => "🛠️ TOOL",
+        ("function", _) => "📌 FUNCTION",
+        _ => match role {
+            "user" => "👤 USER",
+            "assistant" => "🤖 CLAUDE",
+            "system" => "⚙️ SYSTEM",
+            "tool" => "🛠️ TOOL",
+            "function" => "📌 FUNCTION",
+            _ => "📝 ENTRY",
+        },
+    }
+}
+
+/// Formats one entry as a `─── ROLE ───` header followed by its text, or
+/// `None` when the entry carries no readable text.
+pub fn format_entry_block(json: &Value) -> Option<String> {
+    let extracted = extract_all_text(json);
+    
+    if extracted.is_empty() {
+        return None;
+    }
+    
+    let is_sidechain = json.get("isSidechain").and_then(|v| v.as_bool()).unwrap_or(false);
+    let thread_marker = if is_sidechain { "↳ SUB-AGENT · " } else { "" };
+    
+    Some(format!("─── {}{} ───\n{}", thread_marker, role_label(json), extracted))
+}
+
+pub fn extract_all_text(value: &Value) -> String {
+    let mut texts = Vec::new();
+    extract_text_recursive(value, &mut texts, 0);
+    texts.join("\n\n").trim().to_string()
+}
+
+fn extract_text_recursive(value: &Value, texts: &mut Vec<String>, depth: usize) {
+    if depth > 15 {
+        return;
+    }
+    
+    if let Some(message) = value.get("message") {
+        if let Some(content_val) = message.get("content") {
+            if let Some(content_arr) = content_val.as_array() {
+                for block in content_arr {
+                    extract_content_block(block, texts);
+                }
+                return;
+            }
+            if let Some(content_str) = content_val.as_str() {
+                let converted = content_str.replace("\\n", "\n").trim().to_string();
+                if !converted.is_empty() && converted != "{}" {
+                    texts.push(converted);
+                }
+                return;
+            }
+        }
+    }
+    
+    if let Some(content_val) = value.get("content") {
+        if let Some(content_arr) = content_val.as_array() {
+            for block in content_arr {
+                extract_content_block(block, texts);
+            }
+            return;
+        }
+    }
+    
+    match value {
+        Value::String(s) if !s.trim().is_empty() => {
+            let converted = s.replace("\\n", "\n");
+            let trimmed = converted.trim();
+            if !trimmed.is_empty() && trimmed != "{}" && !trimmed.starts_with("{") {
+                texts.push(trimmed.to_string());
+            }
+        }
+        Value::Object(obj) => {
+            for (key, val) in obj.iter() {
+                match key.as_str() {
+                    "text" | "content" | "message" | "output" | "result" | "input" | "thought" | "reasoning" => {
+                        extract_text_recursive(val, texts, depth + 1);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Value::Array(arr) => {
+            for item in arr.iter() {
+                extract_content_block(item, texts);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn extract_content_block(block: &Value, texts: &mut Vec<String>) {
+    if let Some(block_obj) = block.as_object() {
+        if let Some(text) = block_obj.get("text").and_then(|v| v.as_str()) {
+            let converted = text.replace("\\n", "\n").trim().to_string();
+            if !converted.is_empty() {
+                texts.push(converted);
+            }
+        }
+        
+        if let Some(thinking) = block_obj.get("thinking").and_then(|v| v.as_str()) {
+            let converted = thinking.replace("\\n", "\n").trim().to_string();
+            if !converted.is_empty() {
+                texts.push(format!("[Thinking]\n{}", converted));
+            }
+        }
+        
+        if let Some(tool_name) = block_obj.get("name").and_then(|v| v.as_str()) {
+            let mut tool_text = format!("[Tool: {}]", tool_name);
+            if let Some(input_val) = block_obj.get("input") {
+                if let Some(input) = input_val.as_object() {
+                    let input_str = format!("{:?}", input);
+                    if input_str.len() > 10 {
+                        tool_text.push_str(&format!("\n{}", input_str));
+                    }
+                }
+            }
+            texts.push(tool_text);
+        }
+        
+        if let Some(result) = block_obj.get("content") {
+            match result {
+                Value::String(s) => {
+                    let converted = s.replace("\\n", "\n").trim().to_string();
+                    if !converted.is_empty() {
+                        texts.push(converted);
+                    }
+                }
+                Value::Object(obj) => {
+                    if let Some(text) = obj.get("text").and_then(|v| v.as_str()) {
+                        let converted = text.replace("\\n", "\n").trim().to_string();
+                        if !converted.is_empty() {
+                            texts.push(converted);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        
+        if let Some(nested_val) = block_obj.get("content") {
+            if let Some(nested) = nested_val.as_array() {
+                for item in nested {
+                    extract_content_block(item, texts);
+                }
+            }
+        }
+        
+        if let Some(content_str) = block_obj.get("content").and_then(|v| v.as_str()) {
+            let converted = content_str.replace("\\n", "\n").trim().to_string();
+            if !converted.is_empty() {
+                texts.push(converted);
+            }
+        }
+    }
+}
@@ -0,0 +1,752 @@
+use crate::listing::{sort_projects, sort_sessions, ListFilter, SortKey};
+use crate::models::{Project, Session};
+use crate::session_manager::SessionManager;
+use crate::transcript::format_session_content;
+use crate::utils::format_size;
+use crate::viewer;
+use anyhow::Result;
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::{Attribute, Color, Print, SetAttribute, SetForegroundColor},
+    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Bytes of a transcript rendered in the preview pane.
+const PREVIEW_BYTES: u64 = 256 * 1024;
+/// Lines scrolled per `J`/`K` in the preview pane.
+const PREVIEW_STEP: usize = 5;
+
+/// How the full-screen browser was left.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TuiExit {
+    Quit,
+    /// Continue in the numbered menus.
+    Menu,
+}
+
+/// Cursor and scroll position in a list that may be taller than the screen.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Viewport {
+    pub cursor: usize,
+    /// Index of the first visible row.
+    pub offset: usize,
+}
+
+impl Viewport {
+    /// Moves the cursor by `delta` rows, stopping at either end.
+    pub fn move_by(&mut self, delta: isize, len: usize, height: usize) {
+        let last = len.saturating_sub(1) as isize;
+        self.cursor = (self.cursor as isize + delta).clamp(0, last.max(0)) as usize;
+        self.clamp(len, height);
+    }
+
+    /// Keeps the cursor within `len` rows and scrolls so it stays inside a
+    /// window of `height` rows.
+    pub fn clamp(&mut self, len: usize, height: usize) {
+        let height = height.max(1);
+        self.cursor = self.cursor.min(len.saturating_sub(1));
+        if self.cursor < self.offset {
+            self.offset = self.cursor;
+        } else if self.cursor >= self.offset + height {
+            self.offset = self.cursor + 1 - height;
+        }
+        self.offset = self.offset.min(len.saturating_sub(height));
+    }
+
+    /// 1-based page of the cursor and the number of pages.
+    pub fn page(&self, len: usize, height: usize) -> (usize, usize) {
+        let height = height.max(1);
+        (self.cursor / height + 1, len.saturating_sub(1) / height + 1)
+    }
+}
+
+/// What the status bar is currently used for.
+enum Mode {
+    Normal,
+    /// Typing a filter; holds the text entered so far.
+    Filter(String),
+    /// Waiting for `y` to delete these sessions.
+    ConfirmDelete(Vec<Session>),
+}
+
+/// The rendered transcript of the session under the cursor.
+struct Preview {
+    path: PathBuf,
+    lines: Vec<String>,
+    /// `lines` wrapped to the pane width they were last drawn at.
+    wrapped: Option<(usize, Vec<String>)>,
+}
+
+struct Browser<'a> {
+    manager: &'a mut SessionManager,
+    projects: Vec<Project>,
+    /// Working directory of each project, from its sessions' metadata.
+    real_paths: Vec<Option<String>>,
+    /// The project whose sessions are listed, or `None` for the project list.
+    open_project: Option<usize>,
+    project_view: Viewport,
+    session_view: Viewport,
+    project_sort: SortKey,
+    session_sort: SortKey,
+    project_filter: ListFilter,
+    session_filter: ListFilter,
+    /// Selected sessions by path, across projects.
+    selected: HashMap<PathBuf, Session>,
+    preview: Option<Preview>,
+    preview_scroll: usize,
+    mode: Mode,
+    message: Option<String>,
+    /// Terminal size at the last draw.
+    screen_size: Option<(usize, usize)>,
+}
+
+/// Restores the terminal when the browser exits, including on errors.
+pub(crate) struct TerminalGuard;
+
+impl TerminalGuard {
+    pub(crate) fn enter() -> Result<Self> {
+        terminal::enable_raw_mode()?;
+        let guard = TerminalGuard;
+        execute!(io::stdout(), EnterAlternateScreen, cursor::Hide)?;
+        Ok(guard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), LeaveAlternateScreen, cursor::Show);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Runs the full-screen session browser until the user quits or asks for
+/// the numbered menus.
+pub fn run(manager: &mut SessionManager) -> Result<TuiExit> {
+    let projects = manager.scan_projects()?;
+    let mut browser = Browser {
+        real_paths: real_paths(&projects),
+        projects,
+        manager,
+        open_project: None,
+        project_view: Viewport::default(),
+        session_view: Viewport::default(),
+        project_sort: SortKey::Name,
+        session_sort: SortKey::LastActivity,
+        project_filter: ListFilter::default(),
+        session_filter: ListFilter::default(),
+        selected: HashMap::new(),
+        preview: None,
+        preview_scroll: 0,
+        mode: Mode::Normal,
+        message: None,
+        screen_size: None,
+    };
+
+    let _guard = TerminalGuard::enter()?;
+    loop {
+        browser.draw()?;
+        // Resize events need no handling beyond the redraw at the top of the loop
+        if let Event::Key(key) = event::read()? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            if let Some(exit) = browser.handle_key(key)? {
+                return Ok(exit);
+            }
+        }
+    }
+}
+
+fn real_paths(projects: &[Project]) -> Vec<Option<String>> {
+    projects
+        .iter()
+        .map(|p| p.sessions.iter().find_map(|s| s.metadata.cwd.clone()))
+        .collect()
+}
+
+impl Browser<'_> {
+    /// Indices of the listed projects or sessions, sorted and filtered.
+    fn rows(&self) -> Vec<usize> {
+        match self.open_project {
+            None => sort_projects(&self.projects, self.project_sort)
+                .into_iter()
+                .filter(|&i| self.project_filter.matches_project(&self.projects[i], self.real_paths[i].as_deref()))
+                .collect(),
+            Some(p) => {
+                let sessions = &self.projects[p].sessions;
+                sort_sessions(sessions, self.session_sort)
+                    .into_iter()
+                    .filter(|&i| self.session_filter.matches_session(&sessions[i]))
+                    .collect()
+            }
+        }
+    }
+
+    fn view_mut(&mut self) -> &mut Viewport {
+        match self.open_project {
+            None => &mut self.project_view,
+            Some(_) => &mut self.session_view,
+        }
+    }
+
+    fn list_height() -> usize {
+        let (_, height) = terminal::size().unwrap_or((80, 24));
+        (height as usize).saturating_sub(2).max(1)
+    }
+
+    /// The session under the cursor, when a project is open.
+    fn current_session(&self, rows: &[usize]) -> Option<&Session> {
+        let project = &self.projects[self.open_project?];
+        rows.get(self.session_view.cursor).map(|&i| &project.sessions[i])
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<Option<TuiExit>> {
+        self.message = None;
+        match std::mem::replace(&mut self.mode, Mode::Normal) {
+            Mode::Filter(mut text) => {
+                match key.code {
+                    KeyCode::Enter => match ListFilter::parse(&text) {
+                        Ok(filter) => {
+                            match self.open_project {
+                                None => self.project_filter = filter,
+                                Some(_) => self.session_filter = filter,
+                            }
+                            *self.view_mut() = Viewport::default();
+                        }
+                        Err(e) => self.message = Some(e.to_string()),
+                    },
+                    KeyCode::Esc => {}
+                    KeyCode::Backspace => {
+                        text.pop();
+                        self.mode = Mode::Filter(text);
+                    }
+                    KeyCode::Char(c) => {
+                        text.push(c);
+                        self.mode = Mode::Filter(text);
+                    }
+                    _ => self.mode = Mode::Filter(text),
+                }
+                return Ok(None);
+            }
+            Mode::ConfirmDelete(sessions) => {
+                if matches!(key.code, KeyCode::Char('y') | KeyCode::Char('Y')) {
+                    self.delete(&sessions);
+                } else {
+                    self.message = Some("Deletion cancelled".to_string());
+                }
+                return Ok(None);
+            }
+            Mode::Normal => {}
+        }
+
+        let rows = self.rows();
+        let height = Self::list_height();
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Char('c') if ctrl => return Ok(Some(TuiExit::Quit)),
+            KeyCode::Char('q') => return Ok(Some(TuiExit::Quit)),
+            KeyCode::Char('m') => return Ok(Some(TuiExit::Menu)),
+            KeyCode::Char('d') if ctrl => self.view_mut().move_by(height as isize / 2, rows.len(), height),
+            KeyCode::Char('u') if ctrl => self.view_mut().move_by(-(height as isize) / 2, rows.len(), height),
+            KeyCode::Down | KeyCode::Char('j') => self.view_mut().move_by(1, rows.len(), height),
+            KeyCode::Up | KeyCode::Char('k') => self.view_mut().move_by(-1, rows.len(), height),
+            KeyCode::PageDown => self.view_mut().move_by(height as isize, rows.len(), height),
+            KeyCode::PageUp => self.view_mut().move_by(-(height as isize), rows.len(), height),
+            KeyCode::Home | KeyCode::Char('g') => self.view_mut().move_by(isize::MIN / 2, rows.len(), height),
+            KeyCode::End | KeyCode::Char('G') => self.view_mut().move_by(isize::MAX / 2, rows.len(), height),
+            KeyCode::Char('J') => self.preview_scroll += PREVIEW_STEP,
+            KeyCode::Char('K') => self.preview_scroll = self.preview_scroll.saturating_sub(PREVIEW_STEP),
+            KeyCode::Enter | KeyCode::Right | KeyCode::Char('l') if self.open_project.is_none() => {
+                if let Some(&p) = rows.get(self.project_view.cursor) {
+                    self.open_project = Some(p);
+                    self.session_view = Viewport::default();
+                    self.session_filter = ListFilter::default();
+                }
+            }
+            KeyCode::Enter | KeyCode::Char('v') if self.open_project.is_some() => {
+                if let Some(session) = self.current_session(&rows).cloned() {
+                    match self.manager.load_conversation(&session.path) {
+                        Ok(tree) => viewer::run(&session.name, &tree)?,
+                        Err(e) => self.message = Some(format!("Failed to read {}: {}", session.name, e)),
+                    }
+                    // The viewer drew over the whole screen
+                    self.screen_size = None;
+                }
+            }
+            KeyCode::Char('R') if self.open_project.is_some() => {
+                if let Some(session) = self.current_session(&rows).cloned() {
+                    match self.manager.resume_command(&session.path) {
+                        Ok(command) => {
+                            let status = viewer::suspended(|| Ok(command.run()))?;
+                            // Claude Code appended to the transcript and drew over the screen
+                            self.screen_size = None;
+                            self.message = match status {
+                                Ok(status) if status.success() => None,
+                                Ok(status) => Some(format!("{} exited with {}", command.program, status)),
+                                Err(e) => Some(format!("Failed to run {}: {}", command.program, e)),
+                            };
+                            if let Err(e) = self.reload() {
+                                self.message = Some(format!("Failed to rescan: {}", e));
+                            }
+                        }
+                        Err(e) => self.message = Some(format!("Cannot resume: {}", e)),
+                    }
+                }
+            }
+            KeyCode::Esc | KeyCode::Backspace | KeyCode::Left | KeyCode::Char('h') if self.open_project.is_some() => {
+                self.open_project = None;
+            }
+            KeyCode::Char(' ') => {
+                self.toggle(&rows);
+                self.view_mut().move_by(1, rows.len(), height);
+            }
+            KeyCode::Char('a') => {
+                let sessions = self.listed_sessions(&rows);
+                let select = !sessions.iter().all(|s| self.selected.contains_key(&s.path));
+                for session in sessions {
+                    self.set_selected(session, select);
+                }
+            }
+            KeyCode::Char('s') => {
+                let keys: &[SortKey] = match self.open_project {
+                    None => &SortKey::PROJECT_KEYS,
+                    Some(_) => &SortKey::SESSION_KEYS,
+                };
+                let sort = match self.open_project {
+                    None => &mut self.project_sort,
+                    Some(_) => &mut self.session_sort,
+                };
+                let next = keys.iter().position(|k| k == sort).map_or(0, |i| (i + 1) % keys.len());
+                *sort = keys[next];
+            }
+            KeyCode::Char('/') | KeyCode::Char('f') => {
+                let current = match self.open_project {
+                    None => &self.project_filter,
+                    Some(_) => &self.session_filter,
+                };
+                self.mode = Mode::Filter(current.text.clone());
+            }
+            KeyCode::Char('d') | KeyCode::Char('x') | KeyCode::Delete => {
+                let mut sessions: Vec<Session> = self.selected.values().cloned().collect();
+                if sessions.is_empty() {
+                    sessions.extend(self.current_session(&rows).cloned());
+                }
+                match sessions.is_empty() {
+                    true => self.message = Some("Nothing selected".to_string()),
+                    false => self.mode = Mode::ConfirmDelete(sessions),
+                }
+            }
+            KeyCode::Char('r') => {
+                if let Err(e) = self.reload() {
+                    self.message = Some(format!("Failed to rescan: {}", e));
+                }
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    /// Sessions behind the listed rows: every session of each listed project,
+    /// or the listed sessions of the open project.
+    fn listed_sessions(&self, rows: &[usize]) -> Vec<Session> {
+        match self.open_project {
+            None => rows.iter().flat_map(|&p| self.projects[p].sessions.iter().cloned()).collect(),
+            Some(p) => rows.iter().map(|&i| self.projects[p].sessions[i].clone()).collect(),
+        }
+    }
+
+    fn set_selected(&mut self, session: Session, select: bool) {
+        match select {
+            true => self.selected.insert(session.path.clone(), session),
+            false => self.selected.remove(&session.path),
+        };
+    }
+
+    /// Toggles the session under the cursor, or all sessions of the project
+    /// under the cursor.
+    fn toggle(&mut self, rows: &[usize]) {
+        let cursor = match self.open_project {
+            None => self.project_view.cursor,
+            Some(_) => self.session_view.cursor,
+        };
+        let sessions = match rows.get(cursor) {
+            Some(&row) => self.listed_sessions(&[row]),
+            None => return,
+        };
+        let select = !sessions.iter().all(|s| self.selected.contains_key(&s.path));
+        for session in sessions {
+            self.set_selected(session, select);
+        }
+    }
+
+    /// Deletes the sessions and rescans, reporting failures in the status
+    /// bar so the browser stays open.
+    fn delete(&mut self, sessions: &[Session]) {
+        let deleted = match self.manager.delete_sessions(sessions) {
+            Ok(deleted) => deleted,
+            Err(e) => {
+                self.message = Some(format!("Failed to delete sessions: {}", e));
+                return;
+            }
+        };
+        for session in sessions {
+            self.selected.remove(&session.path);
+        }
+        self.message = Some(match self.reload() {
+            Ok(()) => format!("Deleted {} of {} sessions", deleted, sessions.len()),
+            Err(e) => format!("Deleted {} of {} sessions, then failed to rescan: {}", deleted, sessions.len(), e),
+        });
+    }
+
+    /// Rescans the store, keeping the open project and the selection where they still exist.
+    fn reload(&mut self) -> Result<()> {
+        let open_path = self.open_project.map(|p| self.projects[p].path.clone());
+        self.projects = self.manager.scan_projects()?;
+        self.real_paths = real_paths(&self.projects);
+        self.open_project = open_path.and_then(|path| self.projects.iter().position(|p| p.path == path));
+        self.selected.retain(|path, _| path.exists());
+        self.preview = None;
+
+        let rows = self.rows().len();
+        let height = Self::list_height();
+        self.project_view.clamp(self.projects.len(), height);
+        self.view_mut().clamp(rows, height);
+        Ok(())
+    }
+
+    /// Loads the preview of a session once while the cursor rests on it, and
+    /// wraps it again only when the pane width changes.
+    fn load_preview(&mut self, path: &Path, width: usize) {
+        if self.preview.as_ref().map(|p| p.path.as_path()) != Some(path) {
+            self.preview_scroll = 0;
+            let lines = read_preview(path)
+                .map(|text| text.lines().map(|l| l.to_string()).collect())
+                .unwrap_or_else(|e| vec![format!("Failed to read transcript: {}", e)]);
+            self.preview = Some(Preview { path: path.to_path_buf(), lines, wrapped: None });
+        }
+        if let Some(preview) = self.preview.as_mut().filter(|p| p.wrapped.as_ref().map(|(w, _)| *w) != Some(width)) {
+            let wrapped = preview.lines.iter().flat_map(|line| wrap(line, width)).collect();
+            preview.wrapped = Some((width, wrapped));
+        }
+    }
+
+    fn draw(&mut self) -> Result<()> {
+        let (width, height) = terminal::size()?;
+        let (width, height) = (width as usize, height as usize);
+        let list_height = height.saturating_sub(2).max(1);
+        let list_width = (width * 2 / 5).max(30).min(width);
+        let preview_width = width.saturating_sub(list_width + 1);
+
+        let rows = self.rows();
+        let view = match self.open_project {
+            None => &mut self.project_view,
+            Some(_) => &mut self.session_view,
+        };
+        view.clamp(rows.len(), list_height);
+        let view = *view;
+
+        // Every row is padded to its full width, so the screen only needs
+        // clearing when the terminal was resized
+        let mut out = io::stdout();
+        if self.screen_size != Some((width, height)) {
+            self.screen_size = Some((width, height));
+            queue!(out, Clear(ClearType::All))?;
+        }
+        queue!(out, cursor::MoveTo(0, 0))?;
+
+        // Title: where we are, the order and the filter
+        let (place, sort, filter) = match self.open_project {
+            None => ("Projects".to_string(), self.project_sort, &self.project_filter),
+            Some(p) => (format!("Projects › {}", self.projects[p].name), self.session_sort, &self.session_filter),
+        };
+        let mut title = format!(" {} · sorted by {}", place, sort.label());
+        if !filter.is_empty() {
+            title.push_str(&format!(" · filter: {}", filter.text));
+        }
+        queue!(out, SetAttribute(Attribute::Bold), Print(fit(&title, width)), SetAttribute(Attribute::Reset))?;
+
+        // List pane
+        for line in view.offset..view.offset + list_height {
+            let row = match rows.get(line) {
+                Some(&row) => row,
+                None => {
+                    let blank = if rows.is_empty() && line == 0 { " Nothing to show" } else { "" };
+                    queue!(out, cursor::MoveTo(0, (line - view.offset + 1) as u16), Print(fit(blank, list_width)))?;
+                    continue;
+                }
+            };
+            let text = match self.open_project {
+                None => {
+                    let project = &self.projects[row];
+                    let all = !project.sessions.is_empty()
+                        && project.sessions.iter().all(|s| self.selected.contains_key(&s.path));
+                    let some = project.sessions.iter().any(|s| self.selected.contains_key(&s.path));
+                    let mark = if all { "[x]" } else if some { "[-]" } else { "[ ]" };
+                    format!("{} {:>9} {:>4}  {}", mark, project.format_size(), project.sessions.len(), project.name)
+                }
+                Some(p) => {
+                    let session = &self.projects[p].sessions[row];
+                    let mark = if self.selected.contains_key(&session.path) { "[x]" } else { "[ ]" };
+                    let modified = session
+                        .get_modified_datetime()
+                        .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+                        .unwrap_or_default();
+                    let title = session.content_preview.as_deref().unwrap_or(&session.name);
+                    format!("{} {} {:>9}  {}", mark, modified, format_size(session.total_size()), title)
+                }
+            };
+            queue!(out, cursor::MoveTo(0, (line - view.offset + 1) as u16))?;
+            if line == view.cursor {
+                queue!(out, SetAttribute(Attribute::Reverse), Print(fit(&text, list_width)), SetAttribute(Attribute::Reset))?;
+            } else {
+                queue!(out, Print(fit(&text, list_width)))?;
+            }
+        }
+
+        // Preview pane
+        if preview_width > 0 {
+            let text_width = preview_width - 1;
+            let (details, transcript) = self.preview_for(&rows, view.cursor);
+            let details: Vec<String> = details.iter().flat_map(|line| wrap(line, text_width)).collect();
+            let transcript = match transcript {
+                Some(path) => {
+                    self.load_preview(&path, text_width);
+                    let wrapped = self.preview.as_ref().and_then(|p| p.wrapped.as_ref());
+                    wrapped.map(|(_, lines)| lines.as_slice()).unwrap_or(&[])
+                }
+                None => &[],
+            };
+            let scroll = self.preview_scroll.min((details.len() + transcript.len()).saturating_sub(1));
+            for line in 0..list_height {
+                queue!(out, cursor::MoveTo(list_width as u16, (line + 1) as u16), Print("│"))?;
+                let index = scroll + line;
+                let text = match index.checked_sub(details.len()) {
+                    None => &details[index],
+                    Some(index) => transcript.get(index).map(String::as_str).unwrap_or(""),
+                };
+                let text = fit(text, text_width);
+                if is_header(&text) {
+                    queue!(out, SetForegroundColor(Color::Cyan), Print(text), SetForegroundColor(Color::Reset))?;
+                } else {
+                    queue!(out, Print(text))?;
+                }
+            }
+            self.preview_scroll = scroll;
+        }
+
+        // Status bar
+        let status = match &self.mode {
+            Mode::Filter(text) => format!(" Filter (name/title/path, glob, size>N, age<N), Enter to apply, Esc to cancel: {}_", text),
+            Mode::ConfirmDelete(sessions) => {
+                let size: u64 = sessions.iter().map(|s| s.total_size() + s.linked_size).sum();
+                format!(" Delete {} sessions ({}, including linked artifacts)? [y/N]", sessions.len(), format_size(size))
+            }
+            Mode::Normal => {
+                let size: u64 = self.selected.values().map(|s| s.total_size()).sum();
+                let (page, pages) = view.page(rows.len(), list_height);
+                let position = match rows.is_empty() {
+                    true => "0/0".to_string(),
+                    false => format!("{}/{}", view.cursor + 1, rows.len()),
+                };
+                let hint = match &self.message {
+                    Some(message) => message.clone(),
+                    None => "jk move · space select · enter/h open/back · v view · R resume · / filter · s sort · d delete · J/K preview · m menus · q quit"
+                        .to_string(),
+                };
+                format!(
+                    " {} selected ({}) │ {} · page {}/{} │ {}",
+                    self.selected.len(),
+                    format_size(size),
+                    position,
+                    page,
+                    pages,
+                    hint
+                )
+            }
+        };
+        queue!(
+            out,
+            cursor::MoveTo(0, height.saturating_sub(1) as u16),
+            SetAttribute(Attribute::Reverse),
+            Print(fit(&status, width)),
+            SetAttribute(Attribute::Reset)
+        )?;
+
+        out.flush()?;
+        Ok(())
+    }
+
+    /// Lines for the preview pane: a project summary, or the session's
+    /// details together with the transcript to render below them.
+    fn preview_for(&self, rows: &[usize], cursor: usize) -> (Vec<String>, Option<PathBuf>) {
+        let row = match rows.get(cursor) {
+            Some(&row) => row,
+            None => return (Vec::new(), None),
+        };
+        match self.open_project {
+            None => {
+                let project = &self.projects[row];
+                let latest = project.sessions.iter().max_by_key(|s| s.modified);
+                let mut lines = vec![
+                    format!("─── {} ───", project.name),
+                    format!("Path: {}", self.real_paths[row].as_deref().unwrap_or("unknown")),
+                    format!("Sessions: {}", project.sessions.len()),
+                    format!("Size: {} (+{} linked artifacts)", project.format_size(), format_size(project.linked_size())),
+                ];
+                if let Some(latest) = latest {
+                    let modified = latest.get_modified_datetime().map(|dt| dt.format("%Y-%m-%d %H:%M").to_string());
+                    lines.push(format!("Last activity: {}", modified.unwrap_or_default()));
+                    lines.push(String::new());
+                    lines.push("─── Latest session ───".to_string());
+                    lines.push(latest.content_preview.clone().unwrap_or_else(|| latest.name.clone()));
+                }
+                (lines, None)
+            }
+            Some(p) => {
+                let session = &self.projects[p].sessions[row];
+                let mut lines = vec![
+                    format!("─── {} ───", session.name),
+                    format!(
+                        "Size: {} · sub-agents: {} · linked: {}",
+                        format_size(session.total_size()),
+                        session.subagents.len(),
+                        format_size(session.linked_size)
+                    ),
+                ];
+                let metadata = &session.metadata;
+                if let Some(branch) = &metadata.git_branch {
+                    lines.push(format!("Branch: {}", branch));
+                }
+                lines.push(format!(
+                    "Messages: {} user · {} assistant · {} tool",
+                    metadata.user_messages, metadata.assistant_messages, metadata.tool_messages
+                ));
+                if !metadata.models.is_empty() {
+                    lines.push(format!("Models: {}", metadata.models.join(", ")));
+                }
+                if let Some(version) = &metadata.version {
+                    lines.push(format!("Claude Code {}", version));
+                }
+                lines.push(String::new());
+                (lines, Some(session.path.clone()))
+            }
+        }
+    }
+}
+
+/// Reads the start of a transcript and renders it, dropping a line cut off
+/// at the read limit.
+fn read_preview(path: &Path) -> Result<String> {
+    let mut bytes = Vec::new();
+    File::open(path)?.take(PREVIEW_BYTES).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 == PREVIEW_BYTES {
+        if let Some(end) = bytes.iter().rposition(|&b| b == b'\n') {
+            bytes.truncate(end);
+        }
+    }
+    Ok(format_session_content(&String::from_utf8_lossy(&bytes)))
+}
+
+/// Role headers in rendered transcripts look like `─── USER ───`.
+fn is_header(line: &str) -> bool {
+    line.starts_with("───")
+}
+
+/// Terminal columns taken by a character: two for wide East Asian
+/// characters and emoji, none for combining marks (as in Thai), one otherwise.
+fn char_width(c: char) -> usize {
+    match c as u32 {
+        0x0300..=0x036F | 0x0E31 | 0x0E34..=0x0E3A | 0x0E47..=0x0E4E | 0x200B..=0x200F | 0xFE00..=0xFE0F => 0,
+        0x1100..=0x115F
+        | 0x2E80..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1FAFF
+        | 0x20000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+/// Replaces tabs and control characters so they cannot move the cursor.
+fn printable(c: char) -> char {
+    if c.is_control() {
+        ' '
+    } else {
+        c
+    }
+}
+
+/// Cuts or pads `text` to exactly `width` terminal columns.
+pub fn fit(text: &str, width: usize) -> String {
+    let mut fitted = String::new();
+    let mut used = 0;
+    for c in text.chars().map(printable) {
+        let w = char_width(c);
+        if used + w > width {
+            break;
+        }
+        fitted.push(c);
+        used += w;
+    }
+    fitted.push_str(&" ".repeat(width - used));
+    fitted
+}
+
+/// Breaks a line into rows of at most `width` terminal columns.
+pub fn wrap(line: &str, width: usize) -> Vec<String> {
+    let mut rows = vec![String::new()];
+    let mut used = 0;
+    for c in line.chars().map(printable) {
+        let w = char_width(c);
+        if used + w > width && used > 0 {
+            rows.push(String::new());
+            used = 0;
+        }
+        if let Some(row) = rows.last_mut() {
+            row.push(c);
+        }
+        used += w;
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_viewport_scrolling_and_text_fitting() {
+        let mut view = Viewport::default();
+        view.move_by(25, 100, 10);
+        assert_eq!(view, Viewport { cursor: 25, offset: 16 });
+        view.move_by(-20, 100, 10);
+        assert_eq!(view, Viewport { cursor: 5, offset: 5 });
+        view.move_by(isize::MAX / 2, 100, 10);
+        assert_eq!(view, Viewport { cursor: 99, offset: 90 });
+        assert_eq!(view.page(100, 10), (10, 10));
+
+        // The list shrinks, e.g. after deleting or filtering
+        view.clamp(3, 10);
+        assert_eq!(view, Viewport { cursor: 2, offset: 0 });
+        view.clamp(0, 10);
+        assert_eq!(view, Viewport::default());
+        assert_eq!(view.page(0, 10), (1, 1));
+
+        assert_eq!(fit("a\tbc", 6), "a bc  ");
+        assert_eq!(fit("abcdef", 3), "abc");
+        assert_eq!(wrap("abcdefg", 3), ["abc", "def", "g"]);
+        assert_eq!(wrap("", 3), [""]);
+        // Wide characters take two columns, Thai vowel marks none
+        assert_eq!(fit("👤 ab", 4), "👤 a");
+        assert_eq!(wrap("日本語", 4), ["日本", "語"]);
+        assert_eq!(fit("ที่", 2), "ที่ ");
+    }
+}
@@ -1,21 +1,47 @@
-use crate::models::{MenuChoice, Project, Session, Statistics};
+use crate::activity::{Activity, DayActivity};
+use crate::conversation::ConversationTree;
+use crate::diff::{DiffMessage, DiffRow, SessionDiff};
+use crate::anonymize::Replacement;
+use crate::export::ExportFormat;
+use crate::models::{
+    Artifact, ArtifactCategory, ArtifactUsage, DoctorFinding, MenuChoice, MoveOutcome, Project, ProjectAction,
+    ProjectDetail, ProjectDuplicates, RankingAction, RedactionOutcome, RelocationReport, RepairOutcome, ResumeCommand,
+    Session, SessionAction, SessionChain, SessionIntegrity, SessionSecrets, Severity, StaleBranchReport, Statistics,
+};
+use crate::config::SecretPatternConfig;
+use crate::listing::{group_by_branch, sort_projects, sort_sessions, BranchGroup, ListFilter, SortKey};
+use crate::ranking::{RankedSession, StoreRanking};
+use crate::tools::ToolReport;
+use crate::transcript;
+use crate::utils::{bar, format_count, format_rate, format_size};
+use crate::{tui, viewer};
 use anyhow::Result;
+use chrono::{DateTime, Datelike, Local, Utc};
 use crossterm::{
     execute,
-    terminal::{Clear, ClearType},
+    terminal::{self, Clear, ClearType},
 };
 use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 use std::vec::Vec;
 
 #[derive(Clone)]
 pub struct UI {
     selected_sessions: Vec<bool>,
+    project_sort: SortKey,
+    project_filter: ListFilter,
+    session_sort: SortKey,
+    session_filter: ListFilter,
 }
 
 impl UI {
     pub fn new() -> Self {
         Self {
             selected_sessions: Vec::new(),
+            project_sort: SortKey::Name,
+            project_filter: ListFilter::default(),
+            session_sort: SortKey::LastActivity,
+            session_filter: ListFilter::default(),
         }
     }
 
@@ -35,32 +61,69 @@ impl UI {
             println!("[2] 🗂️  Manage by Project");
             println!("[3] 📅 Delete by Age");
             println!("[4] 🗑️  Delete Project");
-            println!("[5] ❌ Exit");
+            println!("[5] 🧹 Clean Up Artifacts");
+            println!("[6] 🧽 Sweep Orphaned Artifacts");
+            println!("[7] 🔁 Find Duplicate Sessions");
+            println!("[8] 🩺 Check Transcript Integrity");
+            println!("[9] 🏥 Doctor");
+            println!("[10] 🔐 Scan for Secrets");
+            println!("[11] 📈 Activity Heatmap");
+            println!("[12] 🏆 Largest Sessions");
+            println!("[13] 🚚 Relocate Project");
+            println!("[14] 🌿 Sessions on Deleted Branches");
+            println!("[15] 🖥️  Full-screen Browser");
+            println!("[16] ❌ Exit");
             println!();
-            print!("Select menu (1-5): ");
+            print!("Select menu (1-16): ");
             io::stdout().flush()?;
 
-            let input = self.read_single_char()?;
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
 
-            match input {
-                '1' => return Ok(MenuChoice::Statistics),
-                '2' => return Ok(MenuChoice::ManageProjects),
-                '3' => return Ok(MenuChoice::DeleteByAge),
-                '4' => return Ok(MenuChoice::DeleteProject),
-                '5' => return Ok(MenuChoice::Exit),
+            match input.trim() {
+                "1" => return Ok(MenuChoice::Statistics),
+                "2" => return Ok(MenuChoice::ManageProjects),
+                "3" => return Ok(MenuChoice::DeleteByAge),
+                "4" => return Ok(MenuChoice::DeleteProject),
+                "5" => return Ok(MenuChoice::CleanArtifacts),
+                "6" => return Ok(MenuChoice::SweepOrphans),
+                "7" => return Ok(MenuChoice::FindDuplicates),
+                "8" => return Ok(MenuChoice::CheckIntegrity),
+                "9" => return Ok(MenuChoice::Doctor),
+                "10" => return Ok(MenuChoice::ScanSecrets),
+                "11" => return Ok(MenuChoice::Activity),
+                "12" => return Ok(MenuChoice::Largest),
+                "13" => return Ok(MenuChoice::Relocate),
+                "14" => return Ok(MenuChoice::StaleBranches),
+                "15" => return Ok(MenuChoice::Browse),
+                "16" => return Ok(MenuChoice::Exit),
                 _ => {
-                    println!("Invalid choice, please select 1-5");
+                    println!("Invalid choice, please select 1-16");
                     std::thread::sleep(std::time::Duration::from_millis(1000));
                 }
             }
         }
     }
 
-    pub fn show_projects(&self, projects: &[Project]) -> Result<Option<usize>> {
+    /// Lists projects in the chosen order, narrowed by the current filter.
+    /// Numbers follow the displayed list; `i<n>` asks for the detail report
+    /// of project n.
+    pub fn show_projects(&mut self, projects: &[Project]) -> Result<ProjectAction> {
+        let real_paths: Vec<Option<String>> = projects
+            .iter()
+            .map(|p| p.sessions.iter().find_map(|s| transcript::read_cwd(&s.path)))
+            .collect();
+
         loop {
+            let order: Vec<usize> = sort_projects(projects, self.project_sort)
+                .into_iter()
+                .filter(|&i| self.project_filter.matches_project(&projects[i], real_paths[i].as_deref()))
+                .collect();
+
             self.clear_screen()?;
             println!("Projects ({} total)", projects.len());
             println!("==================");
+            println!("{}", list_status(self.project_sort, &self.project_filter, order.len(), projects.len()));
 
             if projects.is_empty() {
                 println!("No projects found");
@@ -72,21 +135,27 @@ impl UI {
                 let input = self.read_single_char()?;
 
                 if input == '0' {
-                    return Ok(None);
+                    return Ok(ProjectAction::Back);
                 }
                 continue;
             }
 
-            for (i, project) in projects.iter().enumerate() {
+            if order.is_empty() {
+                println!("No projects match the filter");
+            }
+            for (number, &i) in order.iter().enumerate() {
+                let project = &projects[i];
                 println!(
                     "[{}] {} ({} sessions, {})",
-                    i + 1,
+                    number + 1,
                     project.name,
                     project.sessions.len(),
                     project.format_size()
                 );
             }
             println!();
+            println!("[i<n>] Project details");
+            println!("[s] Sort  [f] Filter");
             println!("[0] Back");
             print!("Select project: ");
             io::stdout().flush()?;
@@ -95,26 +164,166 @@ impl UI {
             io::stdin().read_line(&mut input)?;
             let input = input.trim();
 
-            if input == "0" {
-                return Ok(None);
+            match input {
+                "0" => return Ok(ProjectAction::Back),
+                "s" => {
+                    if let Some(key) = self.prompt_sort(&SortKey::PROJECT_KEYS)? {
+                        self.project_sort = key;
+                    }
+                    continue;
+                }
+                "f" => {
+                    if let Some(filter) = self.prompt_filter(&self.project_filter)? {
+                        self.project_filter = filter;
+                    }
+                    continue;
+                }
+                _ => {}
             }
 
-            if let Ok(index) = input.parse::<usize>() {
-                if index > 0 && index <= projects.len() {
-                    return Ok(Some(index - 1));
+            let (details, number) = match input.strip_prefix('i') {
+                Some(number) => (true, number),
+                None => (false, input),
+            };
+            if let Ok(index) = number.parse::<usize>() {
+                if index > 0 && index <= order.len() {
+                    return Ok(match details {
+                        true => ProjectAction::Details(order[index - 1]),
+                        false => ProjectAction::Select(order[index - 1]),
+                    });
                 }
             }
 
-            println!("Invalid choice. Please select 0-{}", projects.len());
+            println!("Invalid choice. Please select 0-{}", order.len());
             std::thread::sleep(std::time::Duration::from_millis(1000));
         }
     }
 
-    pub fn show_sessions(&mut self, project: &Project) -> Result<Vec<usize>> {
-        // Initialize selection vector
-        self.selected_sessions = vec![false; project.sessions.len()];
+    /// Asks for one of the given sort keys. Returns `None` when cancelled.
+    fn prompt_sort(&self, keys: &[SortKey]) -> Result<Option<SortKey>> {
+        let options: Vec<String> = keys.iter().map(|k| format!("[{}] {}", k.shortcut(), k.label())).collect();
+        print!("Sort by {} (Enter = cancel): ", options.join("  "));
+        io::stdout().flush()?;
+
+        let input = self.read_single_char()?;
+        Ok(keys.iter().copied().find(|k| k.shortcut() == input))
+    }
+
+    /// Asks for a new filter, showing the current one. An empty answer clears
+    /// the filter; returns `None` when the input is invalid.
+    fn prompt_filter(&self, current: &ListFilter) -> Result<Option<ListFilter>> {
+        if !current.is_empty() {
+            println!("Current filter: {}", current.text);
+        }
+        println!("Filter by name, title or path (substring or glob like api-*), branch:NAME (- = none),");
+        print!("size>N / size<N (e.g. 10M) and age>N / age<N days. Enter = show all: ");
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        match ListFilter::parse(&input) {
+            Ok(filter) => Ok(Some(filter)),
+            Err(e) => {
+                println!("{}", e);
+                std::thread::sleep(std::time::Duration::from_millis(1500));
+                Ok(None)
+            }
+        }
+    }
+
+    /// Filters the session list. Only sessions on screen stay selected, so
+    /// deletion never reaches hidden ones.
+    fn set_session_filter(&mut self, project: &Project, filter: ListFilter) {
+        self.session_filter = filter;
+        for (i, session) in project.sessions.iter().enumerate() {
+            if !self.session_filter.matches_session(session) {
+                self.selected_sessions[i] = false;
+            }
+        }
+    }
+
+    /// Lists a project's sessions by git branch and asks what to do with one
+    /// branch: `f` to filter the list to it, `a` to archive or `x` to delete
+    /// all its sessions. Returns the command and the group's index.
+    fn prompt_branch_action(&self, project: &Project, groups: &[BranchGroup]) -> Result<Option<(char, usize)>> {
+        loop {
+            self.clear_screen()?;
+            println!("🌿 Sessions in '{}' by git branch", project.name);
+            println!("=========================================");
+            for (number, group) in groups.iter().enumerate() {
+                let last_activity: DateTime<Local> = group.last_activity.into();
+                println!(
+                    "{:>3}. {:<40} {:>4} sessions {:>10}  last active {}",
+                    number + 1,
+                    group.branch.as_deref().unwrap_or("(no branch)"),
+                    group.sessions.len(),
+                    format_size(group.size),
+                    last_activity.format("%Y-%m-%d %H:%M")
+                );
+            }
+            println!();
+            println!("[f<n>] Show only branch n's sessions");
+            println!("[a<n>] Archive all sessions of branch n");
+            println!("[x<n>] Delete all sessions of branch n");
+            println!("[0] Back");
+            print!("Select: ");
+            io::stdout().flush()?;
+
+            let mut input = String::new();
+            if io::stdin().read_line(&mut input)? == 0 {
+                return Ok(None);
+            }
+            let input = input.trim();
+            if input == "0" || input.is_empty() {
+                return Ok(None);
+            }
+            let command = input.chars().next().filter(|c| matches!(c, 'f' | 'a' | 'x'));
+            let number = input.get(1..).and_then(|n| n.trim().parse::<usize>().ok());
+            match (command, number) {
+                (Some(command), Some(n)) if n > 0 && n <= groups.len() => return Ok(Some((command, n - 1))),
+                _ => {
+                    println!("Invalid input. Please enter f<n>, a<n>, x<n> or 0");
+                    std::thread::sleep(std::time::Duration::from_millis(1000));
+                }
+            }
+        }
+    }
+
+    pub fn show_sessions(&mut self, project: &Project, chains: &[SessionChain]) -> Result<SessionAction> {
+        // Keep the selection when returning from a conversation view
+        if self.selected_sessions.len() != project.sessions.len() {
+            self.selected_sessions = vec![false; project.sessions.len()];
+        }
+
+        let chain_of = |session: &Session| chains.iter().position(|chain| chain.contains(session));
 
         loop {
+            let visible: Vec<usize> = sort_sessions(&project.sessions, self.session_sort)
+                .into_iter()
+                .filter(|&i| self.session_filter.matches_session(&project.sessions[i]))
+                .collect();
+            // Members of a continuation chain are listed together under one
+            // header where the first of them sorts, so numbers follow this display order
+            let mut order: Vec<usize> = Vec::new();
+            for &i in &visible {
+                match chain_of(&project.sessions[i]) {
+                    Some(c) if order.iter().any(|&j| chain_of(&project.sessions[j]) == Some(c)) => {}
+                    Some(c) => order.extend(
+                        chains[c]
+                            .sessions
+                            .iter()
+                            .rev()
+                            .filter_map(|member| project.sessions.iter().position(|s| s.path == member.path))
+                            .filter(|j| visible.contains(j)),
+                    ),
+                    None => order.push(i),
+                }
+            }
+            let parse_number = |text: &str| match text.trim().parse::<usize>() {
+                Ok(number) if number > 0 && number <= order.len() => Some(order[number - 1]),
+                _ => None,
+            };
+
             self.clear_screen()?;
             println!(
                 "Sessions in '{}' ({} total, {})",
@@ -123,8 +332,24 @@ impl UI {
                 project.format_size()
             );
             println!("=========================================");
+            println!("{}", list_status(self.session_sort, &self.session_filter, order.len(), project.sessions.len()));
+
+            let mut current_chain = None;
+            for (number, &i) in order.iter().enumerate() {
+                let session = &project.sessions[i];
+                let chain = chain_of(session);
+                if let Some(c) = chain.filter(|&c| Some(c) != current_chain) {
+                    let c = &chains[c];
+                    println!(
+                        "     ⛓ Continued conversation: {} sessions, {}, spanning {}",
+                        c.sessions.len(),
+                        c.format_size(),
+                        c.format_duration()
+                    );
+                }
+                current_chain = chain;
+                let indent = if chain.is_some() { "  ┆ " } else { "" };
 
-            for (i, session) in project.sessions.iter().enumerate() {
                 let selected = if self.selected_sessions[i] { "[x]" } else { "[ ]" };
                 let age = session.get_age_days();
                 let _modified = session.get_modified_datetime()
@@ -135,7 +360,9 @@ impl UI {
                 match &session.content_preview {
                     Some(content) => {
                         println!(
-                            "{} {} ({}, {} วันที่แล้ว) ► {}",
+                            "{:>3}. {}{} {} ({}, {} วันที่แล้ว) ► {}",
+                            number + 1,
+                            indent,
                             selected,
                             session.name,
                             session.format_size(),
@@ -145,7 +372,9 @@ impl UI {
                     }
                     None => {
                         println!(
-                            "{} {} ({}, {} วันที่แล้ว)",
+                            "{:>3}. {}{} {} ({}, {} วันที่แล้ว)",
+                            number + 1,
+                            indent,
                             selected,
                             session.name,
                             session.format_size(),
@@ -153,11 +382,32 @@ impl UI {
                         );
                     }
                 }
+
+                // Sub-agent threads are listed under their parent and deleted with it
+                for subagent in &session.subagents {
+                    println!(
+                        "           {}↳ {} ({}) ► {}",
+                        indent,
+                        subagent.name,
+                        subagent.format_size(),
+                        subagent.content_preview.as_deref().unwrap_or("")
+                    );
+                }
             }
             println!();
             println!("[a] Select All");
             println!("[d] Deselect All");
             println!("[x] Delete Selected");
+            println!("[v<n>] View conversation of session n");
+            println!("[r<n>] Resume session n in Claude Code");
+            if !chains.is_empty() {
+                println!("[c<n>] Select the whole chain of session n");
+            }
+            println!("[e<n>] Export session n (with its whole chain)");
+            println!("[m] Compare the two selected sessions side by side");
+            println!("[t] Move selected sessions to another project");
+            println!("[b] Sessions by git branch");
+            println!("[s] Sort  [f] Filter");
             println!("[0] Back");
             print!("Select: ");
             io::stdout().flush()?;
@@ -166,12 +416,105 @@ impl UI {
             io::stdin().read_line(&mut input)?;
             let input = input.trim();
 
+            let command = input
+                .chars()
+                .next()
+                .filter(|c| matches!(c, 'v' | 'r' | 'c' | 'e'))
+                .map(|c| (c, &input[1..]));
+            if let Some((command, number)) = command {
+                let index = match parse_number(number) {
+                    Some(index) => index,
+                    None => {
+                        println!("Invalid session number. Please select {0}1-{0}{1}", command, order.len());
+                        std::thread::sleep(std::time::Duration::from_millis(1000));
+                        continue;
+                    }
+                };
+                let members: Vec<usize> = match chain_of(&project.sessions[index]) {
+                    Some(c) => chains[c]
+                        .sessions
+                        .iter()
+                        .filter_map(|member| project.sessions.iter().position(|s| s.path == member.path))
+                        .collect(),
+                    None => vec![index],
+                };
+
+                match command {
+                    'v' => return Ok(SessionAction::View(index)),
+                    'r' => return Ok(SessionAction::Resume(index)),
+                    'e' => return Ok(SessionAction::Export(members)),
+                    _ => {
+                        let select = !members.iter().all(|&i| self.selected_sessions[i]);
+                        for i in members {
+                            self.selected_sessions[i] = select;
+                        }
+                    }
+                }
+                continue;
+            }
+
             match input {
-                "0" => return Ok(Vec::new()),
+                "0" => return Ok(SessionAction::Back),
                 "a" => {
-                    for selected in &mut self.selected_sessions {
-                        *selected = true;
+                    for &i in &order {
+                        self.selected_sessions[i] = true;
+                    }
+                }
+                "s" => {
+                    if let Some(key) = self.prompt_sort(&SortKey::SESSION_KEYS)? {
+                        self.session_sort = key;
+                    }
+                }
+                "f" => {
+                    if let Some(filter) = self.prompt_filter(&self.session_filter)? {
+                        self.set_session_filter(project, filter);
+                    }
+                }
+                "b" => {
+                    let groups = group_by_branch(&project.sessions);
+                    let (command, group) = match self.prompt_branch_action(project, &groups)? {
+                        Some((command, index)) => (command, &groups[index]),
+                        None => continue,
+                    };
+                    let sessions: Vec<&Session> = group.sessions.iter().map(|&i| &project.sessions[i]).collect();
+                    match command {
+                        'f' => self.set_session_filter(project, ListFilter::for_branch(group.branch.as_deref())?),
+                        'a' => {
+                            if self.confirm_archive(&sessions)? {
+                                return Ok(SessionAction::Archive(group.sessions.clone()));
+                            }
+                        }
+                        _ => {
+                            if self.confirm_session_deletion(&sessions) {
+                                return Ok(SessionAction::Delete(group.sessions.clone()));
+                            }
+                            println!("ยกเลิกการลบ");
+                            std::thread::sleep(std::time::Duration::from_millis(1000));
+                        }
+                    }
+                }
+                "m" => {
+                    let selected: Vec<usize> = (0..project.sessions.len()).filter(|&i| self.selected_sessions[i]).collect();
+                    match selected[..] {
+                        // Oldest on the left, so a resumed or retried copy reads as the change
+                        [a, b] => match project.sessions[a].modified <= project.sessions[b].modified {
+                            true => return Ok(SessionAction::Diff(a, b)),
+                            false => return Ok(SessionAction::Diff(b, a)),
+                        },
+                        _ => {
+                            println!("Select exactly two sessions to compare");
+                            std::thread::sleep(std::time::Duration::from_millis(1000));
+                        }
+                    }
+                }
+                "t" => {
+                    let selected: Vec<usize> = (0..project.sessions.len()).filter(|&i| self.selected_sessions[i]).collect();
+                    if selected.is_empty() {
+                        println!("No sessions selected");
+                        std::thread::sleep(std::time::Duration::from_millis(1000));
+                        continue;
                     }
+                    return Ok(SessionAction::Move(selected));
                 }
                 "d" => {
                     for selected in &mut self.selected_sessions {
@@ -193,23 +536,28 @@ impl UI {
                         continue;
                     }
 
-                    if self.confirm_deletion(selected_indices.len()) {
-                        return Ok(selected_indices);
+                    let selected: Vec<&Session> = selected_indices
+                        .iter()
+                        .map(|&i| &project.sessions[i])
+                        .collect();
+                    if self.confirm_session_deletion(&selected) {
+                        return Ok(SessionAction::Delete(selected_indices));
                     } else {
                         println!("ยกเลิกการลบ");
                         std::thread::sleep(std::time::Duration::from_millis(1000));
                     }
                 }
                 _ => {
-                    if let Ok(index) = input.parse::<usize>() {
-                        if index > 0 && index <= self.selected_sessions.len() {
-                            self.selected_sessions[index - 1] = !self.selected_sessions[index - 1];
-                        } else {
-                            println!("Invalid session number. Please select 1-{}", self.selected_sessions.len());
-                            std::thread::sleep(std::time::Duration::from_millis(1000));
+                    if input.parse::<usize>().is_ok() {
+                        match parse_number(input) {
+                            Some(index) => self.selected_sessions[index] = !self.selected_sessions[index],
+                            None => {
+                                println!("Invalid session number. Please select 1-{}", order.len());
+                                std::thread::sleep(std::time::Duration::from_millis(1000));
+                            }
                         }
                     } else {
-                        println!("Invalid input. Please enter a number, 'a', 'd', 'x', or '0'");
+                        println!("Invalid input. Please enter a number, 'a', 'd', 'x', 'v<n>', 'r<n>', 'c<n>', 'e<n>', 'm', 't', 'b', 's', 'f', or '0'");
                         std::thread::sleep(std::time::Duration::from_millis(1000));
                     }
                 }
@@ -217,18 +565,301 @@ impl UI {
         }
     }
 
+    /// Asks where to write an export and whether to anonymize it. Returns
+    /// `None` when cancelled.
+    pub fn prompt_export(&self, default_name: &str) -> Result<Option<(ExportFormat, PathBuf, bool)>> {
+        print!("Format [m]arkdown or [j]sonl (Enter = markdown, 0 = cancel): ");
+        io::stdout().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        let format = match input.trim() {
+            "" | "m" => ExportFormat::Markdown,
+            "j" => ExportFormat::Jsonl,
+            _ => return Ok(None),
+        };
+
+        let default_path = format!("{}.{}", default_name, format.extension());
+        print!("Write to [{}]: ", default_path);
+        io::stdout().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        let path = match input.trim() {
+            "" => PathBuf::from(default_path),
+            path => PathBuf::from(path),
+        };
+
+        print!("Anonymize paths, usernames, hostnames, emails and secrets? (y/N): ");
+        io::stdout().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        let input = input.trim().to_lowercase();
+        let anonymize = input == "y" || input == "yes";
+
+        Ok(Some((format, path, anonymize)))
+    }
+
+    pub fn show_export_result(&self, entry_count: usize, path: &Path) {
+        println!("✅ Exported {} entries to {}", entry_count, path.display());
+        std::thread::sleep(std::time::Duration::from_millis(2000));
+    }
+
+    /// Lists what an anonymized export replaced. Secrets are shown masked.
+    pub fn show_anonymization_report(&self, replacements: &[Replacement]) -> Result<()> {
+        if replacements.is_empty() {
+            println!("Nothing needed anonymizing");
+        } else {
+            println!();
+            println!("🕶  Replaced values:");
+            for replacement in replacements {
+                println!(
+                    "   {:<14} {} ({}×)",
+                    replacement.placeholder, replacement.original, replacement.occurrences
+                );
+            }
+        }
+        println!();
+        print!("Press Enter to continue...");
+        io::stdout().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        Ok(())
+    }
+
+    /// Opens the session in the transcript viewer, or pages it through
+    /// `$PAGER` when not running in a terminal.
+    pub fn show_conversation(&self, session: &Session, tree: &ConversationTree) -> Result<()> {
+        viewer::show(&session.name, tree)
+    }
+
+    /// Prints two sessions side by side: `=` marks a message matched by UUID,
+    /// `≈` one matched by content, `<` and `>` messages found on one side only.
+    /// Long runs of shared messages are folded down to their ends.
+    pub fn show_session_diff(&self, left: &Session, right: &Session, diff: &SessionDiff) -> Result<()> {
+        const CONTEXT: usize = 2;
+        let width = terminal::size().map(|(w, _)| w as usize).unwrap_or(120).max(40);
+        let column = (width - 3) / 2;
+        let cell = |message: Option<&DiffMessage>| match message {
+            Some(m) => tui::fit(&format!("{} {}", m.role, m.first_line()), column),
+            None => tui::fit("", column),
+        };
+
+        self.clear_screen()?;
+        println!("🔀 Session Diff");
+        println!("=========================================");
+        println!("Left:  {} ({} messages)", left.name, diff.left.len());
+        println!("Right: {} ({} messages)", right.name, diff.right.len());
+        match diff.divergence() {
+            None => println!("The conversations are identical ({} messages)", diff.rows.len()),
+            Some(_) => {
+                let line = |message: Option<&DiffMessage>| match message {
+                    Some(m) => format!("line {}", m.line_number),
+                    None => "the end".to_string(),
+                };
+                let (left_at, right_at) = diff.divergence_messages();
+                println!(
+                    "Shared prefix: {} messages; diverges at {} on the left and {} on the right",
+                    diff.shared_prefix(),
+                    line(left_at),
+                    line(right_at)
+                );
+                println!(
+                    "{} shared, {} only on the left, {} only on the right",
+                    diff.shared_count(),
+                    diff.left_only_count(),
+                    diff.right_only_count()
+                );
+            }
+        }
+        println!();
+
+        let mut row = 0;
+        while row < diff.rows.len() {
+            let run = diff.rows[row..].iter().take_while(|r| matches!(r, DiffRow::Shared { .. })).count();
+            // Keep the context next to a difference, fold the rest of the run
+            let head = if row == 0 { 0 } else { CONTEXT };
+            let tail = if row + run == diff.rows.len() { 0 } else { CONTEXT };
+            if run > head + tail + 1 {
+                for r in &diff.rows[row..row + head] {
+                    self.print_diff_row(diff, r, &cell);
+                }
+                println!("{}", tui::fit(&format!("   ⋯ {} shared messages ⋯", run - head - tail), width));
+                row += run - tail;
+                continue;
+            }
+            self.print_diff_row(diff, &diff.rows[row], &cell);
+            row += 1;
+        }
+
+        println!();
+        println!("Press Enter to go back...");
+        let _ = io::stdin().read_line(&mut String::new());
+        Ok(())
+    }
+
+    fn print_diff_row<F>(&self, diff: &SessionDiff, row: &DiffRow, cell: &F)
+    where
+        F: Fn(Option<&DiffMessage>) -> String,
+    {
+        let (left, marker, right) = match *row {
+            DiffRow::Shared { left, right, by_uuid } => {
+                (Some(&diff.left[left]), if by_uuid { "=" } else { "≈" }, Some(&diff.right[right]))
+            }
+            DiffRow::LeftOnly(i) => (Some(&diff.left[i]), "<", None),
+            DiffRow::RightOnly(j) => (None, ">", Some(&diff.right[j])),
+        };
+        println!("{} {} {}", cell(left), marker, cell(right).trim_end());
+    }
+
     pub fn show_statistics(&self, stats: &Statistics) {
         self.clear_screen().unwrap();
         println!("📊 Statistics");
         println!("========");
-        println!("Total projects: {}", stats.total_projects);
+        println!("Total projects: {} ({} empty)", stats.total_projects, stats.empty_projects);
         println!("Total sessions: {}", stats.total_sessions);
         println!("Total sessions size: {}", stats.format_total_size());
+        println!(
+            "Session size: {} average, {} median",
+            format_size(stats.average_session_size),
+            format_size(stats.median_session_size)
+        );
+        if let Some(project) = &stats.largest_project {
+            println!(
+                "Largest project: {} ({}, {} sessions)",
+                project.name,
+                project.format_size(),
+                project.sessions.len()
+            );
+        }
+        if let Some(oldest) = &stats.oldest_session {
+            println!(
+                "Oldest session: {}/{} ({} days old)",
+                oldest.project_name,
+                oldest.session.name,
+                oldest.session.get_age_days()
+            );
+        }
+        if let Some(newest) = &stats.newest_session {
+            println!(
+                "Newest session: {}/{} ({} days old)",
+                newest.project_name,
+                newest.session.name,
+                newest.session.get_age_days()
+            );
+        }
+        println!();
+        println!("Age distribution");
+        println!("----------------");
+        let max_count = stats.age_buckets.iter().map(|b| b.count).max().unwrap_or(0);
+        for bucket in &stats.age_buckets {
+            println!(
+                "{:<11} {:>6} {:>10}  {}",
+                bucket.label,
+                bucket.count,
+                bucket.format_size(),
+                bar(bucket.count as u64, max_count as u64, 30)
+            );
+        }
+        println!();
+        println!("Other artifacts");
+        println!("---------------");
+        for usage in &stats.artifact_usage {
+            println!(
+                "{:<16} {:>6} items  {:>10}",
+                usage.category.label(),
+                usage.count,
+                usage.format_size()
+            );
+        }
+        println!("Total artifacts size: {}", stats.format_total_artifacts_size());
+        self.show_tool_usage(&stats.tools);
         println!();
         println!("Press Enter to go back...");
         let _ = io::stdin().read_line(&mut String::new());
     }
 
+    fn show_tool_usage(&self, report: &ToolReport) {
+        const TOP_TOOLS: usize = 15;
+        const TOP_ENTRIES: usize = 5;
+
+        println!();
+        println!("Tool usage");
+        println!("----------");
+        let total = report.total();
+        if total.calls == 0 {
+            println!("No tool calls recorded.");
+            return;
+        }
+        println!(
+            "{} calls, {} failed ({})",
+            format_count(total.calls as u64),
+            format_count(total.errors as u64),
+            format_rate(total.error_rate())
+        );
+        let ranked = report.overall.ranked();
+        let max_calls = ranked.first().map(|(_, counts)| counts.calls).unwrap_or(0);
+        println!();
+        println!("{:<40} {:>8} {:>7} {:>6}", "Tool", "Calls", "Errors", "Rate");
+        for (tool, counts) in ranked.iter().take(TOP_TOOLS) {
+            println!(
+                "{:<40} {:>8} {:>7} {:>6}  {}",
+                tool,
+                counts.calls,
+                counts.errors,
+                format_rate(counts.error_rate()),
+                bar(counts.calls as u64, max_calls as u64, 20)
+            );
+        }
+        if ranked.len() > TOP_TOOLS {
+            println!("... and {} more tools", ranked.len() - TOP_TOOLS);
+        }
+
+        let servers = report.overall.mcp_servers();
+        if !servers.is_empty() {
+            println!();
+            println!("{:<40} {:>8} {:>7} {:>6}", "MCP server", "Calls", "Errors", "Rate");
+            for (server, counts) in &servers {
+                println!(
+                    "{:<40} {:>8} {:>7} {:>6}",
+                    server,
+                    counts.calls,
+                    counts.errors,
+                    format_rate(counts.error_rate())
+                );
+            }
+        }
+
+        println!();
+        println!("{:<40} {:>8} {:>7} {:>6}  Top tool", "Project", "Calls", "Errors", "Rate");
+        for project in report.projects.iter().take(TOP_ENTRIES) {
+            let counts = project.usage.total();
+            println!(
+                "{:<40} {:>8} {:>7} {:>6}  {}",
+                &project.project_name,
+                counts.calls,
+                counts.errors,
+                format_rate(counts.error_rate()),
+                project.usage.ranked().first().map(|(tool, _)| *tool).unwrap_or("-")
+            );
+        }
+
+        let failing = report.sessions_by_errors();
+        if !failing.is_empty() {
+            println!();
+            println!("Sessions with the most failed tool calls");
+            for entry in failing.iter().take(TOP_ENTRIES) {
+                let counts = entry.usage.total();
+                println!(
+                    "  {}/{}: {} of {} calls failed ({})",
+                    entry.project_name,
+                    entry.session.name,
+                    counts.errors,
+                    counts.calls,
+                    format_rate(counts.error_rate())
+                );
+            }
+        }
+    }
+
     pub fn confirm_deletion(&self, count: usize) -> bool {
         println!();
         println!("⚠️  Warning: Will delete {} items", count);
@@ -242,9 +873,48 @@ impl UI {
         input == "y" || input == "yes"
     }
 
-    pub fn confirm_project_deletion(&self, project_name: &str) -> bool {
+    /// Like `confirm_deletion`, but also reports the bytes freed including the
+    /// artifacts that are deleted along with the sessions.
+    pub fn confirm_session_deletion(&self, sessions: &[&Session]) -> bool {
+        let size: u64 = sessions.iter().map(|s| s.total_size()).sum();
+        let linked_size: u64 = sessions.iter().map(|s| s.linked_size).sum();
+        println!();
+        println!(
+            "Sessions: {}, linked artifacts: {}, total: {}",
+            format_size(size),
+            format_size(linked_size),
+            format_size(size + linked_size)
+        );
+        self.confirm_deletion(sessions.len())
+    }
+
+    /// Asks before moving sessions to the archive; says so when cancelled.
+    fn confirm_archive(&self, sessions: &[&Session]) -> Result<bool> {
+        print!(
+            "Move {} sessions to the archive ({})? (y/n): ",
+            sessions.len(),
+            format_size(sessions.iter().map(|s| s.total_size()).sum())
+        );
+        io::stdout().flush()?;
+        let mut answer = String::new();
+        io::stdin().read_line(&mut answer)?;
+        let answer = answer.trim().to_lowercase();
+        if answer == "y" || answer == "yes" {
+            return Ok(true);
+        }
+        println!("Archiving cancelled");
+        std::thread::sleep(std::time::Duration::from_millis(1000));
+        Ok(false)
+    }
+
+    pub fn confirm_project_deletion(&self, project: &Project) -> bool {
         println!();
-        println!("⚠️  Warning: Will delete project '{}' and all its sessions", project_name);
+        println!("⚠️  Warning: Will delete project '{}' and all its sessions", project.name);
+        println!(
+            "Sessions: {}, linked artifacts: {}",
+            project.format_size(),
+            format_size(project.linked_size())
+        );
         println!("This action cannot be undone!");
         println!();
         print!("confirm delete? (y/n): ");
@@ -278,6 +948,75 @@ impl UI {
         }
     }
 
+    /// Asks for the old and new working directory of a moved repository.
+    /// Returns `None` when cancelled.
+    pub fn prompt_relocation(&self) -> Result<Option<(String, String)>> {
+        self.clear_screen()?;
+        println!("🚚 Relocate Project");
+        println!("===================");
+        println!("Moves the history of a repository that moved on disk to its new location.");
+        println!();
+        let ask = |prompt: &str| -> Result<String> {
+            print!("{} (Enter to cancel): ", prompt);
+            io::stdout().flush()?;
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+            Ok(input.trim().to_string())
+        };
+        let old_cwd = ask("Old working directory")?;
+        if old_cwd.is_empty() {
+            return Ok(None);
+        }
+        let new_cwd = ask("New working directory")?;
+        if new_cwd.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some((old_cwd, new_cwd)))
+    }
+
+    /// Shows what a relocation changes. For a dry run without conflicts,
+    /// asks whether to apply it.
+    pub fn show_relocation_report(&self, report: &RelocationReport) -> bool {
+        println!();
+        match report.dry_run {
+            true => println!("Dry run, nothing has been changed yet:"),
+            false => println!("✅ Relocated:"),
+        }
+        println!("  {} → {}", report.old_cwd, report.new_cwd);
+        let action = match report.merge {
+            true => "merged into the existing",
+            false => "renamed to",
+        };
+        println!("  {} {} {}", report.source.display(), action, report.target.display());
+        println!(
+            "  {} sessions, cwd rewritten in {} entries across {} transcripts",
+            report.sessions, report.rewritten_entries, report.transcripts
+        );
+
+        if !report.conflicts.is_empty() {
+            println!();
+            println!("⚠️  {} files exist in both projects and block the merge:", report.conflicts.len());
+            for conflict in &report.conflicts {
+                println!("  {}", conflict.display());
+            }
+        }
+        if !report.dry_run || !report.conflicts.is_empty() {
+            println!();
+            println!("Press Enter to go back...");
+            let _ = io::stdin().read_line(&mut String::new());
+            return false;
+        }
+
+        println!();
+        print!("Relocate now? (y/n): ");
+        io::stdout().flush().unwrap();
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).unwrap();
+        let input = input.trim().to_lowercase();
+
+        input == "y" || input == "yes"
+    }
+
     pub fn show_old_sessions(&self, sessions: &[&Session], days: u64) -> Result<Vec<usize>> {
         loop {
             self.clear_screen()?;
@@ -317,7 +1056,7 @@ impl UI {
             }
 
             if input == "a" {
-                if self.confirm_deletion(sessions.len()) {
+                if self.confirm_session_deletion(sessions) {
                     let indices: Vec<usize> = (0..sessions.len()).collect();
                     return Ok(indices);
                 } else {
@@ -329,7 +1068,7 @@ impl UI {
 
             if let Ok(index) = input.parse::<usize>() {
                 if index > 0 && index <= sessions.len() {
-                    if self.confirm_deletion(1) {
+                    if self.confirm_session_deletion(&sessions[index - 1..index]) {
                         return Ok(vec![index - 1]);
                     } else {
                         println!("Deletion cancelled");
@@ -346,20 +1085,945 @@ impl UI {
         }
     }
 
-    pub fn show_deletion_result(&self, deleted_count: usize, item_type: &str) {
-        println!("✅ Successfully deleted {} {}", deleted_count, item_type);
-        std::thread::sleep(std::time::Duration::from_millis(2000));
-    }
+    pub fn show_artifact_categories(&self, usage: &[ArtifactUsage]) -> Result<Option<ArtifactCategory>> {
+        loop {
+            self.clear_screen()?;
+            println!("🧹 Clean Up Artifacts");
+            println!("====================");
 
-    pub fn show_error(&self, error: &str) {
-        println!("❌ Error: {}", error);
-        std::thread::sleep(std::time::Duration::from_millis(2000));
-    }
+            for (i, category_usage) in usage.iter().enumerate() {
+                println!(
+                    "[{}] {} ({}/, {} items, {})",
+                    i + 1,
+                    category_usage.category.label(),
+                    category_usage.category.dir_name(),
+                    category_usage.count,
+                    category_usage.format_size()
+                );
+            }
+            println!();
+            println!("[0] Back");
+            print!("Select category: ");
+            io::stdout().flush()?;
 
-    fn read_single_char(&self) -> Result<char> {
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
-        Ok(input.chars().next().unwrap_or('\r'))
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+            let input = input.trim();
+
+            if input == "0" {
+                return Ok(None);
+            }
+
+            if let Ok(index) = input.parse::<usize>() {
+                if index > 0 && index <= usage.len() {
+                    return Ok(Some(usage[index - 1].category));
+                }
+            }
+
+            println!("Invalid choice. Please select 0-{}", usage.len());
+            std::thread::sleep(std::time::Duration::from_millis(1000));
+        }
+    }
+
+    pub fn prompt_artifact_age_days(&self, category: ArtifactCategory) -> Result<u64> {
+        loop {
+            self.clear_screen()?;
+            println!("🧹 Clean Up {}", category.label());
+            println!("===============");
+            print!("Delete {} older than how many days: ", category.dir_name());
+            io::stdout().flush()?;
+
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+
+            match input.trim().parse::<u64>() {
+                Ok(days) => return Ok(days),
+                Err(_) => {
+                    println!("Please enter a valid number");
+                    std::thread::sleep(std::time::Duration::from_millis(1000));
+                }
+            }
+        }
+    }
+
+    pub fn show_old_artifacts(&self, artifacts: &[&Artifact], category: ArtifactCategory, days: u64) -> Result<Vec<usize>> {
+        let title = format!("{} older than {} days", category.label(), days);
+        self.show_artifacts(&title, artifacts)
+    }
+
+    pub fn show_artifacts(&self, title: &str, artifacts: &[&Artifact]) -> Result<Vec<usize>> {
+        let total_size: u64 = artifacts.iter().map(|a| a.size).sum();
+
+        loop {
+            self.clear_screen()?;
+            println!(
+                "{} ({} items, {})",
+                title,
+                artifacts.len(),
+                format_size(total_size)
+            );
+            println!("===============================");
+
+            for (i, artifact) in artifacts.iter().enumerate() {
+                println!(
+                    "[{}] {}/{} ({}, {} days ago)",
+                    i + 1,
+                    artifact.category.dir_name(),
+                    artifact.name,
+                    artifact.format_size(),
+                    artifact.get_age_days()
+                );
+            }
+            println!();
+            println!("[a] Delete All");
+            println!("[0] Back");
+            print!("Select: ");
+            io::stdout().flush()?;
+
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+            let input = input.trim();
+
+            if input == "0" {
+                return Ok(Vec::new());
+            }
+
+            if input == "a" {
+                if self.confirm_deletion(artifacts.len()) {
+                    return Ok((0..artifacts.len()).collect());
+                }
+                println!("Deletion cancelled");
+                std::thread::sleep(std::time::Duration::from_millis(1000));
+                continue;
+            }
+
+            if let Ok(index) = input.parse::<usize>() {
+                if index > 0 && index <= artifacts.len() {
+                    if self.confirm_deletion(1) {
+                        return Ok(vec![index - 1]);
+                    }
+                    println!("Deletion cancelled");
+                    std::thread::sleep(std::time::Duration::from_millis(1000));
+                } else {
+                    println!("Invalid choice. Please select 0-{}, 'a', or '0'", artifacts.len());
+                    std::thread::sleep(std::time::Duration::from_millis(1000));
+                }
+            } else {
+                println!("Invalid input. Please enter a number, 'a', or '0'");
+                std::thread::sleep(std::time::Duration::from_millis(1000));
+            }
+        }
+    }
+
+    /// Lists redundant session copies grouped by project. The returned indices
+    /// refer to the duplicates of all projects in display order.
+    pub fn show_duplicates(&self, projects: &[ProjectDuplicates]) -> Result<Vec<usize>> {
+        let duplicates: Vec<_> = projects.iter().flat_map(|p| p.duplicates.iter()).collect();
+        let total_size: u64 = projects.iter().map(|p| p.redundant_bytes()).sum();
+
+        loop {
+            self.clear_screen()?;
+            println!(
+                "🔁 Duplicate Sessions ({} redundant copies, {})",
+                duplicates.len(),
+                format_size(total_size)
+            );
+            println!("===============================");
+
+            let mut index = 0;
+            for project in projects {
+                println!();
+                println!("{} ({} redundant)", project.project_name, project.format_redundant_bytes());
+                for duplicate in &project.duplicates {
+                    index += 1;
+                    println!(
+                        "[{}] {} ({}, {}) ⊂ {} ({}, {} shared entries)",
+                        index,
+                        duplicate.redundant.name,
+                        duplicate.redundant.format_size(),
+                        duplicate.kind.label(),
+                        duplicate.kept.name,
+                        duplicate.kept.format_size(),
+                        duplicate.shared_entries
+                    );
+                }
+            }
+            println!();
+            println!("[a] Delete All Redundant Copies");
+            println!("[0] Back");
+            print!("Select: ");
+            io::stdout().flush()?;
+
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+            let input = input.trim();
+
+            if input == "0" {
+                return Ok(Vec::new());
+            }
+
+            if input == "a" {
+                let sessions: Vec<&Session> = duplicates.iter().map(|d| &d.redundant).collect();
+                if self.confirm_session_deletion(&sessions) {
+                    return Ok((0..duplicates.len()).collect());
+                }
+                println!("Deletion cancelled");
+                std::thread::sleep(std::time::Duration::from_millis(1000));
+                continue;
+            }
+
+            if let Ok(index) = input.parse::<usize>() {
+                if index > 0 && index <= duplicates.len() {
+                    if self.confirm_session_deletion(&[&duplicates[index - 1].redundant]) {
+                        return Ok(vec![index - 1]);
+                    }
+                    println!("Deletion cancelled");
+                    std::thread::sleep(std::time::Duration::from_millis(1000));
+                } else {
+                    println!("Invalid choice. Please select 0-{}, 'a', or '0'", duplicates.len());
+                    std::thread::sleep(std::time::Duration::from_millis(1000));
+                }
+            } else {
+                println!("Invalid input. Please enter a number, 'a', or '0'");
+                std::thread::sleep(std::time::Duration::from_millis(1000));
+            }
+        }
+    }
+
+    /// Lists transcripts with integrity issues. The returned indices are the
+    /// transcripts the user chose to repair.
+    pub fn show_integrity_report(&self, reports: &[SessionIntegrity]) -> Result<Vec<usize>> {
+        let repairable: Vec<usize> = (0..reports.len()).filter(|&i| reports[i].is_repairable()).collect();
+
+        loop {
+            self.clear_screen()?;
+            println!(
+                "🩺 Transcript Integrity ({} transcripts with issues, {} repairable)",
+                reports.len(),
+                repairable.len()
+            );
+            println!("===============================");
+
+            for (i, report) in reports.iter().enumerate() {
+                println!();
+                println!(
+                    "[{}] {}/{} ({} valid entries)",
+                    i + 1,
+                    report.project_name,
+                    report.session.name,
+                    report.valid_entries
+                );
+                for issue in report.issues.iter().take(5) {
+                    let marker = if issue.is_repairable() { "✗" } else { "⚠" };
+                    println!("      {} {}", marker, issue.describe());
+                }
+                if report.issues.len() > 5 {
+                    println!("      … and {} more", report.issues.len() - 5);
+                }
+            }
+            println!();
+            println!("Repair keeps only valid entries and backs up the original first.");
+            println!("⚠ issues (broken parentUuid links) are reported only.");
+            if !repairable.is_empty() {
+                println!("[a] Repair All");
+            }
+            println!("[0] Back");
+            print!("Select: ");
+            io::stdout().flush()?;
+
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+            let input = input.trim();
+
+            if input == "0" {
+                return Ok(Vec::new());
+            }
+
+            let selected = match input {
+                "a" if !repairable.is_empty() => repairable.clone(),
+                _ => match input.parse::<usize>() {
+                    Ok(index) if index > 0 && index <= reports.len() && reports[index - 1].is_repairable() => {
+                        vec![index - 1]
+                    }
+                    _ => {
+                        println!("Invalid input. Please enter the number of a repairable transcript, 'a', or '0'");
+                        std::thread::sleep(std::time::Duration::from_millis(1000));
+                        continue;
+                    }
+                },
+            };
+
+            print!("Repair {} transcripts? (y/n): ", selected.len());
+            io::stdout().flush()?;
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+            let input = input.trim().to_lowercase();
+            if input == "y" || input == "yes" {
+                return Ok(selected);
+            }
+            println!("Repair cancelled");
+            std::thread::sleep(std::time::Duration::from_millis(1000));
+        }
+    }
+
+    pub fn show_repair_results(&self, outcomes: &[RepairOutcome]) {
+        for outcome in outcomes {
+            match &outcome.backup_path {
+                Some(backup_path) => println!(
+                    "✅ {}: removed {} lines (backup: {})",
+                    outcome.path.display(),
+                    outcome.removed_lines,
+                    backup_path.display()
+                ),
+                None => println!("✅ {}: nothing to repair", outcome.path.display()),
+            }
+        }
+        std::thread::sleep(std::time::Duration::from_millis(2000));
+    }
+
+    /// Asks for an optional extra regex to scan with, on top of the built-in
+    /// detectors and the patterns in the config file.
+    pub fn prompt_secret_pattern(&self) -> Result<Option<SecretPatternConfig>> {
+        self.clear_screen()?;
+        println!("🔐 Scan for Secrets");
+        println!("===================");
+        println!("Built-in detectors: cloud keys, GitHub tokens, private keys, JWTs, high-entropy strings.");
+        print!("Extra regex to scan for (Enter to skip): ");
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        let pattern = input.trim();
+        Ok((!pattern.is_empty()).then(|| SecretPatternConfig {
+            name: "custom".to_string(),
+            pattern: pattern.to_string(),
+        }))
+    }
+
+    /// Lists transcripts containing secrets, masked. The returned indices are
+    /// the transcripts the user chose to redact.
+    pub fn show_secrets(&self, results: &[SessionSecrets]) -> Result<Vec<usize>> {
+        let total: usize = results.iter().map(|r| r.findings.len()).sum();
+
+        loop {
+            self.clear_screen()?;
+            println!("🔐 Secrets ({} findings in {} transcripts)", total, results.len());
+            println!("===============================");
+
+            for (i, result) in results.iter().enumerate() {
+                println!();
+                println!(
+                    "[{}] {}/{} ({} findings)",
+                    i + 1,
+                    result.project_name,
+                    result.session.name,
+                    result.findings.len()
+                );
+                for finding in result.findings.iter().take(5) {
+                    println!(
+                        "      line {}:{} ({}) {} {}",
+                        finding.line,
+                        finding.column,
+                        finding.entry_type,
+                        finding.detector,
+                        finding.masked
+                    );
+                }
+                if result.findings.len() > 5 {
+                    println!("      … and {} more", result.findings.len() - 5);
+                }
+            }
+            println!();
+            println!("Redaction replaces each secret with [REDACTED:<detector>] and backs up the original first.");
+            println!("[a] Redact All");
+            println!("[0] Back");
+            print!("Select: ");
+            io::stdout().flush()?;
+
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+            let input = input.trim();
+
+            if input == "0" {
+                return Ok(Vec::new());
+            }
+
+            let selected = match input {
+                "a" => (0..results.len()).collect::<Vec<_>>(),
+                _ => match input.parse::<usize>() {
+                    Ok(index) if index > 0 && index <= results.len() => vec![index - 1],
+                    _ => {
+                        println!("Invalid input. Please enter a number, 'a', or '0'");
+                        std::thread::sleep(std::time::Duration::from_millis(1000));
+                        continue;
+                    }
+                },
+            };
+
+            print!("Redact secrets in {} transcripts? (y/n): ", selected.len());
+            io::stdout().flush()?;
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+            let input = input.trim().to_lowercase();
+            if input == "y" || input == "yes" {
+                return Ok(selected);
+            }
+            println!("Redaction cancelled");
+            std::thread::sleep(std::time::Duration::from_millis(1000));
+        }
+    }
+
+    pub fn show_redaction_results(&self, outcomes: &[RedactionOutcome]) {
+        for outcome in outcomes {
+            match &outcome.backup_path {
+                Some(backup_path) => println!(
+                    "✅ {}: redacted {} secrets (backup: {})",
+                    outcome.path.display(),
+                    outcome.redacted,
+                    backup_path.display()
+                ),
+                None => println!("✅ {}: nothing to redact", outcome.path.display()),
+            }
+        }
+        std::thread::sleep(std::time::Duration::from_millis(2000));
+    }
+
+    pub fn show_project_detail(&self, detail: &ProjectDetail) -> Result<()> {
+        let local = |timestamp: Option<DateTime<Utc>>| match timestamp {
+            Some(timestamp) => timestamp.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string(),
+            None => "unknown".to_string(),
+        };
+
+        self.clear_screen()?;
+        println!("📁 Project: {}", detail.project.name);
+        println!("=========================================");
+        println!("Working directory: {}", detail.cwd.as_deref().unwrap_or("unknown"));
+        println!("First activity:    {}", local(detail.first_activity));
+        println!("Last activity:     {}", local(detail.last_activity));
+        println!(
+            "Sessions:          {} ({} total, {} average)",
+            detail.project.sessions.len(),
+            detail.project.format_size(),
+            format_size(detail.average_session_size())
+        );
+        println!("Messages:          {}", detail.message_count);
+        println!(
+            "Tokens:            {} in, {} out, {} cache write, {} cache read",
+            format_count(detail.tokens.input),
+            format_count(detail.tokens.output),
+            format_count(detail.tokens.cache_creation),
+            format_count(detail.tokens.cache_read)
+        );
+
+        if !detail.top_tools.is_empty() {
+            println!();
+            println!("Top tools");
+            println!("---------");
+            let max = detail.top_tools[0].1 as u64;
+            for (tool, count) in &detail.top_tools {
+                println!("{:<20} {:>6}  {}", tool, count, bar(*count as u64, max, 30));
+            }
+        }
+
+        if !detail.git_branches.is_empty() {
+            println!();
+            println!("Git branches");
+            println!("------------");
+            for (branch, sessions) in &detail.git_branches {
+                println!("{:<40} {} sessions", branch, sessions);
+            }
+        }
+
+        if !detail.largest_sessions.is_empty() {
+            println!();
+            println!("Largest sessions");
+            println!("----------------");
+            for session in &detail.largest_sessions {
+                println!(
+                    "{:>10}  {} ({} days old){}",
+                    session.format_size(),
+                    session.name,
+                    session.get_age_days(),
+                    session
+                        .content_preview
+                        .as_ref()
+                        .map(|preview| format!(" ► {}", preview.replace('\n', " ")))
+                        .unwrap_or_default()
+                );
+            }
+        }
+
+        println!();
+        print!("Press Enter to continue...");
+        io::stdout().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        Ok(())
+    }
+
+    /// Draws calendar heatmaps of the messages and sessions of the past year
+    /// and the weekday × hour matrix, shaded relative to the busiest cell.
+    pub fn show_activity(&self, activity: &Activity) -> Result<()> {
+        const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+        self.clear_screen()?;
+        println!("📈 Activity");
+        println!("===========");
+        println!(
+            "{} messages, {} active days in the past year",
+            activity.total_messages,
+            activity.active_days()
+        );
+        if let Some(day) = activity.busiest_day() {
+            println!(
+                "Busiest day: {} ({} messages in {} sessions)",
+                day.date, day.messages, day.sessions
+            );
+        }
+
+        let first = match activity.days.first() {
+            Some(day) => day.date,
+            None => return Ok(()),
+        };
+        // Columns are weeks starting on Monday; leading days before `first` stay blank
+        let offset = first.weekday().num_days_from_monday() as usize;
+        let weeks = (offset + activity.days.len() - 1) / 7 + 1;
+
+        let mut months = String::new();
+        let mut last_month = None;
+        for week in 0..weeks {
+            let day = activity.days.get((week * 7).saturating_sub(offset));
+            let month = day.map(|d| d.date.month());
+            if month != last_month && (week == 0 || months.chars().count() < week) {
+                let label = day.map(|d| d.date.format("%b").to_string()).unwrap_or_default();
+                months.push_str(&" ".repeat(week - months.chars().count()));
+                months.push_str(&label);
+                last_month = month;
+            }
+        }
+        let calendar = |title: &str, count: fn(&DayActivity) -> usize| {
+            let max = activity.days.iter().map(count).max().unwrap_or(0);
+            println!();
+            println!("{}", title);
+            println!("    {}", months);
+            for (row, weekday) in WEEKDAYS.iter().enumerate() {
+                let cells: String = (0..weeks)
+                    .map(|week| match (week * 7 + row).checked_sub(offset) {
+                        Some(index) if index < activity.days.len() => shade(count(&activity.days[index]), max),
+                        _ => ' ',
+                    })
+                    .collect();
+                println!("{} {}", weekday, cells);
+            }
+        };
+        calendar("Messages per day", |day| day.messages);
+        calendar("Sessions per day", |day| day.sessions);
+
+        println!();
+        println!("Messages by weekday and hour");
+        println!("    0     3     6     9     12    15    18    21");
+        let max = activity.hourly.iter().flatten().copied().max().unwrap_or(0);
+        for (row, weekday) in WEEKDAYS.iter().enumerate() {
+            let cells: String = activity.hourly[row]
+                .iter()
+                .flat_map(|&count| [shade(count, max); 2])
+                .collect();
+            println!("{} {}", weekday, cells);
+        }
+        println!();
+        println!("Less · ░ ▒ ▓ █ More");
+
+        println!();
+        print!("Press Enter to continue...");
+        io::stdout().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        Ok(())
+    }
+
+    /// Lists the largest and fastest-growing sessions of the whole store and
+    /// the largest projects. Numbers toggle sessions; a session listed twice
+    /// is selected in both places.
+    pub fn show_ranking(&self, ranking: &StoreRanking) -> Result<RankingAction> {
+        let entries: Vec<&RankedSession> =
+            ranking.largest_sessions.iter().chain(&ranking.fastest_growing).collect();
+        let mut selected = vec![false; entries.len()];
+
+        loop {
+            self.clear_screen()?;
+            println!("🏆 Largest Sessions");
+            println!("===================");
+            if entries.is_empty() {
+                println!("No sessions found");
+            }
+
+            for (i, entry) in entries.iter().enumerate() {
+                if i == 0 {
+                    println!();
+                    println!("Largest sessions:");
+                } else if i == ranking.largest_sessions.len() {
+                    println!();
+                    println!("Fastest growing:");
+                }
+
+                let session = &entry.session;
+                let detail = match (i < ranking.largest_sessions.len(), entry.median_ratio()) {
+                    (true, Some(ratio)) => format!("{:.1}× project median", ratio),
+                    (true, None) => String::new(),
+                    (false, _) => format!("{}/day", format_size(entry.growth_per_day.unwrap_or(0))),
+                };
+                println!(
+                    "[{}] {} {}/{} ({}, {} days) {}{}",
+                    i + 1,
+                    if selected[i] { "✓" } else { " " },
+                    entry.project_name,
+                    session.name,
+                    format_size(session.total_size()),
+                    session.get_age_days(),
+                    detail,
+                    if entry.is_outlier { " ⚠️ outlier" } else { "" }
+                );
+            }
+
+            if !ranking.largest_projects.is_empty() {
+                println!();
+                println!("Largest projects:");
+                for project in &ranking.largest_projects {
+                    println!(
+                        "  {} ({} sessions, {})",
+                        project.name,
+                        project.sessions.len(),
+                        project.format_size()
+                    );
+                }
+            }
+
+            let chosen: Vec<usize> = (0..entries.len()).filter(|&i| selected[i]).collect();
+            let mut unique: Vec<&Session> = Vec::new();
+            for &i in &chosen {
+                if !unique.iter().any(|s| s.path == entries[i].session.path) {
+                    unique.push(&entries[i].session);
+                }
+            }
+
+            println!();
+            println!(
+                "Selected: {} sessions, {}",
+                unique.len(),
+                format_size(unique.iter().map(|s| s.total_size()).sum())
+            );
+            println!("[<n> ...] Toggle sessions (e.g. 1 3 5)");
+            println!("[x] Delete Selected");
+            println!("[r] Archive Selected");
+            println!("[0] Back");
+            print!("Select: ");
+            io::stdout().flush()?;
+
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+            let input = input.trim();
+
+            match input {
+                "0" => return Ok(RankingAction::Back),
+                "x" | "r" if unique.is_empty() => {
+                    println!("No sessions selected");
+                    std::thread::sleep(std::time::Duration::from_millis(1000));
+                }
+                "x" => {
+                    if self.confirm_session_deletion(&unique) {
+                        return Ok(RankingAction::Delete(chosen));
+                    }
+                    println!("Deletion cancelled");
+                    std::thread::sleep(std::time::Duration::from_millis(1000));
+                }
+                "r" => {
+                    if self.confirm_archive(&unique)? {
+                        return Ok(RankingAction::Archive(chosen));
+                    }
+                }
+                _ => {
+                    let numbers: Option<Vec<usize>> = input
+                        .split(|c: char| c == ',' || c.is_whitespace())
+                        .filter(|n| !n.is_empty())
+                        .map(|n| n.parse::<usize>().ok().filter(|&n| n > 0 && n <= entries.len()))
+                        .collect();
+                    match numbers {
+                        Some(numbers) if !numbers.is_empty() => {
+                            for n in numbers {
+                                let path = &entries[n - 1].session.path;
+                                let select = !selected[n - 1];
+                                for (i, entry) in entries.iter().enumerate() {
+                                    if &entry.session.path == path {
+                                        selected[i] = select;
+                                    }
+                                }
+                            }
+                        }
+                        _ => {
+                            println!("Invalid input. Please enter numbers 1-{}, 'x', 'r' or '0'", entries.len());
+                            std::thread::sleep(std::time::Duration::from_millis(1000));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Lists the sessions whose git branch is gone, grouped by repository and
+    /// branch and all selected, for deletion or archiving.
+    pub fn show_stale_branches(&self, report: &StaleBranchReport) -> Result<RankingAction> {
+        let entries = &report.sessions;
+        // Numbered in display order, grouped by repository and branch
+        let mut order: Vec<usize> = (0..entries.len()).collect();
+        order.sort_by(|&a, &b| {
+            (&entries[a].repository, &entries[a].branch).cmp(&(&entries[b].repository, &entries[b].branch))
+        });
+        let mut selected = vec![true; entries.len()];
+
+        loop {
+            self.clear_screen()?;
+            println!("🌿 Sessions on Deleted Branches");
+            println!("==============================");
+            println!(
+                "Checked the local and remote-tracking branches of {} repositories",
+                report.repositories
+            );
+            if report.unchecked > 0 {
+                println!(
+                    "{} sessions could not be checked (directory gone, not a git repository or unreadable refs)",
+                    report.unchecked
+                );
+            }
+            if entries.is_empty() {
+                println!();
+                println!("✅ Every session's branch still exists");
+            }
+
+            let mut current = None;
+            for (number, &i) in order.iter().enumerate() {
+                let entry = &entries[i];
+                if current != Some((&entry.repository, &entry.branch)) {
+                    current = Some((&entry.repository, &entry.branch));
+                    println!();
+                    println!("{} · {}", entry.repository.display(), entry.branch);
+                }
+                println!(
+                    "[{}] {} {}/{} ({}, {} days) {}",
+                    number + 1,
+                    if selected[i] { "✓" } else { " " },
+                    entry.project_name,
+                    entry.session.name,
+                    format_size(entry.session.total_size()),
+                    entry.session.get_age_days(),
+                    entry.session.content_preview.as_deref().unwrap_or("")
+                );
+            }
+
+            let chosen: Vec<usize> = (0..entries.len()).filter(|&i| selected[i]).collect();
+            let sessions: Vec<&Session> = chosen.iter().map(|&i| &entries[i].session).collect();
+            println!();
+            println!(
+                "Selected: {} sessions, {}",
+                sessions.len(),
+                format_size(sessions.iter().map(|s| s.total_size()).sum())
+            );
+            println!("[<n> ...] Toggle sessions (e.g. 1 3 5)");
+            println!("[a] Select All / None");
+            println!("[x] Delete Selected");
+            println!("[r] Archive Selected");
+            println!("[0] Back");
+            print!("Select: ");
+            io::stdout().flush()?;
+
+            let mut input = String::new();
+            if io::stdin().read_line(&mut input)? == 0 {
+                return Ok(RankingAction::Back);
+            }
+            let input = input.trim();
+
+            match input {
+                "0" => return Ok(RankingAction::Back),
+                "a" => {
+                    let select = !selected.iter().all(|&s| s);
+                    selected.iter_mut().for_each(|s| *s = select);
+                }
+                "x" | "r" if sessions.is_empty() => {
+                    println!("No sessions selected");
+                    std::thread::sleep(std::time::Duration::from_millis(1000));
+                }
+                "x" => {
+                    if self.confirm_session_deletion(&sessions) {
+                        return Ok(RankingAction::Delete(chosen));
+                    }
+                    println!("Deletion cancelled");
+                    std::thread::sleep(std::time::Duration::from_millis(1000));
+                }
+                "r" => {
+                    if self.confirm_archive(&sessions)? {
+                        return Ok(RankingAction::Archive(chosen));
+                    }
+                }
+                _ => {
+                    let numbers: Option<Vec<usize>> = input
+                        .split(|c: char| c == ',' || c.is_whitespace())
+                        .filter(|n| !n.is_empty())
+                        .map(|n| n.parse::<usize>().ok().filter(|&n| n > 0 && n <= order.len()))
+                        .collect();
+                    match numbers {
+                        Some(numbers) if !numbers.is_empty() => {
+                            for n in numbers {
+                                selected[order[n - 1]] = !selected[order[n - 1]];
+                            }
+                        }
+                        _ => {
+                            println!("Invalid input. Please enter numbers 1-{}, 'a', 'x', 'r' or '0'", order.len());
+                            std::thread::sleep(std::time::Duration::from_millis(1000));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn show_doctor_report(&self, findings: &[DoctorFinding]) -> Result<()> {
+        self.clear_screen()?;
+        println!("🏥 Doctor");
+        println!("=========");
+
+        if findings.is_empty() {
+            println!("✅ No problems found");
+        }
+
+        for finding in findings {
+            let icon = match finding.severity {
+                Severity::Error => "❌",
+                Severity::Warning => "⚠️ ",
+                Severity::Info => "ℹ️ ",
+            };
+            println!();
+            println!("{} [{}] {}", icon, finding.check, finding.message);
+            if let Some(path) = &finding.path {
+                println!("     {}", path.display());
+            }
+            println!("     → {}", finding.suggestion);
+        }
+
+        let count = |severity: Severity| findings.iter().filter(|f| f.severity == severity).count();
+        println!();
+        println!(
+            "{} errors, {} warnings, {} notes",
+            count(Severity::Error),
+            count(Severity::Warning),
+            count(Severity::Info)
+        );
+        println!();
+        println!("Press Enter to go back...");
+        let _ = io::stdin().read_line(&mut String::new());
+        Ok(())
+    }
+
+    /// Asks whether to point the `cwd` recorded in moved transcripts at the
+    /// target project. Returns `None` when cancelled, `Some(None)` to keep the
+    /// recorded directory.
+    pub fn prompt_move_cwd(&self, count: usize, target: &Project, suggested: Option<&str>) -> Result<Option<Option<String>>> {
+        self.clear_screen()?;
+        println!("📦 Move {} sessions to '{}'", count, target.name);
+        println!("==================");
+        println!("Rewriting the working directory (cwd) recorded in the transcripts lets");
+        println!("`claude --resume` find the sessions from the target directory.");
+        println!();
+        match suggested {
+            Some(path) => print!("New cwd (Enter = {}, n = keep the recorded cwd, 0 = cancel): ", path),
+            None => print!("New cwd (Enter or n = keep the recorded cwd, 0 = cancel): "),
+        }
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        Ok(match input.trim() {
+            "0" => None,
+            "n" => Some(None),
+            "" => Some(suggested.map(|path| path.to_string())),
+            path => Some(Some(path.to_string())),
+        })
+    }
+
+    /// Shows the command that resumes a session, to copy into another terminal,
+    /// and asks whether to run it here instead.
+    pub fn confirm_resume(&self, session: &Session, command: &ResumeCommand) -> Result<bool> {
+        println!();
+        println!("▶️  Resume {}", session.name);
+        println!("{}", command.shell_line());
+        print!("Launch it now? (y = launch, Enter = back): ");
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        let input = input.trim().to_lowercase();
+
+        Ok(input == "y" || input == "yes")
+    }
+
+    /// Reports each move and asks whether the originals, kept in the trash so
+    /// far, can be deleted now.
+    pub fn show_move_results(&self, results: &[(Session, Result<MoveOutcome>)]) -> Result<bool> {
+        println!();
+        for (session, result) in results {
+            match result {
+                Ok(outcome) if outcome.rewritten_cwd > 0 => println!(
+                    "✅ {} → {} (cwd rewritten in {} entries)",
+                    session.name,
+                    outcome.target_path.display(),
+                    outcome.rewritten_cwd
+                ),
+                Ok(outcome) => println!("✅ {} → {}", session.name, outcome.target_path.display()),
+                Err(e) => println!("❌ {}: {}", session.name, e),
+            }
+        }
+
+        let trash_dir = match results.iter().find_map(|(_, result)| result.as_ref().ok()) {
+            Some(outcome) => outcome.trash_path.parent().unwrap_or(&outcome.trash_path),
+            None => {
+                std::thread::sleep(std::time::Duration::from_millis(2000));
+                return Ok(false);
+            }
+        };
+        println!();
+        println!("The originals are kept in {}.", trash_dir.display());
+        println!("Check that the moved sessions resume correctly before deleting them.");
+        print!("Delete the originals now? (y/n): ");
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        let input = input.trim().to_lowercase();
+
+        Ok(input == "y" || input == "yes")
+    }
+
+    pub fn show_deletion_result(&self, deleted_count: usize, item_type: &str) {
+        println!("✅ Successfully deleted {} {}", deleted_count, item_type);
+        std::thread::sleep(std::time::Duration::from_millis(2000));
+    }
+
+    pub fn show_error(&self, error: &str) {
+        println!("❌ Error: {}", error);
+        std::thread::sleep(std::time::Duration::from_millis(2000));
+    }
+
+    fn read_single_char(&self) -> Result<char> {
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        Ok(input.chars().next().unwrap_or('\r'))
+    }
+}
+
+/// One line describing the order and filter of a list, e.g.
+/// `Sorted by size · filter: api* (3 of 12 shown)`.
+fn list_status(sort: SortKey, filter: &ListFilter, shown: usize, total: usize) -> String {
+    match filter.is_empty() {
+        true => format!("Sorted by {}", sort.label()),
+        false => format!("Sorted by {} · filter: {} ({} of {} shown)", sort.label(), filter.text, shown, total),
     }
 }
 
@@ -367,4 +2031,14 @@ impl Default for UI {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+/// Heatmap cell for `value` on a scale where `max` is the darkest shade.
+fn shade(value: usize, max: usize) -> char {
+    const SHADES: [char; 5] = ['·', '░', '▒', '▓', '█'];
+    if value == 0 || max == 0 {
+        return SHADES[0];
+    }
+    let level = (value as f64 / max as f64 * 4.0).ceil() as usize;
+    SHADES[level.clamp(1, 4)]
+}
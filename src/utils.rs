@@ -1,2 +1,124 @@
-// Utility functions for future use
-// This module is kept for potential future functionality
\ No newline at end of file
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+/// Formats a byte count using the same B/KB/MB/GB units shown throughout the UI.
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: &[&str] = &["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit_index = 0;
+
+    while size >= 1024.0 && unit_index < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit_index += 1;
+    }
+
+    if unit_index == 0 {
+        format!("{} {}", size as u64, UNITS[unit_index])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit_index])
+    }
+}
+
+/// Parses a size such as `512`, `1.5K`, `10MB` or `2 GB` (binary units, like
+/// `format_size`). Returns `None` for anything else.
+pub fn parse_size(text: &str) -> Option<u64> {
+    let text = text.trim().to_uppercase();
+    let split = text.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let multiplier = match unit.trim().trim_end_matches('B') {
+        "" => 1u64,
+        "K" => 1 << 10,
+        "M" => 1 << 20,
+        "G" => 1 << 30,
+        _ => return None,
+    };
+    Some((number * multiplier as f64) as u64)
+}
+
+/// Formats a large count compactly, e.g. `12.3K` or `4.5M`.
+pub fn format_count(count: u64) -> String {
+    match count {
+        0..=999 => count.to_string(),
+        1_000..=999_999 => format!("{:.1}K", count as f64 / 1e3),
+        1_000_000..=999_999_999 => format!("{:.1}M", count as f64 / 1e6),
+        _ => format!("{:.1}B", count as f64 / 1e9),
+    }
+}
+
+/// Formats a share from 0 to 1 as a percentage, e.g. `4.2%`.
+pub fn format_rate(rate: f64) -> String {
+    format!("{:.1}%", rate * 100.0)
+}
+
+/// A horizontal bar of up to `width` cells, scaled so that `max` fills it.
+/// Any non-zero value gets at least one cell.
+pub fn bar(value: u64, max: u64, width: usize) -> String {
+    if value == 0 || max == 0 {
+        return String::new();
+    }
+    let cells = ((value as f64 / max as f64) * width as f64).round() as usize;
+    "█".repeat(cells.clamp(1, width))
+}
+
+/// Returns the size of a file, or the total size of a directory tree.
+/// Symlinks are counted by their own size and never followed.
+pub fn path_size(path: &Path) -> u64 {
+    let metadata = match fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(_) => return 0,
+    };
+
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+
+    match fs::read_dir(path) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| path_size(&entry.path()))
+            .sum(),
+        Err(_) => 0,
+    }
+}
+
+/// Returns true for the canonical 8-4-4-4-12 hex UUIDs Claude Code uses as session IDs.
+pub fn is_session_id(value: &str) -> bool {
+    let groups: Vec<&str> = value.split('-').collect();
+    groups.len() == 5
+        && groups
+            .iter()
+            .zip([8, 4, 4, 4, 12])
+            .all(|(group, len)| group.len() == len && group.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// The directory name Claude Code gives the project of a working directory
+/// under `~/.claude/projects`: every character other than an ASCII letter or
+/// digit becomes `-`, e.g. `/home/user/my_app` becomes `-home-user-my-app`.
+pub fn encode_project_dir(cwd: &str) -> String {
+    cwd.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '-' }).collect()
+}
+
+/// The name of this machine as reported by `hostname`.
+pub fn hostname() -> Option<String> {
+    let output = Command::new("hostname").output().ok()?;
+    let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !name.is_empty()).then_some(name)
+}
+
+/// Returns the available and total bytes of the filesystem holding `path`,
+/// as reported by `df`. `None` where `df` is unavailable (e.g. Windows).
+pub fn disk_space(path: &Path) -> Option<(u64, u64)> {
+    let output = Command::new("df").arg("-Pk").arg(path).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    // POSIX format: Filesystem 1024-blocks Used Available Capacity Mounted-on
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let fields: Vec<&str> = stdout.lines().nth(1)?.split_whitespace().collect();
+    let total: u64 = fields.get(1)?.parse().ok()?;
+    let available: u64 = fields.get(3)?.parse().ok()?;
+    Some((available * 1024, total * 1024))
+}
@@ -0,0 +1,713 @@
+use crate::conversation::ConversationTree;
+use crate::transcript::{role_label, TranscriptEntry};
+use crate::tui::{fit, wrap, TerminalGuard, Viewport};
+use anyhow::Result;
+use chrono::Local;
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    queue,
+    style::{Attribute, Color, Print, SetAttribute, SetBackgroundColor, SetForegroundColor},
+    terminal::{self, Clear, ClearType},
+};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::io::{self, IsTerminal, Write};
+use std::process::{Command, Stdio};
+
+/// What a part of a message holds; tool calls and results can be collapsed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BlockKind {
+    Text,
+    Thinking,
+    ToolUse,
+    ToolResult,
+    ToolError,
+}
+
+impl BlockKind {
+    pub fn is_collapsible(&self) -> bool {
+        matches!(self, BlockKind::ToolUse | BlockKind::ToolResult | BlockKind::ToolError)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Block {
+    pub kind: BlockKind,
+    /// The tool name for tool calls.
+    pub name: Option<String>,
+    pub text: String,
+}
+
+/// One transcript entry, or the separator above an abandoned branch.
+#[derive(Debug, Clone)]
+pub enum Item {
+    Message {
+        heading: String,
+        blocks: Vec<Block>,
+    },
+    Divider(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LineStyle {
+    Header,
+    Text,
+    Thinking,
+    Tool,
+    Divider,
+}
+
+/// A screen row of the laid out conversation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Line {
+    pub style: LineStyle,
+    pub text: String,
+    pub item: usize,
+    /// The block this row belongs to, `None` for headers and spacing.
+    pub block: Option<usize>,
+}
+
+/// Splits the conversation into messages and their blocks: the active
+/// branch, optionally followed by every abandoned branch.
+pub fn conversation_items(tree: &ConversationTree, include_abandoned: bool) -> Vec<Item> {
+    let mut items: Vec<Item> = tree.active_entries().into_iter().filter_map(message_item).collect();
+    if include_abandoned {
+        for (index, branch) in tree.abandoned_branches().iter().enumerate() {
+            items.push(Item::Divider(tree.branch_heading(index, branch)));
+            items.extend(branch.nodes.iter().filter_map(|&i| message_item(&tree.nodes[i].entry)));
+        }
+    }
+    items
+}
+
+fn message_item(entry: &TranscriptEntry) -> Option<Item> {
+    let mut blocks = Vec::new();
+    let content = entry.value.get("message").and_then(|m| m.get("content"));
+    match content {
+        Some(Value::String(text)) => push_text(&mut blocks, BlockKind::Text, None, text),
+        Some(Value::Array(parts)) => {
+            for part in parts {
+                content_block(&mut blocks, part);
+            }
+        }
+        _ => {
+            let text = entry.value.get("content").or_else(|| entry.value.get("summary")).and_then(|v| v.as_str());
+            push_text(&mut blocks, BlockKind::Text, None, text.unwrap_or(""));
+        }
+    }
+    if blocks.is_empty() {
+        return None;
+    }
+
+    let mut heading = role_label(&entry.value).to_string();
+    if entry.is_sidechain {
+        heading = format!("↳ SUB-AGENT · {}", heading);
+    }
+    if let Some(timestamp) = entry.timestamp {
+        heading.push_str(&format!(" · {}", timestamp.with_timezone(&Local).format("%Y-%m-%d %H:%M")));
+    }
+    Some(Item::Message { heading, blocks })
+}
+
+fn push_text(blocks: &mut Vec<Block>, kind: BlockKind, name: Option<String>, text: &str) {
+    let text = text.trim();
+    if !text.is_empty() || kind.is_collapsible() {
+        blocks.push(Block {
+            kind,
+            name,
+            text: text.to_string(),
+        });
+    }
+}
+
+fn content_block(blocks: &mut Vec<Block>, part: &Value) {
+    let field = |name: &str| part.get(name).and_then(|v| v.as_str());
+    match field("type") {
+        Some("text") => push_text(blocks, BlockKind::Text, None, field("text").unwrap_or("")),
+        Some("thinking") => push_text(blocks, BlockKind::Thinking, None, field("thinking").unwrap_or("")),
+        Some("tool_use") => {
+            let input = match part.get("input") {
+                Some(Value::Object(fields)) => fields
+                    .iter()
+                    .map(|(key, value)| match value {
+                        Value::String(s) => format!("{}: {}", key, s),
+                        other => format!("{}: {}", key, other),
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                Some(other) => other.to_string(),
+                None => String::new(),
+            };
+            push_text(blocks, BlockKind::ToolUse, field("name").map(|s| s.to_string()), &input);
+        }
+        Some("tool_result") => {
+            let text = match part.get("content") {
+                Some(Value::String(s)) => s.clone(),
+                Some(Value::Array(items)) => items
+                    .iter()
+                    .map(|item| match item.get("type").and_then(|t| t.as_str()) {
+                        Some("text") => item.get("text").and_then(|t| t.as_str()).unwrap_or("").to_string(),
+                        Some(other) => format!("[{}]", other),
+                        None => String::new(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                _ => String::new(),
+            };
+            let kind = match part.get("is_error").and_then(|v| v.as_bool()) {
+                Some(true) => BlockKind::ToolError,
+                _ => BlockKind::ToolResult,
+            };
+            push_text(blocks, kind, None, &text);
+        }
+        Some("image") => push_text(blocks, BlockKind::Text, None, "[image]"),
+        _ => {}
+    }
+}
+
+/// Lays the items out in rows of at most `width` columns. Tool blocks are
+/// one summary row unless their `(item, block)` pair is in `expanded`.
+pub fn layout(items: &[Item], expanded: &HashSet<(usize, usize)>, width: usize) -> Vec<Line> {
+    let mut lines = Vec::new();
+    let mut push = |style, text: &str, item, block| {
+        for row in wrap(text, width) {
+            lines.push(Line {
+                style,
+                text: row,
+                item,
+                block,
+            });
+        }
+    };
+
+    for (i, item) in items.iter().enumerate() {
+        let (heading, blocks) = match item {
+            Item::Divider(text) => {
+                push(LineStyle::Divider, text, i, None);
+                push(LineStyle::Text, "", i, None);
+                continue;
+            }
+            Item::Message { heading, blocks } => (heading, blocks),
+        };
+        push(LineStyle::Header, &format!("─── {} ───", heading), i, None);
+
+        for (b, block) in blocks.iter().enumerate() {
+            match block.kind {
+                BlockKind::Text => {
+                    for line in block.text.lines() {
+                        push(LineStyle::Text, line, i, Some(b));
+                    }
+                }
+                BlockKind::Thinking => {
+                    push(LineStyle::Thinking, "✻ Thinking", i, Some(b));
+                    for line in block.text.lines() {
+                        push(LineStyle::Thinking, &format!("  {}", line), i, Some(b));
+                    }
+                }
+                kind => {
+                    let label = match (kind, &block.name) {
+                        (BlockKind::ToolUse, Some(name)) => format!("Tool: {}", name),
+                        (BlockKind::ToolUse, None) => "Tool".to_string(),
+                        (BlockKind::ToolError, _) => "Error".to_string(),
+                        _ => "Result".to_string(),
+                    };
+                    let line_count = block.text.lines().count();
+                    if expanded.contains(&(i, b)) {
+                        push(LineStyle::Tool, &format!("▾ {}", label), i, Some(b));
+                        for line in block.text.lines() {
+                            push(LineStyle::Tool, &format!("  {}", line), i, Some(b));
+                        }
+                    } else {
+                        let first = block.text.lines().next().unwrap_or("");
+                        let more = match line_count {
+                            0 | 1 => String::new(),
+                            n => format!(" (+{} lines)", n - 1),
+                        };
+                        // A collapsed block stays on one row whatever the width
+                        let summary = fit(&format!("▸ {} · {}{}", label, first, more), width);
+                        push(LineStyle::Tool, summary.trim_end(), i, Some(b));
+                    }
+                }
+            }
+        }
+        push(LineStyle::Text, "", i, None);
+    }
+    lines
+}
+
+/// Every tool block, for expanding them all at once.
+fn tool_blocks(items: &[Item]) -> HashSet<(usize, usize)> {
+    let mut blocks = HashSet::new();
+    for (i, item) in items.iter().enumerate() {
+        if let Item::Message { blocks: parts, .. } = item {
+            for (b, block) in parts.iter().enumerate() {
+                if block.kind.is_collapsible() {
+                    blocks.insert((i, b));
+                }
+            }
+        }
+    }
+    blocks
+}
+
+/// Tool blocks containing `term`, so a search can open the ones it hits.
+fn tool_blocks_matching(items: &[Item], term: &str) -> Vec<(usize, usize)> {
+    let term = term.to_lowercase();
+    tool_blocks(items)
+        .into_iter()
+        .filter(|&(i, b)| match &items[i] {
+            Item::Message { blocks, .. } => {
+                let block = &blocks[b];
+                block.text.to_lowercase().contains(&term)
+                    || block.name.as_ref().is_some_and(|name| name.to_lowercase().contains(&term))
+            }
+            Item::Divider(_) => false,
+        })
+        .collect()
+}
+
+/// Character ranges of case-insensitive occurrences of `term` in `text`.
+fn match_ranges(text: &str, term: &str) -> Vec<(usize, usize)> {
+    let lower = |s: &str| -> Vec<char> { s.chars().map(|c| c.to_lowercase().next().unwrap_or(c)).collect() };
+    let (text, term) = (lower(text), lower(term));
+    let mut ranges = Vec::new();
+    if term.is_empty() || term.len() > text.len() {
+        return ranges;
+    }
+    let mut start = 0;
+    while start + term.len() <= text.len() {
+        if text[start..start + term.len()] == term[..] {
+            ranges.push((start, start + term.len()));
+            start += term.len();
+        } else {
+            start += 1;
+        }
+    }
+    ranges
+}
+
+/// The conversation as plain text with every tool block expanded, for
+/// `$PAGER` or non-interactive output.
+pub fn plain_text(tree: &ConversationTree) -> String {
+    let items = conversation_items(tree, true);
+    let lines = layout(&items, &tool_blocks(&items), usize::MAX);
+    let text: Vec<&str> = lines.iter().map(|line| line.text.as_str()).collect();
+    text.join("\n").trim_end().to_string()
+}
+
+/// Pipes `text` into `$PAGER`. Returns `false` when no pager is configured.
+pub fn page_externally(text: &str) -> Result<bool> {
+    let pager = match std::env::var("PAGER") {
+        Ok(pager) if !pager.trim().is_empty() => pager,
+        _ => return Ok(false),
+    };
+    let mut command = if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.arg("/C").arg(&pager);
+        command
+    } else {
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(&pager);
+        command
+    };
+    let mut child = command.stdin(Stdio::piped()).spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // The pager may quit before reading everything
+        let _ = stdin.write_all(text.as_bytes());
+    }
+    child.wait()?;
+    Ok(true)
+}
+
+/// Shows a conversation: in the full-screen viewer when running in a
+/// terminal, otherwise through `$PAGER` or as plain text on stdout.
+pub fn show(title: &str, tree: &ConversationTree) -> Result<()> {
+    if io::stdin().is_terminal() && io::stdout().is_terminal() {
+        let _guard = TerminalGuard::enter()?;
+        return run(title, tree);
+    }
+    show_plain(tree)
+}
+
+/// Hands the plain text to `$PAGER`, or prints it when none is set.
+fn show_plain(tree: &ConversationTree) -> Result<()> {
+    let text = plain_text(tree);
+    if !page_externally(&text)? {
+        println!("{}", text);
+    }
+    Ok(())
+}
+
+/// What the status bar is used for.
+enum Mode {
+    Normal,
+    /// Typing a search term.
+    Search(String),
+}
+
+/// Runs the viewer in a terminal that is already in raw mode on the
+/// alternate screen, until the user leaves it.
+pub fn run(title: &str, tree: &ConversationTree) -> Result<()> {
+    let mut include_abandoned = false;
+    let mut items = conversation_items(tree, include_abandoned);
+    let has_abandoned = !tree.abandoned_branches().is_empty();
+    let mut expanded: HashSet<(usize, usize)> = HashSet::new();
+    let mut view = Viewport::default();
+    let mut mode = Mode::Normal;
+    let mut term = String::new();
+    let mut message: Option<String> = None;
+    // The rows and search hits are redone only when what they depend on changes
+    let mut laid_out = None;
+    let (mut lines, mut matches): (Vec<Line>, Vec<usize>) = (Vec::new(), Vec::new());
+    let mut out = io::stdout();
+    queue!(out, Clear(ClearType::All))?;
+
+    loop {
+        let (width, height) = terminal::size()?;
+        let (width, height) = (width as usize, height as usize);
+        let page = height.saturating_sub(2).max(1);
+        let inputs = (include_abandoned, expanded.clone(), width, term.clone());
+        if laid_out.as_ref() != Some(&inputs) {
+            lines = layout(&items, &expanded, width.max(1));
+            matches = match term.is_empty() {
+                true => Vec::new(),
+                false => (0..lines.len()).filter(|&l| !match_ranges(&lines[l].text, &term).is_empty()).collect(),
+            };
+            laid_out = Some(inputs);
+        }
+        view.clamp(lines.len(), page);
+
+        // Title
+        let messages = items.iter().filter(|item| matches!(item, Item::Message { .. })).count();
+        let current_item = lines.get(view.cursor).map(|line| line.item).unwrap_or(0);
+        let position = items[..items.len().min(current_item + 1)]
+            .iter()
+            .filter(|item| matches!(item, Item::Message { .. }))
+            .count();
+        let heading = format!(" 💬 {} · message {}/{}", title, position, messages);
+        queue!(out, cursor::MoveTo(0, 0), SetAttribute(Attribute::Bold), Print(fit(&heading, width)))?;
+        queue!(out, SetAttribute(Attribute::Reset))?;
+
+        // Conversation
+        for row in 0..page {
+            queue!(out, cursor::MoveTo(0, (row + 1) as u16))?;
+            let index = view.offset + row;
+            let line = match lines.get(index) {
+                Some(line) => line,
+                None => {
+                    queue!(out, Print(fit("", width)))?;
+                    continue;
+                }
+            };
+            let color = match line.style {
+                LineStyle::Header => Color::Cyan,
+                LineStyle::Thinking => Color::Magenta,
+                LineStyle::Tool => Color::DarkYellow,
+                LineStyle::Divider => Color::Red,
+                LineStyle::Text => Color::Reset,
+            };
+            queue!(out, SetForegroundColor(color))?;
+            if index == view.cursor {
+                queue!(out, SetAttribute(Attribute::Reverse))?;
+            }
+            if line.style == LineStyle::Thinking {
+                queue!(out, SetAttribute(Attribute::Italic))?;
+            }
+            let text = fit(&line.text, width);
+            let mut printed = 0;
+            for (start, end) in match_ranges(&text, &term) {
+                let segment = |from: usize, to: usize| -> String { text.chars().skip(from).take(to - from).collect() };
+                queue!(out, Print(segment(printed, start)), SetBackgroundColor(Color::Yellow))?;
+                queue!(out, Print(segment(start, end)), SetBackgroundColor(Color::Reset))?;
+                printed = end;
+            }
+            let rest: String = text.chars().skip(printed).collect();
+            queue!(out, Print(rest), SetAttribute(Attribute::Reset), SetForegroundColor(Color::Reset))?;
+        }
+
+        // Status bar
+        let status = match &mode {
+            Mode::Search(text) => format!(" Search: {}_", text),
+            Mode::Normal => {
+                let percent = match lines.len() {
+                    0 => 100,
+                    n => (view.cursor + 1) * 100 / n,
+                };
+                let found = match matches.iter().position(|&l| l >= view.cursor) {
+                    _ if term.is_empty() => String::new(),
+                    _ if matches.is_empty() => format!(" · /{}: no matches", term),
+                    Some(i) if matches[i] == view.cursor => format!(" · /{}: match {}/{}", term, i + 1, matches.len()),
+                    _ => format!(" · /{}: {} matches", term, matches.len()),
+                };
+                let hint = message.take().unwrap_or_else(|| {
+                    let branches = if has_abandoned { " · a branches" } else { "" };
+                    format!(
+                        "jk scroll · space/b page · g/G first/last · [/] message · enter tool · t all tools · / n N search{} · p $PAGER · q back",
+                        branches
+                    )
+                });
+                format!(" line {}/{} {}%{} │ {}", view.cursor + 1, lines.len(), percent, found, hint)
+            }
+        };
+        queue!(
+            out,
+            cursor::MoveTo(0, height.saturating_sub(1) as u16),
+            SetAttribute(Attribute::Reverse),
+            Print(fit(&status, width)),
+            SetAttribute(Attribute::Reset)
+        )?;
+        out.flush()?;
+
+        let key = match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => key,
+            Event::Resize(..) => {
+                queue!(out, Clear(ClearType::All))?;
+                continue;
+            }
+            _ => continue,
+        };
+
+        if let Mode::Search(mut text) = std::mem::replace(&mut mode, Mode::Normal) {
+            match key.code {
+                KeyCode::Enter => {
+                    term = text;
+                    if !term.is_empty() {
+                        // Open the tool blocks the term occurs in, then jump to the first hit
+                        expanded.extend(tool_blocks_matching(&items, &term));
+                        let lines = layout(&items, &expanded, width.max(1));
+                        let hit = (view.cursor..lines.len())
+                            .chain(0..view.cursor)
+                            .find(|&l| !match_ranges(&lines[l].text, &term).is_empty());
+                        match hit {
+                            Some(l) => view.move_by(l as isize - view.cursor as isize, lines.len(), page),
+                            None => message = Some(format!("Pattern not found: {}", term)),
+                        }
+                    }
+                }
+                KeyCode::Esc => {}
+                KeyCode::Backspace => {
+                    text.pop();
+                    mode = Mode::Search(text);
+                }
+                KeyCode::Char(c) => {
+                    text.push(c);
+                    mode = Mode::Search(text);
+                }
+                _ => mode = Mode::Search(text),
+            }
+            continue;
+        }
+
+        if handle_navigation(key, &mut view, &lines, page) {
+            continue;
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return Ok(()),
+            KeyCode::Char('/') => mode = Mode::Search(String::new()),
+            KeyCode::Char('n') | KeyCode::Char('N') if !matches.is_empty() => {
+                let target = match key.code {
+                    KeyCode::Char('n') => matches.iter().find(|&&l| l > view.cursor).or(matches.first()),
+                    _ => matches.iter().rev().find(|&&l| l < view.cursor).or(matches.last()),
+                };
+                if let Some(&l) = target {
+                    view.move_by(l as isize - view.cursor as isize, lines.len(), page);
+                }
+            }
+            KeyCode::Enter | KeyCode::Char('o') => {
+                let block = lines.get(view.cursor).and_then(|line| line.block.map(|b| (line.item, b)));
+                if let Some(key) = block.filter(|key| tool_blocks(&items).contains(key)) {
+                    if !expanded.remove(&key) {
+                        expanded.insert(key);
+                    }
+                    // Keep the cursor on the block's first row
+                    let lines = layout(&items, &expanded, width.max(1));
+                    if let Some(first) = lines.iter().position(|l| (l.item, l.block) == (key.0, Some(key.1))) {
+                        view.move_by(first as isize - view.cursor as isize, lines.len(), page);
+                    }
+                }
+            }
+            KeyCode::Char('t') => {
+                let all = tool_blocks(&items);
+                expanded = match all.is_subset(&expanded) {
+                    true => HashSet::new(),
+                    false => all,
+                };
+            }
+            KeyCode::Char('a') if has_abandoned => {
+                include_abandoned = !include_abandoned;
+                items = conversation_items(tree, include_abandoned);
+                message = Some(match include_abandoned {
+                    true => "Showing abandoned branches after the conversation".to_string(),
+                    false => "Hiding abandoned branches".to_string(),
+                });
+            }
+            KeyCode::Char('p') => {
+                let text = plain_text(tree);
+                let paged = suspended(|| page_externally(&text))?;
+                queue!(out, Clear(ClearType::All))?;
+                if !paged {
+                    message = Some("$PAGER is not set".to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Moves through the lines; returns `false` for keys that are not navigation.
+fn handle_navigation(key: KeyEvent, view: &mut Viewport, lines: &[Line], page: usize) -> bool {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    let len = lines.len();
+    let header = |l: &usize| lines[*l].style == LineStyle::Header;
+    match key.code {
+        KeyCode::Char('d') if ctrl => view.move_by(page as isize / 2, len, page),
+        KeyCode::Char('u') if ctrl => view.move_by(-(page as isize) / 2, len, page),
+        KeyCode::Down | KeyCode::Char('j') => view.move_by(1, len, page),
+        KeyCode::Up | KeyCode::Char('k') => view.move_by(-1, len, page),
+        KeyCode::PageDown | KeyCode::Char(' ') => view.move_by(page as isize, len, page),
+        KeyCode::PageUp | KeyCode::Char('b') => view.move_by(-(page as isize), len, page),
+        KeyCode::Home | KeyCode::Char('g') => view.move_by(isize::MIN / 2, len, page),
+        KeyCode::End | KeyCode::Char('G') => {
+            // Start of the last message rather than its last line
+            let last = (0..len).rev().find(header).unwrap_or(len.saturating_sub(1));
+            view.move_by(last as isize - view.cursor as isize, len, page);
+        }
+        KeyCode::Char(']') => {
+            if let Some(next) = (view.cursor + 1..len).find(header) {
+                view.move_by(next as isize - view.cursor as isize, len, page);
+            }
+        }
+        KeyCode::Char('[') => {
+            if let Some(previous) = (0..view.cursor).rev().find(header) {
+                view.move_by(previous as isize - view.cursor as isize, len, page);
+            }
+        }
+        _ => return false,
+    }
+    true
+}
+
+/// Hands the terminal back to normal mode while `f` runs, e.g. for an
+/// external pager.
+pub(crate) fn suspended<T>(f: impl FnOnce() -> Result<T>) -> Result<T> {
+    terminal::disable_raw_mode()?;
+    crossterm::execute!(io::stdout(), terminal::LeaveAlternateScreen, cursor::Show)?;
+    let result = f();
+    crossterm::execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+    terminal::enable_raw_mode()?;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_layout_collapses_tools_and_search_opens_them() {
+        let entries = vec![
+            json!({"type": "user", "uuid": "u1", "message": {"role": "user", "content": "list files"}}),
+            json!({"type": "assistant", "uuid": "a1", "parentUuid": "u1", "message": {"role": "assistant", "content": [
+                {"type": "thinking", "thinking": "Use ls"},
+                {"type": "text", "text": "Listing."},
+                {"type": "tool_use", "id": "t1", "name": "Bash", "input": {"command": "ls -la"}},
+            ]}}),
+            json!({"type": "user", "uuid": "u2", "parentUuid": "a1", "message": {"role": "user", "content": [
+                {"type": "tool_result", "tool_use_id": "t1", "content": "Cargo.toml\nsrc\nsecret.txt"},
+            ]}}),
+        ];
+        let tree = tree(entries);
+        let items = conversation_items(&tree, false);
+        assert_eq!(items.len(), 3);
+
+        let collapsed = layout(&items, &HashSet::new(), 80);
+        let text: Vec<&str> = collapsed.iter().map(|l| l.text.as_str()).collect();
+        assert_eq!(
+            text,
+            [
+                "─── 👤 USER ───",
+                "list files",
+                "",
+                "─── 🤖 CLAUDE ───",
+                "✻ Thinking",
+                "  Use ls",
+                "Listing.",
+                "▸ Tool: Bash · command: ls -la",
+                "",
+                "─── 👤 USER ───",
+                "▸ Result · Cargo.toml (+2 lines)",
+                "",
+            ]
+        );
+        assert_eq!(collapsed[5].style, LineStyle::Thinking);
+
+        // The hidden third line of the result is found and its block opened
+        let hits = tool_blocks_matching(&items, "SECRET");
+        assert_eq!(hits, [(2, 0)]);
+        let expanded = layout(&items, &hits.into_iter().collect(), 80);
+        assert!(expanded.iter().any(|l| l.text == "  secret.txt" && l.style == LineStyle::Tool));
+        assert_eq!(match_ranges("a Secret secret", "secret"), [(2, 8), (9, 15)]);
+
+        assert!(plain_text(&tree).contains("▾ Tool: Bash\n  command: ls -la"));
+    }
+
+    fn tree(entries: Vec<Value>) -> ConversationTree {
+        ConversationTree::build(
+            entries
+                .into_iter()
+                .enumerate()
+                .map(|(i, value)| TranscriptEntry::from_value(i + 1, value))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_navigation_jumps_between_messages() {
+        let entries = (0..4)
+            .map(|i| {
+                let parent = if i == 0 { Value::Null } else { json!(format!("m{}", i - 1)) };
+                json!({"type": "user", "uuid": format!("m{}", i), "parentUuid": parent, "message": {"content": "one\ntwo"}})
+            })
+            .collect();
+        let items = conversation_items(&tree(entries), false);
+        // Each message is a header, two text rows and a blank row
+        let lines = layout(&items, &HashSet::new(), 80);
+        assert_eq!(lines.len(), 16);
+        let mut view = Viewport::default();
+        let mut press = |code| {
+            let handled = handle_navigation(KeyEvent::new(code, KeyModifiers::NONE), &mut view, &lines, 5);
+            (handled, view.cursor)
+        };
+
+        assert_eq!(press(KeyCode::Char('G')), (true, 12));
+        assert_eq!(press(KeyCode::Char('[')), (true, 8));
+        assert_eq!(press(KeyCode::Char('j')), (true, 9));
+        assert_eq!(press(KeyCode::Char('[')), (true, 8));
+        assert_eq!(press(KeyCode::Char(']')), (true, 12));
+        // Past the last message `]` stays put
+        assert_eq!(press(KeyCode::Char(']')), (true, 12));
+        assert_eq!(press(KeyCode::Char('g')), (true, 0));
+        assert_eq!(press(KeyCode::Char('q')), (false, 0));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_plain_text_goes_to_pager() {
+        let out = std::env::temp_dir().join(format!("cc-session-manager-pager-{}", std::process::id()));
+        let conversation = tree(vec![json!({"type": "user", "uuid": "u1", "message": {"content": "hello pager"}})]);
+
+        std::env::set_var("PAGER", format!("cat > '{}'", out.display()));
+        show_plain(&conversation).unwrap();
+        assert_eq!(std::fs::read_to_string(&out).unwrap(), plain_text(&conversation));
+        assert!(page_externally("again").unwrap());
+        assert_eq!(std::fs::read_to_string(&out).unwrap(), "again");
+
+        std::env::set_var("PAGER", " ");
+        assert!(!page_externally("unused").unwrap());
+        std::env::remove_var("PAGER");
+        assert!(!page_externally("unused").unwrap());
+        std::fs::remove_file(out).unwrap();
+    }
+}
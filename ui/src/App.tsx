@@ -13,6 +13,20 @@ import {
   ChevronLeft,
   Maximize2,
   Minimize2,
+  Archive,
+  Copy,
+  Stethoscope,
+  Activity,
+  Info,
+  KeyRound,
+  CalendarDays,
+  Trophy,
+  GitCompare,
+  FolderInput,
+  Truck,
+  Terminal,
+  GitBranch,
+  Wrench,
 } from "lucide-react";
 import {
   Card,
@@ -34,14 +48,105 @@ import {
   DialogTitle,
 } from "@/components/ui/dialog";
 import { api } from "./services/api";
-import type { ProjectDto, SessionDto, StatisticsDto } from "./types";
+import type {
+  ActivityDto,
+  ArtifactDto,
+  ArtifactUsageDto,
+  BranchGroupDto,
+  ConversationEntryDto,
+  ConversationTreeDto,
+  DoctorFindingDto,
+  ExportResultDto,
+  ProjectDetailDto,
+  ProjectDto,
+  ProjectDuplicatesDto,
+  SessionChainDto,
+  SessionDto,
+  SessionIntegrityDto,
+  SessionSecretsDto,
+  StaleBranchReportDto,
+  RankedSessionDto,
+  RelocationReportDto,
+  SessionDiffDto,
+  SessionToolUsageDto,
+  StatisticsDto,
+  StoreRankingDto,
+  ToolCountsDto,
+  ToolReportDto,
+} from "./types";
 
 type View =
   | "dashboard"
   | "projects"
   | "sessions"
   | "delete-by-age"
-  | "session-detail";
+  | "artifacts"
+  | "duplicates"
+  | "integrity"
+  | "doctor"
+  | "secrets"
+  | "activity"
+  | "largest"
+  | "relocate"
+  | "stale-branches"
+  | "tools"
+  | "project-detail"
+  | "session-detail"
+  | "session-diff";
+
+/** How many sessions and projects each list of the ranking shows. */
+const RANKING_LIMIT = 20;
+
+/** An error rate from 0 to 1 as a percentage. */
+const formatRate = (rate: number) => `${(rate * 100).toFixed(1)}%`;
+
+const WEEKDAYS = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+/** Background for a heatmap cell, from idle to the busiest cell. */
+const heatColor = (value: number, max: number) =>
+  value === 0 || max === 0
+    ? "rgba(255, 255, 255, 0.05)"
+    : `rgba(20, 184, 166, ${0.25 + 0.75 * (value / max)})`;
+
+const formatTimestamp = (timestamp: string | null) =>
+  timestamp ? new Date(timestamp).toLocaleString() : "unknown";
+
+const formatCount = (count: number) =>
+  count >= 1e9
+    ? `${(count / 1e9).toFixed(1)}B`
+    : count >= 1e6
+    ? `${(count / 1e6).toFixed(1)}M`
+    : count >= 1e3
+    ? `${(count / 1e3).toFixed(1)}K`
+    : `${count}`;
+
+const formatEntries = (entries: ConversationEntryDto[]) =>
+  entries
+    .filter((entry) => entry.text)
+    .map(
+      (entry) =>
+        `─── ${entry.is_sidechain ? "↳ SUB-AGENT · " : ""}${entry.role} ───\n${entry.text}`
+    );
+
+const formatConversation = (
+  tree: ConversationTreeDto,
+  includeAbandoned: boolean
+) => {
+  const blocks = formatEntries(tree.active);
+  if (includeAbandoned) {
+    tree.abandoned_branches.forEach((branch, index) => {
+      const fork =
+        branch.fork_line !== null
+          ? `forked after line ${branch.fork_line}`
+          : "detached";
+      blocks.push(
+        `═══ Abandoned branch ${index + 1} (${fork}, ${branch.entries.length} entries) ═══`,
+        ...formatEntries(branch.entries)
+      );
+    });
+  }
+  return blocks.join("\n\n");
+};
 
 function App() {
   const [view, setView] = useState<View>("dashboard");
@@ -61,14 +166,81 @@ function App() {
   const [selectedOldSessions, setSelectedOldSessions] = useState<Set<string>>(
     new Set()
   );
+  const [artifactCategory, setArtifactCategory] =
+    useState<ArtifactUsageDto | null>(null);
+  const [artifactAge, setArtifactAge] = useState<number>(30);
+  const [oldArtifacts, setOldArtifacts] = useState<ArtifactDto[]>([]);
+  const [selectedArtifacts, setSelectedArtifacts] = useState<Set<string>>(
+    new Set()
+  );
+  const [orphanedArtifacts, setOrphanedArtifacts] = useState<
+    ArtifactDto[] | null
+  >(null);
+  const [duplicates, setDuplicates] = useState<ProjectDuplicatesDto[]>([]);
+  const [chains, setChains] = useState<SessionChainDto[]>([]);
+  const [branchGroups, setBranchGroups] = useState<BranchGroupDto[]>([]);
+  /** Branch the session list is narrowed to: "" for all, "-" for none. */
+  const [branchFilter, setBranchFilter] = useState("");
+  const [integrityReports, setIntegrityReports] = useState<
+    SessionIntegrityDto[]
+  >([]);
+  const [selectedRepairs, setSelectedRepairs] = useState<Set<string>>(
+    new Set()
+  );
+  const [doctorFindings, setDoctorFindings] = useState<DoctorFindingDto[]>(
+    []
+  );
+  const [activity, setActivity] = useState<ActivityDto | null>(null);
+  const [ranking, setRanking] = useState<StoreRankingDto | null>(null);
+  const [toolUsage, setToolUsage] = useState<ToolReportDto | null>(null);
+  const [sessionDiff, setSessionDiff] = useState<{
+    left: SessionDto;
+    right: SessionDto;
+    diff: SessionDiffDto;
+  } | null>(null);
+  const [selectedRanked, setSelectedRanked] = useState<Set<string>>(
+    new Set()
+  );
+  const [staleBranches, setStaleBranches] =
+    useState<StaleBranchReportDto | null>(null);
+  const [selectedStale, setSelectedStale] = useState<Set<string>>(new Set());
+  const [projectDetail, setProjectDetail] = useState<ProjectDetailDto | null>(
+    null
+  );
+  const [moveDialog, setMoveDialog] = useState<{
+    targetPath: string;
+    newCwd: string;
+  } | null>(null);
+  /** Trash directories of the moves waiting to be confirmed. */
+  const [pendingTrash, setPendingTrash] = useState<string[]>([]);
+  const [relocateFrom, setRelocateFrom] = useState("");
+  const [relocateTo, setRelocateTo] = useState("");
+  const [relocation, setRelocation] = useState<RelocationReportDto | null>(
+    null
+  );
+  const [exportReport, setExportReport] = useState<ExportResultDto | null>(
+    null
+  );
+  const [secretReports, setSecretReports] = useState<SessionSecretsDto[]>([]);
+  const [secretPattern, setSecretPattern] = useState("");
+  const [selectedRedactions, setSelectedRedactions] = useState<Set<string>>(
+    new Set()
+  );
+  const [selectedDuplicates, setSelectedDuplicates] = useState<Set<string>>(
+    new Set()
+  );
   const [isLoading, setIsLoading] = useState(false);
   const [isLoadingContent, setIsLoadingContent] = useState(false);
-  const [sessionFullContent, setSessionFullContent] = useState<string>("");
+  const [conversation, setConversation] = useState<ConversationTreeDto | null>(
+    null
+  );
+  const [showAbandoned, setShowAbandoned] = useState(false);
   const [message, setMessage] = useState<{
     type: "success" | "error";
     text: string;
   } | null>(null);
   const [contentExpanded, setContentExpanded] = useState(false);
+  const [activeThreadPath, setActiveThreadPath] = useState<string>("");
 
   useEffect(() => {
     loadData();
@@ -96,10 +268,159 @@ function App() {
     setTimeout(() => setMessage(null), 3000);
   };
 
+  const loadChains = async (projectPath: string) => {
+    try {
+      setChains(await api.getSessionChains(projectPath));
+    } catch (error) {
+      console.error("Failed to load session chains:", error);
+      setChains([]);
+    }
+  };
+
+  const loadBranches = async (projectPath: string) => {
+    try {
+      setBranchGroups(await api.getBranchGroups(projectPath));
+    } catch (error) {
+      console.error("Failed to load branches:", error);
+      setBranchGroups([]);
+    }
+  };
+
   const handleSelectProject = (project: ProjectDto) => {
     setSelectedProject(project);
     setSelectedSessions(new Set());
+    setChains([]);
+    setBranchGroups([]);
+    setBranchFilter("");
     setView("sessions");
+    loadChains(project.path);
+    loadBranches(project.path);
+  };
+
+  /** Sessions of the open project that pass the branch filter. */
+  const visibleSessions = (selectedProject?.sessions ?? []).filter(
+    (s) =>
+      branchFilter === "" || (s.git_branch ?? "-") === branchFilter
+  );
+
+  /** Shows only one branch's sessions, dropping hidden ones from the selection. */
+  const handleFilterBranch = (branch: string) => {
+    setBranchFilter(branch);
+    const shown = new Set(
+      (selectedProject?.sessions ?? [])
+        .filter((s) => branch === "" || (s.git_branch ?? "-") === branch)
+        .map((s) => s.path)
+    );
+    setSelectedSessions(
+      new Set(Array.from(selectedSessions).filter((p) => shown.has(p)))
+    );
+  };
+
+  const handleBranchBulkAction = async (
+    group: BranchGroupDto,
+    action: "archive" | "delete"
+  ) => {
+    setIsLoading(true);
+    try {
+      const count =
+        action === "archive"
+          ? await api.archiveSessions(group.session_paths)
+          : await api.deleteSessions(group.session_paths);
+      showMessage(
+        "success",
+        `${action === "archive" ? "Archived" : "Deleted"} ${count} sessions of ${
+          group.branch ?? "no branch"
+        }`
+      );
+      setSelectedSessions(new Set());
+      await loadData();
+      setView("projects");
+      setSelectedProject(null);
+    } catch (error) {
+      showMessage("error", `Failed to ${action} sessions`);
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const handleShowProjectDetail = async (project: ProjectDto) => {
+    setIsLoading(true);
+    try {
+      setProjectDetail(await api.getProjectDetail(project.path));
+      setView("project-detail");
+    } catch (error) {
+      showMessage("error", "Failed to load project details");
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const chainPosition = (sessionPath: string) => {
+    for (const chain of chains) {
+      const index = chain.sessions.findIndex((s) => s.path === sessionPath);
+      if (index >= 0) return { index, length: chain.sessions.length };
+    }
+    return null;
+  };
+
+  const handleSelectChain = (chain: SessionChainDto) => {
+    const newSelected = new Set(selectedSessions);
+    chain.sessions.forEach((s) => newSelected.add(s.path));
+    setSelectedSessions(newSelected);
+  };
+
+  const handleExportChain = async (
+    chain: SessionChainDto,
+    anonymize = false
+  ) => {
+    try {
+      const result = await api.exportSessions(
+        chain.sessions.map((s) => s.path),
+        "md",
+        anonymize
+      );
+      if (anonymize) {
+        setExportReport(result);
+      } else {
+        showMessage("success", `Exported to ${result.path}`);
+      }
+    } catch (error) {
+      showMessage("error", "Failed to export conversation");
+    }
+  };
+
+  const handleDeleteChain = async (chain: SessionChainDto) => {
+    setIsLoading(true);
+    try {
+      const count = await api.deleteSessions(chain.sessions.map((s) => s.path));
+      showMessage("success", `Deleted ${count} sessions`);
+      setSelectedSessions(new Set());
+      await loadData();
+      setView("projects");
+      setSelectedProject(null);
+    } catch (error) {
+      showMessage("error", "Failed to delete sessions");
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const handleCompareSessions = async () => {
+    if (!selectedProject || selectedSessions.size !== 2) return;
+    // Oldest on the left, so a resumed or retried copy reads as the change
+    const [left, right] = selectedProject.sessions
+      .filter((s) => selectedSessions.has(s.path))
+      .sort((a, b) => b.age_days - a.age_days);
+    setIsLoading(true);
+    try {
+      const diff = await api.diffSessions(left.path, right.path);
+      setSessionDiff({ left, right, diff });
+      setView("session-diff");
+    } catch (error) {
+      showMessage("error", "Failed to compare sessions");
+    } finally {
+      setIsLoading(false);
+    }
   };
 
   const handleToggleSession = (sessionPath: string) => {
@@ -112,31 +433,112 @@ function App() {
     setSelectedSessions(newSelected);
   };
 
-  const handleSelectSession = async (session: SessionDto) => {
-    setSelectedSession(session);
-    setSessionFullContent("");
+  const loadThreadContent = async (threadPath: string) => {
+    setActiveThreadPath(threadPath);
+    setConversation(null);
+    setShowAbandoned(false);
     setIsLoadingContent(true);
     try {
-      const content = await api.getSessionContent(session.path);
-      setSessionFullContent(content);
+      const tree = await api.getConversationTree(threadPath);
+      setConversation(tree);
     } catch (error) {
       console.error("Failed to load session content:", error);
-      setSessionFullContent("");
+      setConversation(null);
     } finally {
       setIsLoadingContent(false);
     }
+  };
+
+  const handleSelectSession = async (session: SessionDto) => {
+    setSelectedSession(session);
+    await loadThreadContent(session.path);
     setView("session-detail");
   };
 
+  /** Copies the shell command that resumes the session in Claude Code. */
+  const handleCopyResumeCommand = async (session: SessionDto) => {
+    try {
+      const command = await api.getResumeCommand(session.path);
+      await navigator.clipboard.writeText(command.shell_line);
+      showMessage("success", `Copied: ${command.shell_line}`);
+    } catch (error) {
+      showMessage("error", `${error}`);
+    }
+  };
+
   const handleSelectAllSessions = () => {
     if (selectedProject) {
-      if (selectedSessions.size === selectedProject.sessions.length) {
+      if (selectedSessions.size === visibleSessions.length) {
         setSelectedSessions(new Set());
       } else {
-        setSelectedSessions(
-          new Set(selectedProject.sessions.map((s) => s.path))
+        setSelectedSessions(new Set(visibleSessions.map((s) => s.path)));
+      }
+    }
+  };
+
+  /** Picks a move target and suggests the directory its sessions ran in. */
+  const handleMoveTarget = async (targetPath: string) => {
+    setMoveDialog({ targetPath, newCwd: "" });
+    if (!targetPath) return;
+    try {
+      const detail = await api.getProjectDetail(targetPath);
+      setMoveDialog({ targetPath, newCwd: detail.cwd ?? "" });
+    } catch (error) {
+      console.error("Failed to load project detail:", error);
+    }
+  };
+
+  const handleMoveSessions = async () => {
+    if (!moveDialog?.targetPath || selectedSessions.size === 0) return;
+    setIsLoading(true);
+    try {
+      const results = await api.moveSessions(
+        Array.from(selectedSessions),
+        moveDialog.targetPath,
+        moveDialog.newCwd.trim() || null
+      );
+      const moved = results.flatMap((result) =>
+        result.outcome ? [result.outcome] : []
+      );
+      const failed = results.filter((result) => result.error);
+      if (failed.length > 0) {
+        showMessage(
+          "error",
+          `Moved ${moved.length} of ${results.length} sessions. ` +
+            failed.map((result) => `${result.name}: ${result.error}`).join("; ")
+        );
+      } else {
+        showMessage(
+          "success",
+          `Moved ${moved.length} sessions; the originals are kept in the trash`
         );
       }
+      setPendingTrash((paths) => [
+        ...paths,
+        ...moved.map((outcome) => outcome.trash_path),
+      ]);
+      setMoveDialog(null);
+      setSelectedSessions(new Set());
+      await loadData();
+      setView("projects");
+      setSelectedProject(null);
+    } catch (error) {
+      showMessage("error", `${error}`);
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const handleEmptyTrash = async () => {
+    setIsLoading(true);
+    try {
+      const freed = await api.emptyTrash(pendingTrash);
+      showMessage("success", `Deleted the originals of moved sessions (${freed})`);
+      setPendingTrash([]);
+    } catch (error) {
+      showMessage("error", `${error}`);
+    } finally {
+      setIsLoading(false);
     }
   };
 
@@ -149,6 +551,8 @@ function App() {
       setSelectedSessions(new Set());
       await loadData();
       if (selectedProject) {
+        loadChains(selectedProject.path);
+        loadBranches(selectedProject.path);
         const updatedProject = projects.find(
           (p) => p.path === selectedProject.path
         );
@@ -243,116 +647,605 @@ function App() {
     }
   };
 
-  return (
-    <div className="min-h-screen p-6 overflow-hidden">
-      {message && (
-        <div
-          className={`fixed top-4 right-4 z-50 flex items-center gap-2 px-4 py-3 rounded-lg glass shadow-lg ${
-            message.type === "success"
-              ? "text-green-400 border-green-500/30"
-              : "text-red-400 border-red-500/30"
-          }`}
-        >
-          {message.type === "success" ? (
-            <CheckCircle className="w-4 h-4" />
-          ) : (
-            <XCircle className="w-4 h-4" />
-          )}
-          {message.text}
-        </div>
-      )}
+  const handleLoadOldArtifacts = async (category: ArtifactUsageDto) => {
+    setIsLoading(true);
+    try {
+      const artifacts = await api.filterArtifactsByAge(
+        category.category,
+        artifactAge
+      );
+      setOldArtifacts(artifacts);
+      setSelectedArtifacts(new Set());
+    } catch (error) {
+      showMessage("error", "Failed to filter artifacts");
+    } finally {
+      setIsLoading(false);
+    }
+  };
 
-      <div className="w-full mx-auto h-[calc(100vh-48px)]">
-        {view === "dashboard" && (
-          <>
-            <header className="mb-8">
-              <div className="glass-light rounded-2xl p-6 mb-6 flex items-center gap-4">
-                <img
-                  src="/logo.png"
-                  alt="Logo"
-                  className="w-16 h-16 rounded-xl object-contain"
-                />
-                <div>
-                  <h1 className="text-3xl font-bold text-foreground mb-2 bg-gradient-to-r from-teal-400 to-emerald-400 bg-clip-text text-transparent">
-                    Claude Code Session Manager
-                  </h1>
-                  <p className="text-muted-foreground">
-                    Manage your Claude Code sessions across all projects
-                  </p>
-                </div>
-              </div>
-            </header>
+  const handleSelectArtifactCategory = (category: ArtifactUsageDto) => {
+    setArtifactCategory(category);
+    setOldArtifacts([]);
+    setSelectedArtifacts(new Set());
+    setView("artifacts");
+    handleLoadOldArtifacts(category);
+  };
 
-            <div className="space-y-6">
-              <div className="grid grid-cols-1 md:grid-cols-3 gap-4">
-                <Card glass className="glass-hover">
-                  <CardHeader className="flex flex-row items-center justify-between space-y-0 pb-2">
-                    <CardTitle className="text-sm font-medium text-muted-foreground">
-                      Projects
-                    </CardTitle>
-                    <Layers className="h-5 w-5 text-teal-500" />
-                  </CardHeader>
-                  <CardContent>
-                    <div className="text-3xl font-bold text-foreground">
-                      {stats?.total_projects || 0}
-                    </div>
-                  </CardContent>
-                </Card>
-                <Card glass className="glass-hover">
-                  <CardHeader className="flex flex-row items-center justify-between space-y-0 pb-2">
-                    <CardTitle className="text-sm font-medium text-muted-foreground">
-                      Sessions
-                    </CardTitle>
-                    <Folder className="h-5 w-5 text-teal-500" />
-                  </CardHeader>
-                  <CardContent>
-                    <div className="text-3xl font-bold text-foreground">
-                      {stats?.total_sessions || 0}
-                    </div>
-                  </CardContent>
-                </Card>
-                <Card glass className="glass-hover">
-                  <CardHeader className="flex flex-row items-center justify-between space-y-0 pb-2">
-                    <CardTitle className="text-sm font-medium text-muted-foreground">
-                      Total Size
-                    </CardTitle>
-                    <HardDrive className="h-5 w-5 text-teal-500" />
-                  </CardHeader>
-                  <CardContent>
-                    <div className="text-3xl font-bold text-foreground">
-                      {stats?.total_size || "0 B"}
-                    </div>
-                  </CardContent>
-                </Card>
-              </div>
+  const handleToggleArtifact = (artifactPath: string) => {
+    const newSelected = new Set(selectedArtifacts);
+    if (newSelected.has(artifactPath)) {
+      newSelected.delete(artifactPath);
+    } else {
+      newSelected.add(artifactPath);
+    }
+    setSelectedArtifacts(newSelected);
+  };
 
-              <div className="grid grid-cols-1 md:grid-cols-2 gap-4">
-                <Card
-                  glass
-                  className="cursor-pointer glass-hover"
-                  onClick={() => setView("projects")}
-                >
-                  <CardHeader>
-                    <CardTitle className="flex items-center gap-2 text-foreground">
-                      <Folder className="h-5 w-5 text-teal-500" />
-                      Manage Projects
-                    </CardTitle>
-                    <CardDescription>
-                      View and manage sessions by project
-                    </CardDescription>
-                  </CardHeader>
-                  <CardContent>
-                    <p className="text-sm text-muted-foreground">
-                      Select a project to view its sessions and delete selected
-                      ones.
-                    </p>
-                  </CardContent>
-                </Card>
+  const handleSelectAllArtifacts = () => {
+    if (selectedArtifacts.size === oldArtifacts.length) {
+      setSelectedArtifacts(new Set());
+    } else {
+      setSelectedArtifacts(new Set(oldArtifacts.map((a) => a.path)));
+    }
+  };
 
-                <Card
-                  glass
-                  className="cursor-pointer glass-hover"
-                  onClick={() => {
+  const handleDeleteArtifacts = async () => {
+    if (!artifactCategory || selectedArtifacts.size === 0) return;
+    setIsLoading(true);
+    try {
+      const count = await api.deleteArtifacts(
+        artifactCategory.category,
+        Array.from(selectedArtifacts)
+      );
+      showMessage("success", `Deleted ${count} ${artifactCategory.category}`);
+      await handleLoadOldArtifacts(artifactCategory);
+      await loadData();
+    } catch (error) {
+      showMessage("error", "Failed to delete artifacts");
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const handleDeleteAllArtifacts = async () => {
+    if (!artifactCategory || oldArtifacts.length === 0) return;
+    setIsLoading(true);
+    try {
+      const count = await api.deleteOldArtifacts(
+        artifactCategory.category,
+        artifactAge
+      );
+      showMessage("success", `Deleted ${count} ${artifactCategory.category}`);
+      setOldArtifacts([]);
+      setSelectedArtifacts(new Set());
+      await loadData();
+    } catch (error) {
+      showMessage("error", "Failed to delete artifacts");
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const handleFindOrphanedArtifacts = async () => {
+    setIsLoading(true);
+    try {
+      const orphans = await api.findOrphanedArtifacts();
+      if (orphans.length === 0) {
+        showMessage("success", "No orphaned artifacts found");
+      } else {
+        setOrphanedArtifacts(orphans);
+      }
+    } catch (error) {
+      showMessage("error", "Failed to find orphaned artifacts");
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const handleDeleteOrphanedArtifacts = async () => {
+    setIsLoading(true);
+    try {
+      const count = await api.deleteOrphanedArtifacts();
+      showMessage("success", `Deleted ${count} orphaned artifacts`);
+      setOrphanedArtifacts(null);
+      await loadData();
+    } catch (error) {
+      showMessage("error", "Failed to delete orphaned artifacts");
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const handleLoadDuplicates = async () => {
+    setIsLoading(true);
+    try {
+      const projectDuplicates = await api.findDuplicateSessions();
+      setDuplicates(projectDuplicates);
+      setSelectedDuplicates(
+        new Set(
+          projectDuplicates.flatMap((p) =>
+            p.duplicates.map((d) => d.redundant.path)
+          )
+        )
+      );
+    } catch (error) {
+      showMessage("error", "Failed to find duplicate sessions");
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const handleToggleDuplicate = (sessionPath: string) => {
+    const newSelected = new Set(selectedDuplicates);
+    if (newSelected.has(sessionPath)) {
+      newSelected.delete(sessionPath);
+    } else {
+      newSelected.add(sessionPath);
+    }
+    setSelectedDuplicates(newSelected);
+  };
+
+  const handleDeleteDuplicates = async () => {
+    if (selectedDuplicates.size === 0) return;
+    setIsLoading(true);
+    try {
+      const count = await api.deleteSessions(Array.from(selectedDuplicates));
+      showMessage("success", `Deleted ${count} redundant sessions`);
+      await handleLoadDuplicates();
+      await loadData();
+    } catch (error) {
+      showMessage("error", "Failed to delete sessions");
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const handleLoadActivity = async () => {
+    setIsLoading(true);
+    try {
+      setActivity(await api.getActivity());
+    } catch (error) {
+      showMessage("error", "Failed to load activity");
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const handleLoadToolUsage = async () => {
+    setIsLoading(true);
+    try {
+      setToolUsage(await api.getToolUsage(RANKING_LIMIT));
+    } catch (error) {
+      showMessage("error", "Failed to load tool usage");
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const handleLoadRanking = async () => {
+    setIsLoading(true);
+    try {
+      setRanking(await api.getStoreRanking(RANKING_LIMIT));
+      setSelectedRanked(new Set());
+    } catch (error) {
+      showMessage("error", "Failed to rank sessions");
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const handleToggleRanked = (sessionPath: string) => {
+    const newSelected = new Set(selectedRanked);
+    if (newSelected.has(sessionPath)) {
+      newSelected.delete(sessionPath);
+    } else {
+      newSelected.add(sessionPath);
+    }
+    setSelectedRanked(newSelected);
+  };
+
+  const handleDeleteRanked = async () => {
+    if (selectedRanked.size === 0) return;
+    setIsLoading(true);
+    try {
+      const count = await api.deleteSessions(Array.from(selectedRanked));
+      showMessage("success", `Deleted ${count} sessions`);
+      await handleLoadRanking();
+      await loadData();
+    } catch (error) {
+      showMessage("error", "Failed to delete sessions");
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const handleArchiveRanked = async () => {
+    if (selectedRanked.size === 0) return;
+    setIsLoading(true);
+    try {
+      const count = await api.archiveSessions(Array.from(selectedRanked));
+      showMessage("success", `Archived ${count} sessions`);
+      await handleLoadRanking();
+      await loadData();
+    } catch (error) {
+      showMessage("error", "Failed to archive sessions");
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  /** Finds sessions on deleted branches, all of them selected. */
+  const handleLoadStaleBranches = async () => {
+    setIsLoading(true);
+    try {
+      const report = await api.findStaleBranchSessions();
+      setStaleBranches(report);
+      setSelectedStale(new Set(report.sessions.map((s) => s.session.path)));
+    } catch (error) {
+      showMessage("error", "Failed to check git branches");
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const handleToggleStale = (sessionPath: string) => {
+    const newSelected = new Set(selectedStale);
+    if (newSelected.has(sessionPath)) {
+      newSelected.delete(sessionPath);
+    } else {
+      newSelected.add(sessionPath);
+    }
+    setSelectedStale(newSelected);
+  };
+
+  const handlePruneStale = async (action: "archive" | "delete") => {
+    if (selectedStale.size === 0) return;
+    setIsLoading(true);
+    try {
+      const paths = Array.from(selectedStale);
+      const count =
+        action === "archive"
+          ? await api.archiveSessions(paths)
+          : await api.deleteSessions(paths);
+      showMessage(
+        "success",
+        `${action === "archive" ? "Archived" : "Deleted"} ${count} sessions`
+      );
+      await handleLoadStaleBranches();
+      await loadData();
+    } catch (error) {
+      showMessage("error", `Failed to ${action} sessions`);
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  /** Previews a relocation; applying it is only offered for this preview. */
+  const handlePreviewRelocation = async () => {
+    setIsLoading(true);
+    try {
+      setRelocation(
+        await api.previewRelocation(relocateFrom.trim(), relocateTo.trim())
+      );
+    } catch (error) {
+      setRelocation(null);
+      showMessage("error", `${error}`);
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const handleRelocate = async () => {
+    if (!relocation?.dry_run) return;
+    setIsLoading(true);
+    try {
+      const report = await api.applyRelocation(relocation);
+      setRelocation(report);
+      showMessage(
+        "success",
+        `Relocated ${report.sessions} sessions to ${report.new_cwd}`
+      );
+      await loadData();
+    } catch (error) {
+      showMessage("error", `${error}`);
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const handleRunDoctor = async () => {
+    setIsLoading(true);
+    try {
+      setDoctorFindings(await api.runDoctor());
+    } catch (error) {
+      showMessage("error", "Failed to run health check");
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const handleCheckIntegrity = async () => {
+    setIsLoading(true);
+    try {
+      const reports = await api.checkIntegrity();
+      setIntegrityReports(reports);
+      setSelectedRepairs(
+        new Set(
+          reports.filter((r) => r.repairable).map((r) => r.session.path)
+        )
+      );
+    } catch (error) {
+      showMessage("error", "Failed to check transcripts");
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const handleToggleRepair = (sessionPath: string) => {
+    const newSelected = new Set(selectedRepairs);
+    if (newSelected.has(sessionPath)) {
+      newSelected.delete(sessionPath);
+    } else {
+      newSelected.add(sessionPath);
+    }
+    setSelectedRepairs(newSelected);
+  };
+
+  const handleRepairSessions = async () => {
+    if (selectedRepairs.size === 0) return;
+    setIsLoading(true);
+    try {
+      const outcomes = await api.repairSessions(Array.from(selectedRepairs));
+      const removed = outcomes.reduce((sum, o) => sum + o.removed_lines, 0);
+      showMessage(
+        "success",
+        `Repaired ${outcomes.length} transcripts, removed ${removed} lines (originals backed up)`
+      );
+      await handleCheckIntegrity();
+      await loadData();
+    } catch (error) {
+      showMessage("error", "Failed to repair transcripts");
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const extraSecretPatterns = () =>
+    secretPattern.trim() ? [secretPattern.trim()] : [];
+
+  const handleScanSecrets = async () => {
+    setIsLoading(true);
+    try {
+      const reports = await api.scanSecrets(extraSecretPatterns());
+      setSecretReports(reports);
+      setSelectedRedactions(new Set(reports.map((r) => r.session.path)));
+    } catch (error) {
+      showMessage("error", `Failed to scan for secrets: ${error}`);
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const handleToggleRedaction = (sessionPath: string) => {
+    const newSelected = new Set(selectedRedactions);
+    if (newSelected.has(sessionPath)) {
+      newSelected.delete(sessionPath);
+    } else {
+      newSelected.add(sessionPath);
+    }
+    setSelectedRedactions(newSelected);
+  };
+
+  const handleRedactSecrets = async () => {
+    if (selectedRedactions.size === 0) return;
+    setIsLoading(true);
+    try {
+      const outcomes = await api.redactSecrets(
+        Array.from(selectedRedactions),
+        extraSecretPatterns()
+      );
+      const redacted = outcomes.reduce((sum, o) => sum + o.redacted, 0);
+      showMessage(
+        "success",
+        `Redacted ${redacted} secrets in ${outcomes.length} transcripts (originals backed up)`
+      );
+      await handleScanSecrets();
+    } catch (error) {
+      showMessage("error", "Failed to redact secrets");
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  return (
+    <div className="min-h-screen p-6 overflow-hidden">
+      {message && (
+        <div
+          className={`fixed top-4 right-4 z-50 flex items-center gap-2 px-4 py-3 rounded-lg glass shadow-lg ${
+            message.type === "success"
+              ? "text-green-400 border-green-500/30"
+              : "text-red-400 border-red-500/30"
+          }`}
+        >
+          {message.type === "success" ? (
+            <CheckCircle className="w-4 h-4" />
+          ) : (
+            <XCircle className="w-4 h-4" />
+          )}
+          {message.text}
+        </div>
+      )}
+
+      <div className="w-full mx-auto h-[calc(100vh-48px)]">
+        {view === "dashboard" && (
+          <>
+            <header className="mb-8">
+              <div className="glass-light rounded-2xl p-6 mb-6 flex items-center gap-4">
+                <img
+                  src="/logo.png"
+                  alt="Logo"
+                  className="w-16 h-16 rounded-xl object-contain"
+                />
+                <div>
+                  <h1 className="text-3xl font-bold text-foreground mb-2 bg-gradient-to-r from-teal-400 to-emerald-400 bg-clip-text text-transparent">
+                    Claude Code Session Manager
+                  </h1>
+                  <p className="text-muted-foreground">
+                    Manage your Claude Code sessions across all projects
+                  </p>
+                </div>
+              </div>
+            </header>
+
+            <div className="space-y-6">
+              <div className="grid grid-cols-1 md:grid-cols-3 gap-4">
+                <Card glass className="glass-hover">
+                  <CardHeader className="flex flex-row items-center justify-between space-y-0 pb-2">
+                    <CardTitle className="text-sm font-medium text-muted-foreground">
+                      Projects
+                    </CardTitle>
+                    <Layers className="h-5 w-5 text-teal-500" />
+                  </CardHeader>
+                  <CardContent>
+                    <div className="text-3xl font-bold text-foreground">
+                      {stats?.total_projects || 0}
+                    </div>
+                  </CardContent>
+                </Card>
+                <Card glass className="glass-hover">
+                  <CardHeader className="flex flex-row items-center justify-between space-y-0 pb-2">
+                    <CardTitle className="text-sm font-medium text-muted-foreground">
+                      Sessions
+                    </CardTitle>
+                    <Folder className="h-5 w-5 text-teal-500" />
+                  </CardHeader>
+                  <CardContent>
+                    <div className="text-3xl font-bold text-foreground">
+                      {stats?.total_sessions || 0}
+                    </div>
+                  </CardContent>
+                </Card>
+                <Card glass className="glass-hover">
+                  <CardHeader className="flex flex-row items-center justify-between space-y-0 pb-2">
+                    <CardTitle className="text-sm font-medium text-muted-foreground">
+                      Total Size
+                    </CardTitle>
+                    <HardDrive className="h-5 w-5 text-teal-500" />
+                  </CardHeader>
+                  <CardContent>
+                    <div className="text-3xl font-bold text-foreground">
+                      {stats?.total_size || "0 B"}
+                    </div>
+                  </CardContent>
+                </Card>
+              </div>
+
+              <Card glass>
+                <CardHeader>
+                  <CardTitle className="flex items-center gap-2 text-foreground">
+                    <Clock className="h-5 w-5 text-teal-500" />
+                    Session Age
+                  </CardTitle>
+                  <CardDescription>
+                    {stats?.average_session_size || "0 B"} average,{" "}
+                    {stats?.median_session_size || "0 B"} median per session
+                    {stats?.empty_projects
+                      ? ` · ${stats.empty_projects} empty projects`
+                      : ""}
+                  </CardDescription>
+                </CardHeader>
+                <CardContent className="grid grid-cols-1 md:grid-cols-2 gap-6">
+                  <div className="space-y-2">
+                    {stats?.age_buckets.map((bucket) => {
+                      const max = Math.max(
+                        ...stats.age_buckets.map((b) => b.count),
+                        1
+                      );
+                      return (
+                        <div
+                          key={bucket.label}
+                          className="flex items-center gap-3 text-sm"
+                        >
+                          <span className="w-24 text-muted-foreground">
+                            {bucket.label}
+                          </span>
+                          <div className="flex-1 h-2 rounded bg-white/5">
+                            <div
+                              className="h-2 rounded bg-teal-500"
+                              style={{
+                                width: `${(bucket.count / max) * 100}%`,
+                              }}
+                            />
+                          </div>
+                          <span className="w-28 text-right text-foreground">
+                            {bucket.count} · {bucket.size}
+                          </span>
+                        </div>
+                      );
+                    })}
+                  </div>
+                  <div className="space-y-2 text-sm">
+                    {stats?.largest_project && (
+                      <p className="text-muted-foreground">
+                        Largest project:{" "}
+                        <span className="text-foreground">
+                          {stats.largest_project.name}
+                        </span>{" "}
+                        ({stats.largest_project.total_size},{" "}
+                        {stats.largest_project.session_count} sessions)
+                      </p>
+                    )}
+                    {stats?.oldest_session && (
+                      <p className="text-muted-foreground">
+                        Oldest session:{" "}
+                        <span className="text-foreground">
+                          {stats.oldest_session.project_name}/
+                          {stats.oldest_session.session.name}
+                        </span>{" "}
+                        ({stats.oldest_session.session.age_days} days)
+                      </p>
+                    )}
+                    {stats?.newest_session && (
+                      <p className="text-muted-foreground">
+                        Newest session:{" "}
+                        <span className="text-foreground">
+                          {stats.newest_session.project_name}/
+                          {stats.newest_session.session.name}
+                        </span>{" "}
+                        ({stats.newest_session.session.age_days} days)
+                      </p>
+                    )}
+                  </div>
+                </CardContent>
+              </Card>
+
+              <div className="grid grid-cols-1 md:grid-cols-2 gap-4">
+                <Card
+                  glass
+                  className="cursor-pointer glass-hover"
+                  onClick={() => setView("projects")}
+                >
+                  <CardHeader>
+                    <CardTitle className="flex items-center gap-2 text-foreground">
+                      <Folder className="h-5 w-5 text-teal-500" />
+                      Manage Projects
+                    </CardTitle>
+                    <CardDescription>
+                      View and manage sessions by project
+                    </CardDescription>
+                  </CardHeader>
+                  <CardContent>
+                    <p className="text-sm text-muted-foreground">
+                      Select a project to view its sessions and delete selected
+                      ones.
+                    </p>
+                  </CardContent>
+                </Card>
+
+                <Card
+                  glass
+                  className="cursor-pointer glass-hover"
+                  onClick={() => {
                     handleLoadOldSessions();
                     setView("delete-by-age");
                   }}
@@ -368,273 +1261,1875 @@ function App() {
                   </CardHeader>
                   <CardContent>
                     <p className="text-sm text-muted-foreground">
-                      Find and delete sessions based on their age.
+                      Find and delete sessions based on their age.
+                    </p>
+                  </CardContent>
+                </Card>
+
+                <Card
+                  glass
+                  className="cursor-pointer glass-hover"
+                  onClick={() => {
+                    handleLoadDuplicates();
+                    setView("duplicates");
+                  }}
+                >
+                  <CardHeader>
+                    <CardTitle className="flex items-center gap-2 text-foreground">
+                      <Copy className="h-5 w-5 text-teal-500" />
+                      Duplicate Sessions
+                    </CardTitle>
+                    <CardDescription>
+                      Find resumed copies that repeat an earlier session
+                    </CardDescription>
+                  </CardHeader>
+                  <CardContent>
+                    <p className="text-sm text-muted-foreground">
+                      Keep the most complete transcript and remove the rest.
+                    </p>
+                  </CardContent>
+                </Card>
+
+                <Card
+                  glass
+                  className="cursor-pointer glass-hover"
+                  onClick={() => {
+                    handleCheckIntegrity();
+                    setView("integrity");
+                  }}
+                >
+                  <CardHeader>
+                    <CardTitle className="flex items-center gap-2 text-foreground">
+                      <Stethoscope className="h-5 w-5 text-teal-500" />
+                      Transcript Integrity
+                    </CardTitle>
+                    <CardDescription>
+                      Find corrupt lines, truncated tails and broken links
+                    </CardDescription>
+                  </CardHeader>
+                  <CardContent>
+                    <p className="text-sm text-muted-foreground">
+                      Repair keeps only valid entries and backs up the original.
+                    </p>
+                  </CardContent>
+                </Card>
+
+                <Card
+                  glass
+                  className="cursor-pointer glass-hover"
+                  onClick={() => {
+                    handleRunDoctor();
+                    setView("doctor");
+                  }}
+                >
+                  <CardHeader>
+                    <CardTitle className="flex items-center gap-2 text-foreground">
+                      <Activity className="h-5 w-5 text-teal-500" />
+                      Doctor
+                    </CardTitle>
+                    <CardDescription>
+                      Health report for the whole Claude directory
+                    </CardDescription>
+                  </CardHeader>
+                  <CardContent>
+                    <p className="text-sm text-muted-foreground">
+                      Permissions, stray files, broken links and disk space.
+                    </p>
+                  </CardContent>
+                </Card>
+
+                <Card
+                  glass
+                  className="cursor-pointer glass-hover"
+                  onClick={() => {
+                    handleScanSecrets();
+                    setView("secrets");
+                  }}
+                >
+                  <CardHeader>
+                    <CardTitle className="flex items-center gap-2 text-foreground">
+                      <KeyRound className="h-5 w-5 text-teal-500" />
+                      Secret Scanner
+                    </CardTitle>
+                    <CardDescription>
+                      Find API keys, tokens and private keys in transcripts
+                    </CardDescription>
+                  </CardHeader>
+                  <CardContent>
+                    <p className="text-sm text-muted-foreground">
+                      Redaction masks them in place and backs up the original.
+                    </p>
+                  </CardContent>
+                </Card>
+
+                <Card
+                  glass
+                  className="cursor-pointer glass-hover"
+                  onClick={() => {
+                    handleLoadActivity();
+                    setView("activity");
+                  }}
+                >
+                  <CardHeader>
+                    <CardTitle className="flex items-center gap-2 text-foreground">
+                      <CalendarDays className="h-5 w-5 text-teal-500" />
+                      Activity
+                    </CardTitle>
+                    <CardDescription>
+                      When you actually use Claude Code
+                    </CardDescription>
+                  </CardHeader>
+                  <CardContent>
+                    <p className="text-sm text-muted-foreground">
+                      Messages per day over the past year and by weekday and hour.
+                    </p>
+                  </CardContent>
+                </Card>
+
+                <Card
+                  glass
+                  className="cursor-pointer glass-hover"
+                  onClick={() => {
+                    handleLoadRanking();
+                    setView("largest");
+                  }}
+                >
+                  <CardHeader>
+                    <CardTitle className="flex items-center gap-2 text-foreground">
+                      <Trophy className="h-5 w-5 text-teal-500" />
+                      Largest Sessions
+                    </CardTitle>
+                    <CardDescription>
+                      The biggest and fastest-growing sessions across all projects
+                    </CardDescription>
+                  </CardHeader>
+                  <CardContent>
+                    <p className="text-sm text-muted-foreground">
+                      Flags sessions far above their project's median size.
+                    </p>
+                  </CardContent>
+                </Card>
+
+                <Card
+                  glass
+                  className="cursor-pointer glass-hover"
+                  onClick={() => {
+                    handleLoadToolUsage();
+                    setView("tools");
+                  }}
+                >
+                  <CardHeader>
+                    <CardTitle className="flex items-center gap-2 text-foreground">
+                      <Wrench className="h-5 w-5 text-teal-500" />
+                      Tool Usage
+                    </CardTitle>
+                    <CardDescription>
+                      Which tools Claude calls and how often they fail
+                    </CardDescription>
+                  </CardHeader>
+                  <CardContent>
+                    <p className="text-sm text-muted-foreground">
+                      Calls and error rates by tool, MCP server, project and session.
+                    </p>
+                  </CardContent>
+                </Card>
+
+                <Card
+                  glass
+                  className="cursor-pointer glass-hover"
+                  onClick={() => {
+                    setRelocation(null);
+                    setView("relocate");
+                  }}
+                >
+                  <CardHeader>
+                    <CardTitle className="flex items-center gap-2 text-foreground">
+                      <Truck className="h-5 w-5 text-teal-500" />
+                      Relocate Project
+                    </CardTitle>
+                    <CardDescription>
+                      Follow a repository that moved to a new directory
+                    </CardDescription>
+                  </CardHeader>
+                  <CardContent>
+                    <p className="text-sm text-muted-foreground">
+                      Renames or merges the project and rewrites recorded paths.
+                    </p>
+                  </CardContent>
+                </Card>
+
+                <Card
+                  glass
+                  className="cursor-pointer glass-hover"
+                  onClick={() => {
+                    handleLoadStaleBranches();
+                    setView("stale-branches");
+                  }}
+                >
+                  <CardHeader>
+                    <CardTitle className="flex items-center gap-2 text-foreground">
+                      <GitBranch className="h-5 w-5 text-teal-500" />
+                      Deleted Branches
+                    </CardTitle>
+                    <CardDescription>
+                      Sessions whose git branch no longer exists
+                    </CardDescription>
+                  </CardHeader>
+                  <CardContent>
+                    <p className="text-sm text-muted-foreground">
+                      Reads local and remote-tracking refs, without fetching.
+                    </p>
+                  </CardContent>
+                </Card>
+              </div>
+
+              <Card glass>
+                <CardHeader>
+                  <CardTitle className="flex items-center gap-2 text-foreground">
+                    <Archive className="h-5 w-5 text-teal-500" />
+                    Other Artifacts
+                  </CardTitle>
+                  <CardDescription className="flex items-center justify-between">
+                    <span>
+                      {stats?.total_artifacts_size || "0 B"} outside of session
+                      transcripts
+                    </span>
+                    <Button
+                      variant="glass"
+                      size="sm"
+                      onClick={handleFindOrphanedArtifacts}
+                      disabled={isLoading}
+                    >
+                      Sweep Orphaned
+                    </Button>
+                  </CardDescription>
+                </CardHeader>
+                <CardContent className="grid grid-cols-1 md:grid-cols-5 gap-2">
+                  {stats?.artifacts.map((usage) => (
+                    <div
+                      key={usage.category}
+                      className="glass rounded-lg p-3 cursor-pointer glass-hover"
+                      onClick={() => handleSelectArtifactCategory(usage)}
+                    >
+                      <p className="text-sm text-muted-foreground">
+                        {usage.label}
+                      </p>
+                      <p className="font-semibold text-foreground">
+                        {usage.size}
+                      </p>
+                      <p className="text-xs text-muted-foreground">
+                        {usage.count} items
+                      </p>
+                    </div>
+                  ))}
+                </CardContent>
+              </Card>
+
+              <div className="flex justify-center">
+                <Button variant="glass" onClick={loadData} disabled={isLoading}>
+                  <RefreshCw
+                    className={`w-4 h-4 mr-2 ${
+                      isLoading ? "animate-spin" : ""
+                    }`}
+                  />
+                  Refresh
+                </Button>
+              </div>
+            </div>
+          </>
+        )}
+
+        {view === "projects" && (
+          <div className="flex flex-col h-[calc(100vh-50px)]">
+            <div className="flex items-center gap-4 mb-4 shrink-0">
+              <Button variant="glass" onClick={() => setView("dashboard")}>
+                ← Back to Dashboard
+              </Button>
+              <h2 className="text-xl font-semibold text-foreground">
+                Projects ({projects.length})
+              </h2>
+              {pendingTrash.length > 0 && (
+                <Button
+                  variant="destructive"
+                  size="sm"
+                  onClick={handleEmptyTrash}
+                  disabled={isLoading}
+                >
+                  <Trash2 className="w-4 h-4 mr-1" />
+                  Delete Moved Originals
+                </Button>
+              )}
+            </div>
+
+            <ScrollArea className="flex-1 rounded-xl border-0">
+              <div className="p-2 space-y-2">
+                {projects.map((project) => (
+                  <Card
+                    key={project.path}
+                    glass
+                    className="cursor-pointer glass-hover"
+                    onClick={() => handleSelectProject(project)}
+                  >
+                    <CardContent className="flex items-center justify-between p-4">
+                      <div>
+                        <h3 className="font-semibold text-foreground">
+                          {project.name}
+                        </h3>
+                        <p className="text-sm text-muted-foreground">
+                          {project.session_count} sessions •{" "}
+                          {project.total_size}
+                        </p>
+                      </div>
+                      <div className="flex items-center gap-2">
+                        <Button
+                          variant="glass"
+                          size="sm"
+                          onClick={(e) => {
+                            e.stopPropagation();
+                            handleShowProjectDetail(project);
+                          }}
+                          disabled={isLoading}
+                        >
+                          <Info className="w-4 h-4 mr-1" />
+                          Details
+                        </Button>
+                        <Button
+                          variant="destructive"
+                          size="sm"
+                          onClick={(e) => {
+                            e.stopPropagation();
+                            setSelectedProject(project);
+                          }}
+                        >
+                          <Trash2 className="w-4 h-4 mr-1" />
+                          Delete Project
+                        </Button>
+                      </div>
+                    </CardContent>
+                  </Card>
+                ))}
+                {projects.length === 0 && (
+                  <div className="glass rounded-lg p-8 text-center">
+                    <p className="text-muted-foreground py-8">
+                      No projects found
+                    </p>
+                  </div>
+                )}
+              </div>
+            </ScrollArea>
+          </div>
+        )}
+
+        {view === "project-detail" && projectDetail && (
+          <div className="flex flex-col h-[calc(100vh-50px)]">
+            <div className="flex items-center gap-4 mb-4 shrink-0">
+              <Button variant="glass" onClick={() => setView("projects")}>
+                ← Back to Projects
+              </Button>
+              <h2 className="text-xl font-semibold text-foreground">
+                {projectDetail.project.name}
+              </h2>
+            </div>
+
+            <ScrollArea className="flex-1 rounded-xl border-0">
+              <div className="p-2 space-y-4">
+                <Card glass>
+                  <CardContent className="grid grid-cols-1 md:grid-cols-2 gap-2 pt-4 text-sm">
+                    <p className="text-muted-foreground">
+                      Working directory:{" "}
+                      <span className="text-foreground">
+                        {projectDetail.cwd || "unknown"}
+                      </span>
+                    </p>
+                    <p className="text-muted-foreground">
+                      Sessions:{" "}
+                      <span className="text-foreground">
+                        {projectDetail.project.session_count} (
+                        {projectDetail.project.total_size} total,{" "}
+                        {projectDetail.average_session_size} average)
+                      </span>
+                    </p>
+                    <p className="text-muted-foreground">
+                      First activity:{" "}
+                      <span className="text-foreground">
+                        {formatTimestamp(projectDetail.first_activity)}
+                      </span>
+                    </p>
+                    <p className="text-muted-foreground">
+                      Last activity:{" "}
+                      <span className="text-foreground">
+                        {formatTimestamp(projectDetail.last_activity)}
+                      </span>
+                    </p>
+                    <p className="text-muted-foreground">
+                      Messages:{" "}
+                      <span className="text-foreground">
+                        {projectDetail.message_count}
+                      </span>
+                    </p>
+                    <p className="text-muted-foreground">
+                      Tokens:{" "}
+                      <span className="text-foreground">
+                        {formatCount(projectDetail.tokens.input)} in,{" "}
+                        {formatCount(projectDetail.tokens.output)} out,{" "}
+                        {formatCount(projectDetail.tokens.cache_creation)} cache
+                        write, {formatCount(projectDetail.tokens.cache_read)}{" "}
+                        cache read
+                      </span>
+                    </p>
+                  </CardContent>
+                </Card>
+
+                <div className="grid grid-cols-1 md:grid-cols-2 gap-4">
+                  <Card glass>
+                    <CardHeader>
+                      <CardTitle className="text-foreground">Top tools</CardTitle>
+                    </CardHeader>
+                    <CardContent className="space-y-2">
+                      {projectDetail.top_tools.map((tool) => (
+                        <div
+                          key={tool.name}
+                          className="flex items-center gap-3 text-sm"
+                        >
+                          <span className="w-28 truncate text-muted-foreground">
+                            {tool.name}
+                          </span>
+                          <div className="flex-1 h-2 rounded bg-white/5">
+                            <div
+                              className="h-2 rounded bg-teal-500"
+                              style={{
+                                width: `${
+                                  (tool.count / projectDetail.top_tools[0].count) *
+                                  100
+                                }%`,
+                              }}
+                            />
+                          </div>
+                          <span className="w-12 text-right text-foreground">
+                            {tool.count}
+                          </span>
+                        </div>
+                      ))}
+                      {projectDetail.top_tools.length === 0 && (
+                        <p className="text-sm text-muted-foreground">
+                          No tool calls
+                        </p>
+                      )}
+                    </CardContent>
+                  </Card>
+
+                  <Card glass>
+                    <CardHeader>
+                      <CardTitle className="text-foreground">
+                        Git branches
+                      </CardTitle>
+                    </CardHeader>
+                    <CardContent className="flex flex-wrap gap-2">
+                      {projectDetail.git_branches.map((branch) => (
+                        <Badge key={branch.name} variant="secondary">
+                          {branch.name} · {branch.count} sessions
+                        </Badge>
+                      ))}
+                      {projectDetail.git_branches.length === 0 && (
+                        <p className="text-sm text-muted-foreground">
+                          No git branches recorded
+                        </p>
+                      )}
+                    </CardContent>
+                  </Card>
+                </div>
+
+                <Card glass>
+                  <CardHeader>
+                    <CardTitle className="text-foreground">
+                      Largest sessions
+                    </CardTitle>
+                  </CardHeader>
+                  <CardContent className="space-y-1 text-sm">
+                    {projectDetail.largest_sessions.map((session) => (
+                      <div key={session.path} className="flex items-center gap-2">
+                        <Badge variant="secondary">{session.size}</Badge>
+                        <span className="text-foreground truncate">
+                          {session.content_preview || session.name}
+                        </span>
+                        <span className="text-muted-foreground shrink-0">
+                          {session.age_days} days
+                        </span>
+                      </div>
+                    ))}
+                  </CardContent>
+                </Card>
+              </div>
+            </ScrollArea>
+          </div>
+        )}
+
+        {view === "sessions" && selectedProject && (
+          <div className="flex flex-col h-[calc(100vh-50px)]">
+            <div className="flex items-center gap-4 mb-4 shrink-0">
+              <Button
+                variant="glass"
+                onClick={() => {
+                  setView("projects");
+                  setSelectedProject(null);
+                }}
+              >
+                ← Back to Projects
+              </Button>
+              <h2 className="text-xl font-semibold text-foreground">
+                Sessions in "{selectedProject.name}" ({selectedSessions.size}/
+                {selectedProject.sessions.length})
+              </h2>
+            </div>
+
+            <div className="flex items-center gap-2 mb-4 shrink-0">
+              <Button
+                variant="glass"
+                size="sm"
+                onClick={handleSelectAllSessions}
+              >
+                {selectedSessions.size === visibleSessions.length
+                  ? "Deselect All"
+                  : "Select All"}
+              </Button>
+              <Button
+                variant="destructive"
+                size="sm"
+                onClick={handleDeleteSessions}
+                disabled={selectedSessions.size === 0}
+              >
+                <Trash2 className="w-4 h-4 mr-1" />
+                Delete Selected ({selectedSessions.size})
+              </Button>
+              <Button
+                variant="glass"
+                size="sm"
+                onClick={handleCompareSessions}
+                disabled={selectedSessions.size !== 2 || isLoading}
+              >
+                <GitCompare className="w-4 h-4 mr-1" />
+                Compare
+              </Button>
+              <Button
+                variant="glass"
+                size="sm"
+                onClick={() => setMoveDialog({ targetPath: "", newCwd: "" })}
+                disabled={selectedSessions.size === 0 || isLoading}
+              >
+                <FolderInput className="w-4 h-4 mr-1" />
+                Move…
+              </Button>
+              {branchGroups.length > 0 && (
+                <select
+                  value={branchFilter}
+                  onChange={(e) => handleFilterBranch(e.target.value)}
+                  className="glass-input"
+                >
+                  <option value="">All branches</option>
+                  {branchGroups.map((group) => (
+                    <option
+                      key={group.branch ?? "-"}
+                      value={group.branch ?? "-"}
+                    >
+                      {group.branch ?? "(no branch)"}
+                    </option>
+                  ))}
+                </select>
+              )}
+            </div>
+
+            {branchGroups.length > 1 && (
+              <div className="space-y-2 mb-4 shrink-0">
+                {branchGroups.map((group) => (
+                  <Card key={group.branch ?? "-"} glass>
+                    <CardContent className="flex items-center gap-3 p-3 flex-wrap">
+                      <GitBranch className="w-4 h-4 text-teal-500" />
+                      <span className="font-medium text-foreground">
+                        {group.branch ?? "(no branch)"}
+                      </span>
+                      <Badge variant="secondary">
+                        {group.session_paths.length} sessions
+                      </Badge>
+                      <Badge variant="secondary">{group.size}</Badge>
+                      <Badge variant="outline">
+                        last active {formatTimestamp(group.last_activity)}
+                      </Badge>
+                      <div className="flex-1" />
+                      <Button
+                        variant="glass"
+                        size="sm"
+                        onClick={() => handleFilterBranch(group.branch ?? "-")}
+                      >
+                        Show
+                      </Button>
+                      <Button
+                        variant="glass"
+                        size="sm"
+                        onClick={() => handleBranchBulkAction(group, "archive")}
+                        disabled={isLoading}
+                      >
+                        <Archive className="w-4 h-4 mr-1" />
+                        Archive all
+                      </Button>
+                      <Button
+                        variant="destructive"
+                        size="sm"
+                        onClick={() => handleBranchBulkAction(group, "delete")}
+                        disabled={isLoading}
+                      >
+                        <Trash2 className="w-4 h-4 mr-1" />
+                        Delete all
+                      </Button>
+                    </CardContent>
+                  </Card>
+                ))}
+              </div>
+            )}
+
+            {chains.length > 0 && (
+              <div className="space-y-2 mb-4 shrink-0">
+                {chains.map((chain) => (
+                  <Card key={chain.sessions[0].path} glass>
+                    <CardContent className="flex items-center gap-3 p-3 flex-wrap">
+                      <span className="font-medium text-foreground">
+                        ⛓ Continued conversation
+                      </span>
+                      <Badge variant="secondary">
+                        {chain.sessions.length} sessions
+                      </Badge>
+                      <Badge variant="secondary">{chain.total_size}</Badge>
+                      <Badge variant="outline">spanning {chain.duration}</Badge>
+                      <div className="flex-1" />
+                      <Button
+                        variant="glass"
+                        size="sm"
+                        onClick={() => handleSelectChain(chain)}
+                      >
+                        Select chain
+                      </Button>
+                      <Button
+                        variant="glass"
+                        size="sm"
+                        onClick={() => handleExportChain(chain)}
+                      >
+                        Export
+                      </Button>
+                      <Button
+                        variant="glass"
+                        size="sm"
+                        onClick={() => handleExportChain(chain, true)}
+                      >
+                        Export anonymized
+                      </Button>
+                      <Button
+                        variant="destructive"
+                        size="sm"
+                        onClick={() => handleDeleteChain(chain)}
+                        disabled={isLoading}
+                      >
+                        <Trash2 className="w-4 h-4 mr-1" />
+                        Delete chain
+                      </Button>
+                    </CardContent>
+                  </Card>
+                ))}
+              </div>
+            )}
+
+            <ScrollArea className="flex-1 rounded-xl border-0">
+              <div className="p-2 space-y-2">
+                {visibleSessions.map((session) => (
+                  <Card
+                    key={session.path}
+                    glass
+                    className="cursor-pointer glass-hover"
+                    onClick={() => handleSelectSession(session)}
+                  >
+                    <CardContent className="flex items-start gap-3 p-3">
+                      <div onClick={(e) => e.stopPropagation()}>
+                        <Checkbox
+                          checked={selectedSessions.has(session.path)}
+                          onCheckedChange={() =>
+                            handleToggleSession(session.path)
+                          }
+                        />
+                      </div>
+                      <div className="flex-1 min-w-0">
+                        <div className="flex items-center gap-2 flex-wrap">
+                          <h4 className="font-medium text-foreground truncate">
+                            {session.name}
+                          </h4>
+                          <Badge variant="secondary">{session.size}</Badge>
+                          {session.linked_size && (
+                            <Badge variant="secondary">
+                              +{session.linked_size} linked
+                            </Badge>
+                          )}
+                          {session.subagents.length > 0 && (
+                            <Badge variant="outline">
+                              {session.subagents.length} sub-agents
+                            </Badge>
+                          )}
+                          {session.git_branch && (
+                            <Badge variant="outline">
+                              <GitBranch className="w-3 h-3 mr-1" />
+                              {session.git_branch}
+                            </Badge>
+                          )}
+                          {chainPosition(session.path) && (
+                            <Badge variant="outline">
+                              ⛓ part {chainPosition(session.path)!.index + 1}/
+                              {chainPosition(session.path)!.length}
+                            </Badge>
+                          )}
+                          <Badge variant="outline">
+                            {session.age_days} days ago
+                          </Badge>
+                        </div>
+                        {session.content_preview && (
+                          <p className="text-sm text-muted-foreground mt-1 line-clamp-10">
+                            {session.content_preview}
+                          </p>
+                        )}
+                      </div>
+                    </CardContent>
+                  </Card>
+                ))}
+              </div>
+            </ScrollArea>
+          </div>
+        )}
+
+        {view === "delete-by-age" && (
+          <div className="flex flex-col h-[calc(100vh-50px)]">
+            <div className="flex items-center gap-4 mb-4 shrink-0">
+              <Button variant="glass" onClick={() => setView("dashboard")}>
+                ← Back to Dashboard
+              </Button>
+              <h2 className="text-xl font-semibold text-foreground">
+                Delete by Age
+              </h2>
+            </div>
+
+            <Card glass className="shrink-0">
+              <CardContent className="pt-4 pb-4">
+                <div className="flex items-center gap-4 flex-wrap">
+                  <label className="text-sm font-medium text-muted-foreground">
+                    Delete sessions older than
+                  </label>
+                  <input
+                    type="number"
+                    value={deleteAge}
+                    onChange={(e) =>
+                      setDeleteAge(parseInt(e.target.value) || 0)
+                    }
+                    className="glass-input w-20 text-center"
+                    min="1"
+                  />
+                  <label className="text-sm font-medium text-muted-foreground">
+                    days
+                  </label>
+                  <Button
+                    variant="glass"
+                    onClick={handleLoadOldSessions}
+                    disabled={isLoading}
+                  >
+                    <RefreshCw
+                      className={`w-4 h-4 mr-2 ${
+                        isLoading ? "animate-spin" : ""
+                      }`}
+                    />
+                    Find Sessions
+                  </Button>
+                </div>
+              </CardContent>
+            </Card>
+
+            {oldSessions.length > 0 && (
+              <div className="flex flex-col flex-1 min-h-0 mt-4">
+                <div className="flex items-center gap-2 flex-wrap mb-4 shrink-0">
+                  <Button
+                    variant="glass"
+                    size="sm"
+                    onClick={handleSelectAllOldSessions}
+                  >
+                    {selectedOldSessions.size === oldSessions.length
+                      ? "Deselect All"
+                      : "Select All"}
+                  </Button>
+                  <Button
+                    variant="destructive"
+                    size="sm"
+                    onClick={handleDeleteOldSessions}
+                    disabled={selectedOldSessions.size === 0}
+                  >
+                    <Trash2 className="w-4 h-4 mr-1" />
+                    Delete Selected ({selectedOldSessions.size})
+                  </Button>
+                  <Button
+                    variant="destructive"
+                    size="sm"
+                    onClick={handleDeleteAllOldSessions}
+                  >
+                    <AlertTriangle className="w-4 h-4 mr-1" />
+                    Delete All ({oldSessions.length})
+                  </Button>
+                </div>
+
+                <ScrollArea className="flex-1 rounded-xl border-0">
+                  <div className="p-2 space-y-2">
+                    {oldSessions.map((session) => (
+                      <Card key={session.path} glass className="glass-hover">
+                        <CardContent className="flex items-start gap-3 p-3">
+                          <Checkbox
+                            checked={selectedOldSessions.has(session.path)}
+                            onCheckedChange={() =>
+                              handleToggleOldSession(session.path)
+                            }
+                          />
+                          <div className="flex-1 min-w-0">
+                            <div className="flex items-center gap-2">
+                              <h4 className="font-medium text-foreground truncate">
+                                {session.name}
+                              </h4>
+                              <Badge variant="secondary">{session.size}</Badge>
+                              <Badge variant="outline">
+                                {session.age_days} days ago
+                              </Badge>
+                            </div>
+                            {session.content_preview && (
+                              <p className="text-sm text-muted-foreground mt-1 line-clamp-10">
+                                {session.content_preview}
+                              </p>
+                            )}
+                          </div>
+                        </CardContent>
+                      </Card>
+                    ))}
+                  </div>
+                </ScrollArea>
+              </div>
+            )}
+          </div>
+        )}
+
+        {view === "artifacts" && artifactCategory && (
+          <div className="flex flex-col h-[calc(100vh-50px)]">
+            <div className="flex items-center gap-4 mb-4 shrink-0">
+              <Button variant="glass" onClick={() => setView("dashboard")}>
+                ← Back to Dashboard
+              </Button>
+              <h2 className="text-xl font-semibold text-foreground">
+                Clean Up {artifactCategory.label}
+              </h2>
+            </div>
+
+            <Card glass className="shrink-0">
+              <CardContent className="pt-4 pb-4">
+                <div className="flex items-center gap-4 flex-wrap">
+                  <label className="text-sm font-medium text-muted-foreground">
+                    Delete {artifactCategory.category} older than
+                  </label>
+                  <input
+                    type="number"
+                    value={artifactAge}
+                    onChange={(e) =>
+                      setArtifactAge(parseInt(e.target.value) || 0)
+                    }
+                    className="glass-input w-20 text-center"
+                    min="1"
+                  />
+                  <label className="text-sm font-medium text-muted-foreground">
+                    days
+                  </label>
+                  <Button
+                    variant="glass"
+                    onClick={() => handleLoadOldArtifacts(artifactCategory)}
+                    disabled={isLoading}
+                  >
+                    <RefreshCw
+                      className={`w-4 h-4 mr-2 ${
+                        isLoading ? "animate-spin" : ""
+                      }`}
+                    />
+                    Find Artifacts
+                  </Button>
+                </div>
+              </CardContent>
+            </Card>
+
+            {oldArtifacts.length > 0 && (
+              <div className="flex flex-col flex-1 min-h-0 mt-4">
+                <div className="flex items-center gap-2 flex-wrap mb-4 shrink-0">
+                  <Button
+                    variant="glass"
+                    size="sm"
+                    onClick={handleSelectAllArtifacts}
+                  >
+                    {selectedArtifacts.size === oldArtifacts.length
+                      ? "Deselect All"
+                      : "Select All"}
+                  </Button>
+                  <Button
+                    variant="destructive"
+                    size="sm"
+                    onClick={handleDeleteArtifacts}
+                    disabled={selectedArtifacts.size === 0}
+                  >
+                    <Trash2 className="w-4 h-4 mr-1" />
+                    Delete Selected ({selectedArtifacts.size})
+                  </Button>
+                  <Button
+                    variant="destructive"
+                    size="sm"
+                    onClick={handleDeleteAllArtifacts}
+                  >
+                    <AlertTriangle className="w-4 h-4 mr-1" />
+                    Delete All ({oldArtifacts.length})
+                  </Button>
+                </div>
+
+                <ScrollArea className="flex-1 rounded-xl border-0">
+                  <div className="p-2 space-y-2">
+                    {oldArtifacts.map((artifact) => (
+                      <Card key={artifact.path} glass className="glass-hover">
+                        <CardContent className="flex items-center gap-3 p-3">
+                          <Checkbox
+                            checked={selectedArtifacts.has(artifact.path)}
+                            onCheckedChange={() =>
+                              handleToggleArtifact(artifact.path)
+                            }
+                          />
+                          <h4 className="font-medium text-foreground truncate">
+                            {artifact.name}
+                          </h4>
+                          <Badge variant="secondary">{artifact.size}</Badge>
+                          <Badge variant="outline">
+                            {artifact.age_days} days ago
+                          </Badge>
+                        </CardContent>
+                      </Card>
+                    ))}
+                  </div>
+                </ScrollArea>
+              </div>
+            )}
+          </div>
+        )}
+
+        {view === "duplicates" && (
+          <div className="flex flex-col h-[calc(100vh-50px)]">
+            <div className="flex items-center gap-4 mb-4 shrink-0">
+              <Button variant="glass" onClick={() => setView("dashboard")}>
+                ← Back to Dashboard
+              </Button>
+              <h2 className="text-xl font-semibold text-foreground">
+                Duplicate Sessions
+              </h2>
+              <Button
+                variant="destructive"
+                size="sm"
+                onClick={handleDeleteDuplicates}
+                disabled={selectedDuplicates.size === 0 || isLoading}
+              >
+                <Trash2 className="w-4 h-4 mr-1" />
+                Delete Selected ({selectedDuplicates.size})
+              </Button>
+            </div>
+
+            <ScrollArea className="flex-1 rounded-xl border-0">
+              <div className="p-2 space-y-4">
+                {duplicates.map((project) => (
+                  <div key={project.project_path} className="space-y-2">
+                    <h3 className="font-semibold text-foreground">
+                      {project.project_name}{" "}
+                      <Badge variant="secondary">
+                        {project.redundant_size} redundant
+                      </Badge>
+                    </h3>
+                    {project.duplicates.map((duplicate) => (
+                      <Card
+                        key={duplicate.redundant.path}
+                        glass
+                        className="glass-hover"
+                      >
+                        <CardContent className="flex items-start gap-3 p-3">
+                          <Checkbox
+                            checked={selectedDuplicates.has(
+                              duplicate.redundant.path
+                            )}
+                            onCheckedChange={() =>
+                              handleToggleDuplicate(duplicate.redundant.path)
+                            }
+                          />
+                          <div className="flex-1 min-w-0">
+                            <div className="flex items-center gap-2 flex-wrap">
+                              <h4 className="font-medium text-foreground truncate">
+                                {duplicate.redundant.name}
+                              </h4>
+                              <Badge variant="secondary">
+                                {duplicate.redundant.size}
+                              </Badge>
+                              <Badge variant="outline">{duplicate.kind}</Badge>
+                            </div>
+                            <p className="text-sm text-muted-foreground mt-1">
+                              {duplicate.shared_entries} entries also in{" "}
+                              {duplicate.kept.name} ({duplicate.kept.size})
+                            </p>
+                          </div>
+                        </CardContent>
+                      </Card>
+                    ))}
+                  </div>
+                ))}
+                {duplicates.length === 0 && !isLoading && (
+                  <div className="glass rounded-lg p-8 text-center">
+                    <p className="text-muted-foreground py-8">
+                      No duplicate sessions found
+                    </p>
+                  </div>
+                )}
+              </div>
+            </ScrollArea>
+          </div>
+        )}
+
+        {view === "largest" && (
+          <div className="flex flex-col h-[calc(100vh-50px)]">
+            <div className="flex items-center gap-4 mb-4 shrink-0">
+              <Button variant="glass" onClick={() => setView("dashboard")}>
+                ← Back to Dashboard
+              </Button>
+              <h2 className="text-xl font-semibold text-foreground">
+                Largest Sessions
+              </h2>
+              <Button
+                variant="glass"
+                size="sm"
+                onClick={handleArchiveRanked}
+                disabled={selectedRanked.size === 0 || isLoading}
+              >
+                <Archive className="w-4 h-4 mr-1" />
+                Archive Selected ({selectedRanked.size})
+              </Button>
+              <Button
+                variant="destructive"
+                size="sm"
+                onClick={handleDeleteRanked}
+                disabled={selectedRanked.size === 0 || isLoading}
+              >
+                <Trash2 className="w-4 h-4 mr-1" />
+                Delete Selected ({selectedRanked.size})
+              </Button>
+            </div>
+
+            <ScrollArea className="flex-1 rounded-xl border-0">
+              {ranking && (
+                <div className="p-2 space-y-6">
+                  {(
+                    [
+                      ["Largest sessions", ranking.largest_sessions, false],
+                      ["Fastest growing", ranking.fastest_growing, true],
+                    ] as [string, RankedSessionDto[], boolean][]
+                  ).map(([title, entries, growth]) => (
+                    <div key={title} className="space-y-2">
+                      <h3 className="font-semibold text-foreground">{title}</h3>
+                      {entries.map((entry) => (
+                        <Card
+                          key={entry.session.path}
+                          glass
+                          className="glass-hover"
+                        >
+                          <CardContent className="flex items-start gap-3 p-3">
+                            <Checkbox
+                              checked={selectedRanked.has(entry.session.path)}
+                              onCheckedChange={() =>
+                                handleToggleRanked(entry.session.path)
+                              }
+                            />
+                            <div className="flex-1 min-w-0">
+                              <div className="flex items-center gap-2 flex-wrap">
+                                <h4 className="font-medium text-foreground truncate">
+                                  {entry.session.name}
+                                </h4>
+                                <Badge variant="secondary">
+                                  {entry.total_size}
+                                </Badge>
+                                {growth && entry.growth_per_day && (
+                                  <Badge variant="outline">
+                                    {entry.growth_per_day}/day
+                                  </Badge>
+                                )}
+                                {entry.is_outlier && (
+                                  <Badge variant="destructive">
+                                    <AlertTriangle className="w-3 h-3 mr-1" />
+                                    Outlier
+                                  </Badge>
+                                )}
+                              </div>
+                              <p className="text-sm text-muted-foreground mt-1">
+                                {entry.project_name} · {entry.session.age_days}{" "}
+                                days old
+                                {entry.median_ratio !== null &&
+                                  ` · ${entry.median_ratio.toFixed(1)}× the project median (${entry.project_median})`}
+                              </p>
+                            </div>
+                          </CardContent>
+                        </Card>
+                      ))}
+                    </div>
+                  ))}
+
+                  <div className="space-y-2">
+                    <h3 className="font-semibold text-foreground">
+                      Largest projects
+                    </h3>
+                    {ranking.largest_projects.map((project) => (
+                      <div
+                        key={project.path}
+                        className="glass rounded-lg p-3 flex items-center justify-between"
+                      >
+                        <span className="text-foreground truncate">
+                          {project.name}
+                        </span>
+                        <span className="text-sm text-muted-foreground">
+                          {project.session_count} sessions ·{" "}
+                          {project.total_size}
+                        </span>
+                      </div>
+                    ))}
+                  </div>
+                </div>
+              )}
+            </ScrollArea>
+          </div>
+        )}
+
+        {view === "tools" && (
+          <div className="flex flex-col h-[calc(100vh-50px)]">
+            <div className="flex items-center gap-4 mb-4 shrink-0">
+              <Button variant="glass" onClick={() => setView("dashboard")}>
+                ← Back to Dashboard
+              </Button>
+              <h2 className="text-xl font-semibold text-foreground">
+                Tool Usage
+              </h2>
+              {toolUsage && (
+                <span className="text-sm text-muted-foreground">
+                  {toolUsage.overall.calls} calls · {toolUsage.overall.errors}{" "}
+                  failed ({formatRate(toolUsage.overall.error_rate)})
+                </span>
+              )}
+            </div>
+
+            <ScrollArea className="flex-1 rounded-xl border-0">
+              {toolUsage && (
+                <div className="p-2 space-y-6">
+                  {(
+                    [
+                      ["Tools", toolUsage.overall.tools],
+                      ["MCP servers", toolUsage.overall.mcp_servers],
+                    ] as [string, ToolCountsDto[]][]
+                  )
+                    .filter(([, entries]) => entries.length > 0)
+                    .map(([title, entries]) => (
+                      <div key={title} className="space-y-2">
+                        <h3 className="font-semibold text-foreground">{title}</h3>
+                        {entries.map((tool) => (
+                          <div
+                            key={tool.name}
+                            className="glass rounded-lg p-3 flex items-center gap-3"
+                          >
+                            <span className="text-foreground truncate w-72">
+                              {tool.name}
+                            </span>
+                            <div className="flex-1 h-2 rounded bg-white/5">
+                              <div
+                                className="h-2 rounded bg-teal-500"
+                                style={{
+                                  width: `${(tool.calls / entries[0].calls) * 100}%`,
+                                }}
+                              />
+                            </div>
+                            <span className="text-sm text-muted-foreground w-48 text-right">
+                              {tool.calls} calls · {tool.errors} failed
+                            </span>
+                            <Badge
+                              variant={tool.errors > 0 ? "destructive" : "secondary"}
+                            >
+                              {formatRate(tool.error_rate)}
+                            </Badge>
+                          </div>
+                        ))}
+                      </div>
+                    ))}
+
+                  <div className="space-y-2">
+                    <h3 className="font-semibold text-foreground">Projects</h3>
+                    {toolUsage.projects.map((project) => (
+                      <div
+                        key={project.project_path}
+                        className="glass rounded-lg p-3 flex items-center justify-between"
+                      >
+                        <span className="text-foreground truncate">
+                          {project.project_name}
+                        </span>
+                        <span className="text-sm text-muted-foreground">
+                          {project.usage.calls} calls · {project.usage.errors}{" "}
+                          failed ({formatRate(project.usage.error_rate)}) · top:{" "}
+                          {project.usage.tools[0]?.name ?? "-"}
+                        </span>
+                      </div>
+                    ))}
+                  </div>
+
+                  {(
+                    [
+                      ["Most tool calls", toolUsage.sessions],
+                      ["Most failed tool calls", toolUsage.failing_sessions],
+                    ] as [string, SessionToolUsageDto[]][]
+                  ).map(([title, entries]) => (
+                    <div key={title} className="space-y-2">
+                      <h3 className="font-semibold text-foreground">{title}</h3>
+                      {entries.map((entry) => (
+                        <div
+                          key={entry.session.path}
+                          className="glass rounded-lg p-3 flex items-center justify-between"
+                        >
+                          <span className="text-foreground truncate">
+                            {entry.project_name}/{entry.session.name}
+                          </span>
+                          <span className="text-sm text-muted-foreground">
+                            {entry.usage.calls} calls · {entry.usage.errors}{" "}
+                            failed ({formatRate(entry.usage.error_rate)})
+                          </span>
+                        </div>
+                      ))}
+                    </div>
+                  ))}
+                </div>
+              )}
+            </ScrollArea>
+          </div>
+        )}
+
+        {view === "relocate" && (
+          <div className="flex flex-col h-[calc(100vh-50px)]">
+            <div className="flex items-center gap-4 mb-4 shrink-0">
+              <Button variant="glass" onClick={() => setView("dashboard")}>
+                ← Back to Dashboard
+              </Button>
+              <h2 className="text-xl font-semibold text-foreground">
+                Relocate Project
+              </h2>
+            </div>
+
+            <Card glass className="shrink-0 mb-4">
+              <CardContent className="pt-4 pb-4 space-y-3">
+                <div className="flex items-center gap-4 flex-wrap">
+                  <label className="text-sm font-medium text-muted-foreground w-32">
+                    Old directory
+                  </label>
+                  <input
+                    type="text"
+                    value={relocateFrom}
+                    onChange={(e) => {
+                      setRelocateFrom(e.target.value);
+                      setRelocation(null);
+                    }}
+                    className="glass-input flex-1"
+                    placeholder="/old/path/to/repo"
+                  />
+                </div>
+                <div className="flex items-center gap-4 flex-wrap">
+                  <label className="text-sm font-medium text-muted-foreground w-32">
+                    New directory
+                  </label>
+                  <input
+                    type="text"
+                    value={relocateTo}
+                    onChange={(e) => {
+                      setRelocateTo(e.target.value);
+                      setRelocation(null);
+                    }}
+                    className="glass-input flex-1"
+                    placeholder="/new/path/to/repo"
+                  />
+                </div>
+                <div className="flex items-center gap-2">
+                  <Button
+                    variant="glass"
+                    onClick={handlePreviewRelocation}
+                    disabled={!relocateFrom.trim() || !relocateTo.trim() || isLoading}
+                  >
+                    Preview
+                  </Button>
+                  <Button
+                    variant="glass"
+                    onClick={handleRelocate}
+                    disabled={
+                      !relocation?.dry_run ||
+                      relocation.conflicts.length > 0 ||
+                      isLoading
+                    }
+                  >
+                    <Truck className="w-4 h-4 mr-1" />
+                    Relocate
+                  </Button>
+                </div>
+              </CardContent>
+            </Card>
+
+            {relocation && (
+              <ScrollArea className="flex-1 rounded-xl border-0">
+                <Card glass>
+                  <CardContent className="pt-4 space-y-2 text-sm">
+                    <p className="font-medium text-foreground">
+                      {relocation.dry_run ? "Would relocate" : "Relocated"}{" "}
+                      {relocation.old_cwd} → {relocation.new_cwd}
                     </p>
+                    <p className="text-muted-foreground font-mono break-all">
+                      {relocation.source} →{" "}
+                      {relocation.target}
+                      {relocation.merge && (
+                        <Badge variant="secondary" className="ml-2">
+                          merge
+                        </Badge>
+                      )}
+                    </p>
+                    <p className="text-muted-foreground">
+                      {relocation.sessions} sessions ·{" "}
+                      {relocation.transcripts} transcripts ·{" "}
+                      {relocation.rewritten_entries} entries with the old path
+                    </p>
+                    {relocation.conflicts.length > 0 && (
+                      <div className="space-y-1">
+                        <p className="flex items-center gap-2 text-red-400">
+                          <AlertTriangle className="w-4 h-4" />
+                          {relocation.conflicts.length} files already exist in
+                          the target project
+                        </p>
+                        {relocation.conflicts.map((conflict) => (
+                          <p
+                            key={conflict}
+                            className="text-xs text-muted-foreground font-mono break-all"
+                          >
+                            {conflict}
+                          </p>
+                        ))}
+                      </div>
+                    )}
                   </CardContent>
                 </Card>
-              </div>
-
-              <div className="flex justify-center">
-                <Button variant="glass" onClick={loadData} disabled={isLoading}>
-                  <RefreshCw
-                    className={`w-4 h-4 mr-2 ${
-                      isLoading ? "animate-spin" : ""
-                    }`}
-                  />
-                  Refresh
-                </Button>
-              </div>
-            </div>
-          </>
+              </ScrollArea>
+            )}
+          </div>
         )}
 
-        {view === "projects" && (
+        {view === "stale-branches" && (
           <div className="flex flex-col h-[calc(100vh-50px)]">
             <div className="flex items-center gap-4 mb-4 shrink-0">
               <Button variant="glass" onClick={() => setView("dashboard")}>
                 ← Back to Dashboard
               </Button>
               <h2 className="text-xl font-semibold text-foreground">
-                Projects ({projects.length})
+                Sessions on Deleted Branches
               </h2>
+              <Button
+                variant="glass"
+                size="sm"
+                onClick={() => handlePruneStale("archive")}
+                disabled={selectedStale.size === 0 || isLoading}
+              >
+                <Archive className="w-4 h-4 mr-1" />
+                Archive Selected ({selectedStale.size})
+              </Button>
+              <Button
+                variant="destructive"
+                size="sm"
+                onClick={() => handlePruneStale("delete")}
+                disabled={selectedStale.size === 0 || isLoading}
+              >
+                <Trash2 className="w-4 h-4 mr-1" />
+                Delete Selected ({selectedStale.size})
+              </Button>
             </div>
 
+            {staleBranches && (
+              <p className="text-sm text-muted-foreground mb-4 shrink-0">
+                Checked {staleBranches.repositories} repositories ·{" "}
+                {staleBranches.sessions.length} sessions on deleted branches (
+                {staleBranches.total_size})
+                {staleBranches.unchecked > 0 &&
+                  ` · ${staleBranches.unchecked} sessions could not be checked`}
+              </p>
+            )}
+
             <ScrollArea className="flex-1 rounded-xl border-0">
               <div className="p-2 space-y-2">
-                {projects.map((project) => (
-                  <Card
-                    key={project.path}
-                    glass
-                    className="cursor-pointer glass-hover"
-                    onClick={() => handleSelectProject(project)}
-                  >
-                    <CardContent className="flex items-center justify-between p-4">
-                      <div>
-                        <h3 className="font-semibold text-foreground">
-                          {project.name}
-                        </h3>
-                        <p className="text-sm text-muted-foreground">
-                          {project.session_count} sessions •{" "}
-                          {project.total_size}
+                {staleBranches?.sessions.length === 0 && (
+                  <p className="flex items-center gap-2 text-muted-foreground">
+                    <CheckCircle className="w-4 h-4 text-teal-500" />
+                    Every session's branch still exists
+                  </p>
+                )}
+                {staleBranches?.sessions.map((entry) => (
+                  <Card key={entry.session.path} glass className="glass-hover">
+                    <CardContent className="flex items-start gap-3 p-3">
+                      <Checkbox
+                        checked={selectedStale.has(entry.session.path)}
+                        onCheckedChange={() =>
+                          handleToggleStale(entry.session.path)
+                        }
+                      />
+                      <div className="flex-1 min-w-0">
+                        <div className="flex items-center gap-2 flex-wrap">
+                          <h4 className="font-medium text-foreground truncate">
+                            {entry.session.name}
+                          </h4>
+                          <Badge variant="secondary">{entry.session.size}</Badge>
+                          <Badge variant="outline">
+                            <GitBranch className="w-3 h-3 mr-1" />
+                            {entry.branch}
+                          </Badge>
+                        </div>
+                        <p className="text-sm text-muted-foreground mt-1 truncate">
+                          {entry.repository} · {entry.project_name} ·{" "}
+                          {entry.session.age_days} days old
                         </p>
+                        {entry.session.content_preview && (
+                          <p className="text-sm text-muted-foreground truncate">
+                            {entry.session.content_preview}
+                          </p>
+                        )}
                       </div>
-                      <Button
-                        variant="destructive"
-                        size="sm"
-                        onClick={(e) => {
-                          e.stopPropagation();
-                          setSelectedProject(project);
-                        }}
-                      >
-                        <Trash2 className="w-4 h-4 mr-1" />
-                        Delete Project
-                      </Button>
                     </CardContent>
                   </Card>
                 ))}
-                {projects.length === 0 && (
-                  <div className="glass rounded-lg p-8 text-center">
-                    <p className="text-muted-foreground py-8">
-                      No projects found
-                    </p>
-                  </div>
-                )}
               </div>
             </ScrollArea>
           </div>
         )}
 
-        {view === "sessions" && selectedProject && (
+        {view === "activity" && (
           <div className="flex flex-col h-[calc(100vh-50px)]">
             <div className="flex items-center gap-4 mb-4 shrink-0">
-              <Button
-                variant="glass"
-                onClick={() => {
-                  setView("projects");
-                  setSelectedProject(null);
-                }}
-              >
-                ← Back to Projects
+              <Button variant="glass" onClick={() => setView("dashboard")}>
+                ← Back to Dashboard
               </Button>
-              <h2 className="text-xl font-semibold text-foreground">
-                Sessions in "{selectedProject.name}" ({selectedSessions.size}/
-                {selectedProject.sessions.length})
-              </h2>
+              <h2 className="text-xl font-semibold text-foreground">Activity</h2>
+              {activity && (
+                <span className="text-sm text-muted-foreground">
+                  {activity.total_messages} messages, {activity.active_days}{" "}
+                  active days in the past year
+                </span>
+              )}
             </div>
 
-            <div className="flex items-center gap-2 mb-4 shrink-0">
+            {activity && (
+              <ScrollArea className="flex-1 rounded-xl border-0">
+                <div className="p-2 space-y-4">
+                  <Card glass>
+                    <CardHeader>
+                      <CardTitle className="text-foreground">
+                        Messages per day
+                      </CardTitle>
+                    </CardHeader>
+                    <CardContent className="overflow-x-auto">
+                      <div className="grid grid-rows-7 grid-flow-col gap-[3px] w-max">
+                        {Array.from({
+                          length:
+                            (new Date(activity.days[0]?.date).getUTCDay() + 6) %
+                            7,
+                        }).map((_, index) => (
+                          <div key={`pad-${index}`} className="w-3 h-3" />
+                        ))}
+                        {activity.days.map((day) => (
+                          <div
+                            key={day.date}
+                            className="w-3 h-3 rounded-sm"
+                            style={{
+                              background: heatColor(
+                                day.messages,
+                                Math.max(...activity.days.map((d) => d.messages))
+                              ),
+                            }}
+                            title={`${day.date}: ${day.messages} messages in ${day.sessions} sessions`}
+                          />
+                        ))}
+                      </div>
+                    </CardContent>
+                  </Card>
+
+                  <Card glass>
+                    <CardHeader>
+                      <CardTitle className="text-foreground">
+                        Messages by weekday and hour
+                      </CardTitle>
+                    </CardHeader>
+                    <CardContent className="overflow-x-auto">
+                      <div className="space-y-[3px] w-max">
+                        {WEEKDAYS.map((weekday, row) => (
+                          <div key={weekday} className="flex items-center gap-[3px]">
+                            <span className="w-10 text-xs text-muted-foreground">
+                              {weekday}
+                            </span>
+                            {activity.hourly
+                              .filter((cell) => cell.weekday === row)
+                              .map((cell) => (
+                                <div
+                                  key={cell.hour}
+                                  className="w-5 h-5 rounded-sm"
+                                  style={{
+                                    background: heatColor(
+                                      cell.messages,
+                                      Math.max(
+                                        ...activity.hourly.map((c) => c.messages)
+                                      )
+                                    ),
+                                  }}
+                                  title={`${weekday} ${cell.hour}:00: ${cell.messages} messages`}
+                                />
+                              ))}
+                          </div>
+                        ))}
+                        <div className="flex gap-[3px] text-xs text-muted-foreground">
+                          <span className="w-10" />
+                          {Array.from({ length: 24 }).map((_, hour) => (
+                            <span key={hour} className="w-5 text-center">
+                              {hour % 3 === 0 ? hour : ""}
+                            </span>
+                          ))}
+                        </div>
+                      </div>
+                    </CardContent>
+                  </Card>
+                </div>
+              </ScrollArea>
+            )}
+          </div>
+        )}
+
+        {view === "doctor" && (
+          <div className="flex flex-col h-[calc(100vh-50px)]">
+            <div className="flex items-center gap-4 mb-4 shrink-0">
+              <Button variant="glass" onClick={() => setView("dashboard")}>
+                ← Back to Dashboard
+              </Button>
+              <h2 className="text-xl font-semibold text-foreground">Doctor</h2>
               <Button
                 variant="glass"
                 size="sm"
-                onClick={handleSelectAllSessions}
+                onClick={handleRunDoctor}
+                disabled={isLoading}
               >
-                {selectedSessions.size === selectedProject.sessions.length
-                  ? "Deselect All"
-                  : "Select All"}
+                <RefreshCw
+                  className={`w-4 h-4 mr-1 ${isLoading ? "animate-spin" : ""}`}
+                />
+                Run Again
+              </Button>
+            </div>
+
+            <ScrollArea className="flex-1 rounded-xl border-0">
+              <div className="p-2 space-y-2">
+                {doctorFindings.map((finding, index) => (
+                  <Card key={index} glass>
+                    <CardContent className="flex items-start gap-3 p-3">
+                      {finding.severity === "error" ? (
+                        <XCircle className="h-5 w-5 text-red-400 shrink-0" />
+                      ) : finding.severity === "warning" ? (
+                        <AlertTriangle className="h-5 w-5 text-yellow-400 shrink-0" />
+                      ) : (
+                        <Info className="h-5 w-5 text-teal-500 shrink-0" />
+                      )}
+                      <div className="flex-1 min-w-0">
+                        <div className="flex items-center gap-2 flex-wrap">
+                          <h4 className="font-medium text-foreground">
+                            {finding.message}
+                          </h4>
+                          <Badge variant="outline">{finding.check}</Badge>
+                        </div>
+                        {finding.path && (
+                          <p className="text-sm text-muted-foreground truncate">
+                            {finding.path}
+                          </p>
+                        )}
+                        <p className="text-sm text-foreground mt-1">
+                          → {finding.suggestion}
+                        </p>
+                      </div>
+                    </CardContent>
+                  </Card>
+                ))}
+                {doctorFindings.length === 0 && !isLoading && (
+                  <div className="glass rounded-lg p-8 text-center">
+                    <CheckCircle className="h-8 w-8 text-teal-500 mx-auto" />
+                    <p className="text-muted-foreground py-4">
+                      No problems found
+                    </p>
+                  </div>
+                )}
+              </div>
+            </ScrollArea>
+          </div>
+        )}
+
+        {view === "integrity" && (
+          <div className="flex flex-col h-[calc(100vh-50px)]">
+            <div className="flex items-center gap-4 mb-4 shrink-0">
+              <Button variant="glass" onClick={() => setView("dashboard")}>
+                ← Back to Dashboard
               </Button>
+              <h2 className="text-xl font-semibold text-foreground">
+                Transcript Integrity
+              </h2>
               <Button
                 variant="destructive"
                 size="sm"
-                onClick={handleDeleteSessions}
-                disabled={selectedSessions.size === 0}
+                onClick={handleRepairSessions}
+                disabled={selectedRepairs.size === 0 || isLoading}
               >
-                <Trash2 className="w-4 h-4 mr-1" />
-                Delete Selected ({selectedSessions.size})
+                Repair Selected ({selectedRepairs.size})
               </Button>
             </div>
 
             <ScrollArea className="flex-1 rounded-xl border-0">
               <div className="p-2 space-y-2">
-                {selectedProject.sessions.map((session) => (
+                {integrityReports.map((report) => (
                   <Card
-                    key={session.path}
+                    key={report.session.path}
                     glass
-                    className="cursor-pointer glass-hover"
-                    onClick={() => handleSelectSession(session)}
+                    className="glass-hover"
                   >
                     <CardContent className="flex items-start gap-3 p-3">
-                      <div onClick={(e) => e.stopPropagation()}>
-                        <Checkbox
-                          checked={selectedSessions.has(session.path)}
-                          onCheckedChange={() =>
-                            handleToggleSession(session.path)
-                          }
-                        />
-                      </div>
+                      <Checkbox
+                        checked={selectedRepairs.has(report.session.path)}
+                        disabled={!report.repairable}
+                        onCheckedChange={() =>
+                          handleToggleRepair(report.session.path)
+                        }
+                      />
                       <div className="flex-1 min-w-0">
                         <div className="flex items-center gap-2 flex-wrap">
                           <h4 className="font-medium text-foreground truncate">
-                            {session.name}
+                            {report.project_name}/{report.session.name}
                           </h4>
-                          <Badge variant="secondary">{session.size}</Badge>
-                          <Badge variant="outline">
-                            {session.age_days} days ago
-                          </Badge>
-                        </div>
-                        {session.content_preview && (
-                          <p className="text-sm text-muted-foreground mt-1 line-clamp-10">
-                            {session.content_preview}
-                          </p>
-                        )}
+                          <Badge variant="secondary">
+                            {report.valid_entries} valid entries
+                          </Badge>
+                          <Badge variant="outline">
+                            {report.issues.length} issues
+                          </Badge>
+                        </div>
+                        <ul className="text-sm text-muted-foreground mt-1 space-y-0.5">
+                          {report.issues.slice(0, 5).map((issue, index) => (
+                            <li key={index}>
+                              {issue.repairable ? "✗" : "⚠"} {issue.description}
+                            </li>
+                          ))}
+                          {report.issues.length > 5 && (
+                            <li>… and {report.issues.length - 5} more</li>
+                          )}
+                        </ul>
                       </div>
                     </CardContent>
                   </Card>
                 ))}
+                {integrityReports.length === 0 && !isLoading && (
+                  <div className="glass rounded-lg p-8 text-center">
+                    <p className="text-muted-foreground py-8">
+                      All transcripts are valid
+                    </p>
+                  </div>
+                )}
               </div>
             </ScrollArea>
           </div>
         )}
 
-        {view === "delete-by-age" && (
+        {view === "secrets" && (
           <div className="flex flex-col h-[calc(100vh-50px)]">
             <div className="flex items-center gap-4 mb-4 shrink-0">
               <Button variant="glass" onClick={() => setView("dashboard")}>
                 ← Back to Dashboard
               </Button>
               <h2 className="text-xl font-semibold text-foreground">
-                Delete by Age
+                Secret Scanner
               </h2>
+              <Button
+                variant="destructive"
+                size="sm"
+                onClick={handleRedactSecrets}
+                disabled={selectedRedactions.size === 0 || isLoading}
+              >
+                Redact Selected ({selectedRedactions.size})
+              </Button>
             </div>
 
-            <Card glass className="shrink-0">
+            <Card glass className="shrink-0 mb-4">
               <CardContent className="pt-4 pb-4">
                 <div className="flex items-center gap-4 flex-wrap">
                   <label className="text-sm font-medium text-muted-foreground">
-                    Delete sessions older than
+                    Extra pattern (regex)
                   </label>
                   <input
-                    type="number"
-                    value={deleteAge}
-                    onChange={(e) =>
-                      setDeleteAge(parseInt(e.target.value) || 0)
-                    }
-                    className="glass-input w-20 text-center"
-                    min="1"
+                    type="text"
+                    value={secretPattern}
+                    onChange={(e) => setSecretPattern(e.target.value)}
+                    className="glass-input w-64"
+                    placeholder="e.g. itk_[0-9]{6}"
                   />
-                  <label className="text-sm font-medium text-muted-foreground">
-                    days
-                  </label>
                   <Button
                     variant="glass"
-                    onClick={handleLoadOldSessions}
+                    onClick={handleScanSecrets}
                     disabled={isLoading}
                   >
                     <RefreshCw
-                      className={`w-4 h-4 mr-2 ${
-                        isLoading ? "animate-spin" : ""
-                      }`}
+                      className={`w-4 h-4 mr-2 ${isLoading ? "animate-spin" : ""}`}
                     />
-                    Find Sessions
+                    Scan
                   </Button>
                 </div>
               </CardContent>
             </Card>
 
-            {oldSessions.length > 0 && (
-              <div className="flex flex-col flex-1 min-h-0 mt-4">
-                <div className="flex items-center gap-2 flex-wrap mb-4 shrink-0">
-                  <Button
-                    variant="glass"
-                    size="sm"
-                    onClick={handleSelectAllOldSessions}
-                  >
-                    {selectedOldSessions.size === oldSessions.length
-                      ? "Deselect All"
-                      : "Select All"}
-                  </Button>
-                  <Button
-                    variant="destructive"
-                    size="sm"
-                    onClick={handleDeleteOldSessions}
-                    disabled={selectedOldSessions.size === 0}
-                  >
-                    <Trash2 className="w-4 h-4 mr-1" />
-                    Delete Selected ({selectedOldSessions.size})
-                  </Button>
-                  <Button
-                    variant="destructive"
-                    size="sm"
-                    onClick={handleDeleteAllOldSessions}
+            <ScrollArea className="flex-1 rounded-xl border-0">
+              <div className="p-2 space-y-2">
+                {secretReports.map((report) => (
+                  <Card
+                    key={report.session.path}
+                    glass
+                    className="glass-hover"
                   >
-                    <AlertTriangle className="w-4 h-4 mr-1" />
-                    Delete All ({oldSessions.length})
-                  </Button>
-                </div>
+                    <CardContent className="flex items-start gap-3 p-3">
+                      <Checkbox
+                        checked={selectedRedactions.has(report.session.path)}
+                        onCheckedChange={() =>
+                          handleToggleRedaction(report.session.path)
+                        }
+                      />
+                      <div className="flex-1 min-w-0">
+                        <div className="flex items-center gap-2 flex-wrap">
+                          <h4 className="font-medium text-foreground truncate">
+                            {report.project_name}/{report.session.name}
+                          </h4>
+                          <Badge variant="outline">
+                            {report.findings.length} findings
+                          </Badge>
+                        </div>
+                        <ul className="text-sm text-muted-foreground mt-1 space-y-0.5">
+                          {report.findings.slice(0, 5).map((finding, index) => (
+                            <li key={index}>
+                              line {finding.line}:{finding.column} (
+                              {finding.entry_type}){" "}
+                              <Badge variant="secondary">{finding.detector}</Badge>{" "}
+                              <code>{finding.masked}</code>
+                            </li>
+                          ))}
+                          {report.findings.length > 5 && (
+                            <li>… and {report.findings.length - 5} more</li>
+                          )}
+                        </ul>
+                      </div>
+                    </CardContent>
+                  </Card>
+                ))}
+                {secretReports.length === 0 && !isLoading && (
+                  <div className="glass rounded-lg p-8 text-center">
+                    <p className="text-muted-foreground py-8">
+                      No secrets found
+                    </p>
+                  </div>
+                )}
+              </div>
+            </ScrollArea>
+          </div>
+        )}
 
-                <ScrollArea className="flex-1 rounded-xl border-0">
-                  <div className="p-2 space-y-2">
-                    {oldSessions.map((session) => (
-                      <Card key={session.path} glass className="glass-hover">
-                        <CardContent className="flex items-start gap-3 p-3">
-                          <Checkbox
-                            checked={selectedOldSessions.has(session.path)}
-                            onCheckedChange={() =>
-                              handleToggleOldSession(session.path)
-                            }
-                          />
-                          <div className="flex-1 min-w-0">
-                            <div className="flex items-center gap-2">
-                              <h4 className="font-medium text-foreground truncate">
-                                {session.name}
-                              </h4>
-                              <Badge variant="secondary">{session.size}</Badge>
-                              <Badge variant="outline">
-                                {session.age_days} days ago
-                              </Badge>
+        {view === "session-diff" && sessionDiff && (
+          <div className="flex flex-col h-[calc(100vh-50px)]">
+            <div className="flex items-center gap-4 mb-4 shrink-0">
+              <Button variant="glass" onClick={() => setView("sessions")}>
+                ← Back to Sessions
+              </Button>
+              <h2 className="text-xl font-semibold text-foreground">
+                Session Diff
+              </h2>
+            </div>
+
+            <Card glass className="shrink-0 mb-4">
+              <CardContent className="flex items-center gap-2 p-3 flex-wrap">
+                {sessionDiff.diff.divergence_row === null ? (
+                  <Badge variant="secondary">
+                    Identical ({sessionDiff.diff.shared_count} messages)
+                  </Badge>
+                ) : (
+                  <>
+                    <Badge variant="secondary">
+                      Shared prefix: {sessionDiff.diff.shared_prefix} messages
+                    </Badge>
+                    <Badge variant="outline">
+                      Diverges at line{" "}
+                      {sessionDiff.diff.left_divergence_line ?? "end"} /{" "}
+                      {sessionDiff.diff.right_divergence_line ?? "end"}
+                    </Badge>
+                    <Badge variant="secondary">
+                      {sessionDiff.diff.shared_count} shared
+                    </Badge>
+                    <Badge variant="destructive">
+                      {sessionDiff.diff.left_only_count} only left
+                    </Badge>
+                    <Badge variant="default">
+                      {sessionDiff.diff.right_only_count} only right
+                    </Badge>
+                  </>
+                )}
+              </CardContent>
+            </Card>
+
+            <div className="grid grid-cols-2 gap-2 mb-2 shrink-0 text-sm font-medium text-foreground">
+              <span className="truncate">{sessionDiff.left.name}</span>
+              <span className="truncate">{sessionDiff.right.name}</span>
+            </div>
+
+            <ScrollArea className="flex-1 rounded-xl border-0">
+              <div className="p-2 space-y-1">
+                {sessionDiff.diff.rows.map((row, index) => (
+                  <div key={index} className="grid grid-cols-2 gap-2">
+                    {[row.left, row.right].map((message, side) => (
+                      <div
+                        key={side}
+                        className={`rounded-lg p-2 text-sm ${
+                          !message
+                            ? ""
+                            : row.kind === "shared"
+                            ? "glass text-muted-foreground"
+                            : row.kind === "left"
+                            ? "bg-red-500/10 text-foreground"
+                            : "bg-emerald-500/10 text-foreground"
+                        } ${
+                          index === sessionDiff.diff.divergence_row
+                            ? "ring-1 ring-amber-400"
+                            : ""
+                        }`}
+                      >
+                        {message && (
+                          <>
+                            <div className="text-xs font-medium mb-1">
+                              {message.role} · line {message.line_number}
+                              {row.matched_by === "content" && " · ≈ content"}
                             </div>
-                            {session.content_preview && (
-                              <p className="text-sm text-muted-foreground mt-1 line-clamp-10">
-                                {session.content_preview}
-                              </p>
-                            )}
-                          </div>
-                        </CardContent>
-                      </Card>
+                            <p className="whitespace-pre-wrap break-words line-clamp-6">
+                              {message.text}
+                            </p>
+                          </>
+                        )}
+                      </div>
                     ))}
                   </div>
-                </ScrollArea>
+                ))}
               </div>
-            )}
+            </ScrollArea>
           </div>
         )}
 
@@ -655,6 +3150,14 @@ function App() {
                 <FileText className="h-5 w-5 text-teal-500" />
                 Session Details
               </h2>
+              <Button
+                variant="glass"
+                size="sm"
+                onClick={() => handleCopyResumeCommand(selectedSession)}
+              >
+                <Terminal className="w-4 h-4 mr-1" />
+                Copy Resume Command
+              </Button>
             </div>
 
             {!contentExpanded && (
@@ -685,14 +3188,109 @@ function App() {
                         {selectedSession.path}
                       </p>
                     </div>
+                    <div>
+                      <span className="text-muted-foreground">Branch:</span>
+                      <p className="font-medium text-foreground truncate">
+                        {selectedSession.git_branch ?? "unknown"}
+                      </p>
+                    </div>
+                    <div>
+                      <span className="text-muted-foreground">Version:</span>
+                      <p className="font-medium text-foreground">
+                        {selectedSession.version ?? "unknown"}
+                      </p>
+                    </div>
+                    <div className="col-span-2">
+                      <span className="text-muted-foreground">
+                        Working directory:
+                      </span>
+                      <p className="font-medium text-foreground truncate">
+                        {selectedSession.cwd ?? "unknown"}
+                      </p>
+                    </div>
+                    <div className="col-span-2">
+                      <span className="text-muted-foreground">Active:</span>
+                      <p className="font-medium text-foreground">
+                        {formatTimestamp(selectedSession.first_timestamp)} –{" "}
+                        {formatTimestamp(selectedSession.last_timestamp)}
+                      </p>
+                    </div>
+                    <div>
+                      <span className="text-muted-foreground">Messages:</span>
+                      <p className="font-medium text-foreground">
+                        {selectedSession.user_messages} user ·{" "}
+                        {selectedSession.assistant_messages} assistant ·{" "}
+                        {selectedSession.tool_messages} tool
+                      </p>
+                    </div>
+                    <div>
+                      <span className="text-muted-foreground">Tool calls:</span>
+                      <p className="font-medium text-foreground">
+                        {selectedSession.tool_calls} ·{" "}
+                        {selectedSession.tool_errors} failed
+                      </p>
+                    </div>
+                    <div>
+                      <span className="text-muted-foreground">Models:</span>
+                      <p className="font-medium text-foreground truncate">
+                        {selectedSession.models.join(", ") || "none"}
+                      </p>
+                    </div>
                   </div>
                 </CardContent>
               </Card>
             )}
 
+            {selectedSession.subagents.length > 0 && (
+              <div className="flex items-center gap-2 flex-wrap mt-4 shrink-0">
+                <span className="text-sm text-muted-foreground">Threads:</span>
+                <Button
+                  variant={
+                    activeThreadPath === selectedSession.path
+                      ? "default"
+                      : "glass"
+                  }
+                  size="sm"
+                  onClick={() => loadThreadContent(selectedSession.path)}
+                >
+                  Main conversation
+                </Button>
+                {selectedSession.subagents.map((subagent) => (
+                  <Button
+                    key={subagent.path}
+                    variant={
+                      activeThreadPath === subagent.path ? "default" : "glass"
+                    }
+                    size="sm"
+                    title={subagent.content_preview || undefined}
+                    onClick={() => loadThreadContent(subagent.path)}
+                  >
+                    ↳ {subagent.name} ({subagent.size})
+                  </Button>
+                ))}
+              </div>
+            )}
+
             <div className="flex-1 flex flex-col min-h-0 mt-4">
               <div className="flex items-center justify-between mb-2 shrink-0">
-                <span className="text-sm text-muted-foreground">Content:</span>
+                <div className="flex items-center gap-3">
+                  <span className="text-sm text-muted-foreground">
+                    Content:
+                  </span>
+                  {conversation &&
+                    conversation.abandoned_branches.length > 0 && (
+                      <label className="flex items-center gap-2 text-sm text-muted-foreground cursor-pointer">
+                        <Checkbox
+                          checked={showAbandoned}
+                          onCheckedChange={(checked) =>
+                            setShowAbandoned(checked === true)
+                          }
+                        />
+                        Show {conversation.abandoned_branches.length} abandoned
+                        branches
+                      </label>
+                    )}
+                </div>
                 <Button
                   variant="ghost"
                   size="sm"
@@ -723,7 +3321,8 @@ function App() {
                 >
                   <div className="glass rounded-lg p-4 min-h-full">
                     <pre className="text-sm whitespace-pre-wrap font-mono break-all w-full text-foreground">
-                      {sessionFullContent ||
+                      {(conversation &&
+                        formatConversation(conversation, showAbandoned)) ||
                         selectedSession.content_preview ||
                         "No content available"}
                     </pre>
@@ -747,7 +3346,8 @@ function App() {
               <DialogDescription>
                 Are you sure you want to delete the project "
                 {selectedProject?.name}"? This will permanently delete all
-                sessions in this project and cannot be undone.
+                sessions in this project, along with their todos, file history
+                and debug logs, and cannot be undone.
               </DialogDescription>
             </DialogHeader>
             <DialogFooter>
@@ -764,6 +3364,154 @@ function App() {
             </DialogFooter>
           </DialogContent>
         </Dialog>
+
+        <Dialog
+          open={!!exportReport}
+          onOpenChange={() => setExportReport(null)}
+        >
+          <DialogContent className="glass">
+            <DialogHeader>
+              <DialogTitle className="flex items-center gap-2 text-foreground">
+                <CheckCircle className="h-5 w-5 text-green-400" />
+                Anonymized Export
+              </DialogTitle>
+              <DialogDescription>
+                Written to {exportReport?.path}.{" "}
+                {exportReport?.replacements.length
+                  ? "These values were replaced:"
+                  : "Nothing needed anonymizing."}
+              </DialogDescription>
+            </DialogHeader>
+            <ScrollArea className="max-h-64">
+              <div className="space-y-1 text-sm">
+                {exportReport?.replacements.map((replacement) => (
+                  <div
+                    key={replacement.placeholder}
+                    className="flex items-center gap-2"
+                  >
+                    <Badge variant="secondary">{replacement.placeholder}</Badge>
+                    <span className="text-muted-foreground truncate">
+                      {replacement.original}
+                    </span>
+                    <span className="text-muted-foreground">
+                      ({replacement.occurrences}×)
+                    </span>
+                  </div>
+                ))}
+              </div>
+            </ScrollArea>
+            <DialogFooter>
+              <Button variant="glass" onClick={() => setExportReport(null)}>
+                Close
+              </Button>
+            </DialogFooter>
+          </DialogContent>
+        </Dialog>
+
+        <Dialog
+          open={!!orphanedArtifacts}
+          onOpenChange={() => setOrphanedArtifacts(null)}
+        >
+          <DialogContent className="glass">
+            <DialogHeader>
+              <DialogTitle className="flex items-center gap-2 text-red-400">
+                <AlertTriangle className="h-5 w-5" />
+                Sweep Orphaned Artifacts
+              </DialogTitle>
+              <DialogDescription>
+                {orphanedArtifacts?.length} artifacts belong to sessions that
+                no longer exist. Delete them?
+              </DialogDescription>
+            </DialogHeader>
+            <ScrollArea className="max-h-64">
+              <div className="space-y-1 text-sm">
+                {orphanedArtifacts?.map((artifact) => (
+                  <div
+                    key={artifact.path}
+                    className="flex justify-between gap-2 text-muted-foreground"
+                  >
+                    <span className="truncate">
+                      {artifact.category}/{artifact.name}
+                    </span>
+                    <span>{artifact.size}</span>
+                  </div>
+                ))}
+              </div>
+            </ScrollArea>
+            <DialogFooter>
+              <Button
+                variant="glass"
+                onClick={() => setOrphanedArtifacts(null)}
+              >
+                Cancel
+              </Button>
+              <Button
+                variant="destructive"
+                onClick={handleDeleteOrphanedArtifacts}
+                disabled={isLoading}
+              >
+                Delete Orphans
+              </Button>
+            </DialogFooter>
+          </DialogContent>
+        </Dialog>
+
+        <Dialog open={!!moveDialog} onOpenChange={() => setMoveDialog(null)}>
+          <DialogContent className="glass">
+            <DialogHeader>
+              <DialogTitle className="flex items-center gap-2 text-foreground">
+                <FolderInput className="h-5 w-5" />
+                Move {selectedSessions.size} Sessions
+              </DialogTitle>
+              <DialogDescription>
+                The sessions, their sub-agents and session data are copied into
+                the target project and checked; the originals stay in the trash
+                until you empty it.
+              </DialogDescription>
+            </DialogHeader>
+            <div className="space-y-3 text-sm">
+              <select
+                value={moveDialog?.targetPath ?? ""}
+                onChange={(e) => handleMoveTarget(e.target.value)}
+                className="glass-input w-full"
+              >
+                <option value="">Choose a project…</option>
+                {projects
+                  .filter((p) => p.path !== selectedProject?.path)
+                  .map((p) => (
+                    <option key={p.path} value={p.path}>
+                      {p.name}
+                    </option>
+                  ))}
+              </select>
+              <label className="block text-muted-foreground">
+                New working directory (leave empty to keep the recorded one)
+              </label>
+              <input
+                type="text"
+                value={moveDialog?.newCwd ?? ""}
+                onChange={(e) =>
+                  moveDialog &&
+                  setMoveDialog({ ...moveDialog, newCwd: e.target.value })
+                }
+                className="glass-input w-full"
+                placeholder="/path/to/project"
+              />
+            </div>
+            <DialogFooter>
+              <Button variant="glass" onClick={() => setMoveDialog(null)}>
+                Cancel
+              </Button>
+              <Button
+                variant="glass"
+                onClick={handleMoveSessions}
+                disabled={!moveDialog?.targetPath || isLoading}
+              >
+                Move
+              </Button>
+            </DialogFooter>
+          </DialogContent>
+        </Dialog>
       </div>
     </div>
   );
@@ -1,5 +1,28 @@
 import { invoke } from '@tauri-apps/api/core';
-import type { ProjectDto, SessionDto, StatisticsDto } from '../types';
+import type {
+  ActivityDto,
+  ArtifactDto,
+  BranchGroupDto,
+  ConversationTreeDto,
+  DoctorFindingDto,
+  ExportResultDto,
+  MoveResultDto,
+  ProjectDetailDto,
+  ProjectDto,
+  ProjectDuplicatesDto,
+  RedactionOutcomeDto,
+  RelocationReportDto,
+  RepairOutcomeDto,
+  ResumeCommandDto,
+  SessionChainDto,
+  SessionDto,
+  SessionIntegrityDto,
+  SessionSecretsDto,
+  StaleBranchReportDto,
+  StatisticsDto,
+  StoreRankingDto,
+  ToolReportDto,
+} from '../types';
 
 export const api = {
   async getStatistics(): Promise<StatisticsDto> {
@@ -18,6 +41,26 @@ export const api = {
     return await invoke('delete_sessions', { sessionPaths });
   },
 
+  async getProjectDetail(projectPath: string): Promise<ProjectDetailDto> {
+    return await invoke('get_project_detail', { projectPath });
+  },
+
+  async getBranchGroups(projectPath: string): Promise<BranchGroupDto[]> {
+    return await invoke('get_branch_groups', { projectPath });
+  },
+
+  async getSessionChains(projectPath: string): Promise<SessionChainDto[]> {
+    return await invoke('get_session_chains', { projectPath });
+  },
+
+  async exportSessions(
+    sessionPaths: string[],
+    format: 'md' | 'jsonl',
+    anonymize = false
+  ): Promise<ExportResultDto> {
+    return await invoke('export_sessions', { sessionPaths, format, anonymize });
+  },
+
   async deleteProject(projectPath: string): Promise<void> {
     return await invoke('delete_project', { projectPath });
   },
@@ -37,4 +80,103 @@ export const api = {
   async getSessionContent(sessionPath: string): Promise<string> {
     return await invoke('get_session_content', { sessionPath });
   },
+
+  async getConversationTree(sessionPath: string): Promise<ConversationTreeDto> {
+    return await invoke('get_conversation_tree', { sessionPath });
+  },
+
+  async diffSessions(leftPath: string, rightPath: string): Promise<SessionDiffDto> {
+    return await invoke('diff_sessions', { leftPath, rightPath });
+  },
+
+  async getResumeCommand(sessionPath: string): Promise<ResumeCommandDto> {
+    return await invoke('get_resume_command', { sessionPath });
+  },
+
+  async filterArtifactsByAge(category: string, days: number): Promise<ArtifactDto[]> {
+    return await invoke('filter_artifacts_by_age', { category, days });
+  },
+
+  async deleteArtifacts(category: string, artifactPaths: string[]): Promise<number> {
+    return await invoke('delete_artifacts', { category, artifactPaths });
+  },
+
+  async findOrphanedArtifacts(): Promise<ArtifactDto[]> {
+    return await invoke('find_orphaned_artifacts');
+  },
+
+  async deleteOrphanedArtifacts(): Promise<number> {
+    return await invoke('delete_orphaned_artifacts');
+  },
+
+  async findDuplicateSessions(): Promise<ProjectDuplicatesDto[]> {
+    return await invoke('find_duplicate_sessions');
+  },
+
+  async getActivity(): Promise<ActivityDto> {
+    return await invoke('get_activity');
+  },
+
+  async getStoreRanking(limit: number): Promise<StoreRankingDto> {
+    return await invoke('get_store_ranking', { limit });
+  },
+
+  async getToolUsage(limit: number): Promise<ToolReportDto> {
+    return await invoke('get_tool_usage', { limit });
+  },
+
+  async archiveSessions(sessionPaths: string[]): Promise<number> {
+    return await invoke('archive_sessions', { sessionPaths });
+  },
+
+  async moveSessions(
+    sessionPaths: string[],
+    targetProjectPath: string,
+    newCwd: string | null
+  ): Promise<MoveResultDto[]> {
+    return await invoke('move_sessions', { sessionPaths, targetProjectPath, newCwd });
+  },
+
+  async emptyTrash(trashPaths: string[]): Promise<string> {
+    return await invoke('empty_trash', { trashPaths });
+  },
+
+  async previewRelocation(oldCwd: string, newCwd: string): Promise<RelocationReportDto> {
+    return await invoke('preview_relocation', { oldCwd, newCwd });
+  },
+
+  async applyRelocation(preview: RelocationReportDto): Promise<RelocationReportDto> {
+    return await invoke('apply_relocation', { preview });
+  },
+
+  async findStaleBranchSessions(): Promise<StaleBranchReportDto> {
+    return await invoke('find_stale_branch_sessions');
+  },
+
+  async runDoctor(): Promise<DoctorFindingDto[]> {
+    return await invoke('run_doctor');
+  },
+
+  async checkIntegrity(): Promise<SessionIntegrityDto[]> {
+    return await invoke('check_integrity');
+  },
+
+  async repairSessions(sessionPaths: string[]): Promise<RepairOutcomeDto[]> {
+    return await invoke('repair_sessions', { sessionPaths });
+  },
+
+  async scanSecrets(extraPatterns: string[]): Promise<SessionSecretsDto[]> {
+    return await invoke('scan_secrets', { extraPatterns });
+  },
+
+  async redactSecrets(
+    sessionPaths: string[],
+    extraPatterns: string[]
+  ): Promise<RedactionOutcomeDto[]> {
+    return await invoke('redact_secrets', { sessionPaths, extraPatterns });
+  },
+
+  async deleteOldArtifacts(category: string, days: number): Promise<number> {
+    return await invoke('delete_old_artifacts', { category, days });
+  },
 };
@@ -4,6 +4,20 @@ export interface SessionDto {
   size: string;
   age_days: number;
   content_preview: string | null;
+  linked_size: string | null;
+  subagents: SessionDto[];
+  session_id: string | null;
+  cwd: string | null;
+  git_branch: string | null;
+  version: string | null;
+  first_timestamp: string | null;
+  last_timestamp: string | null;
+  user_messages: number;
+  assistant_messages: number;
+  tool_messages: number;
+  models: string[];
+  tool_calls: number;
+  tool_errors: number;
 }
 
 export interface ProjectDto {
@@ -18,4 +32,326 @@ export interface StatisticsDto {
   total_projects: number;
   total_sessions: number;
   total_size: string;
+  artifacts: ArtifactUsageDto[];
+  total_artifacts_size: string;
+  age_buckets: AgeBucketDto[];
+  oldest_session: ProjectSessionDto | null;
+  newest_session: ProjectSessionDto | null;
+  empty_projects: number;
+  average_session_size: string;
+  median_session_size: string;
+  largest_project: ProjectSummaryDto | null;
+}
+
+export interface AgeBucketDto {
+  label: string;
+  count: number;
+  size: string;
+}
+
+export interface ProjectSessionDto {
+  project_name: string;
+  session: SessionDto;
+}
+
+export interface ProjectSummaryDto {
+  name: string;
+  path: string;
+  session_count: number;
+  total_size: string;
+}
+
+export interface ArtifactUsageDto {
+  category: string;
+  label: string;
+  count: number;
+  size: string;
+}
+
+export interface ArtifactDto {
+  category: string;
+  name: string;
+  path: string;
+  size: string;
+  age_days: number;
+}
+
+export interface DuplicateSessionDto {
+  redundant: SessionDto;
+  kept: SessionDto;
+  kind: "identical" | "prefix" | "subset";
+  shared_entries: number;
+}
+
+export interface ProjectDuplicatesDto {
+  project_name: string;
+  project_path: string;
+  redundant_size: string;
+  duplicates: DuplicateSessionDto[];
+}
+
+export interface SessionChainDto {
+  sessions: SessionDto[];
+  total_size: string;
+  duration: string;
+  started: string | null;
+  ended: string | null;
+}
+
+export interface ConversationEntryDto {
+  uuid: string | null;
+  parent_uuid: string | null;
+  line_number: number;
+  role: string;
+  text: string;
+  is_sidechain: boolean;
+}
+
+export interface AbandonedBranchDto {
+  fork_line: number | null;
+  entries: ConversationEntryDto[];
+}
+
+export interface ConversationTreeDto {
+  total_entries: number;
+  branch_points: number;
+  active: ConversationEntryDto[];
+  abandoned_branches: AbandonedBranchDto[];
+}
+
+export interface MoveOutcomeDto {
+  path: string;
+  target_path: string;
+  trash_path: string;
+  rewritten_cwd: number;
+}
+
+export interface MoveResultDto {
+  path: string;
+  name: string;
+  outcome: MoveOutcomeDto | null;
+  error: string | null;
+}
+
+export interface StaleBranchSessionDto {
+  project_name: string;
+  branch: string;
+  repository: string;
+  session: SessionDto;
+}
+
+export interface StaleBranchReportDto {
+  sessions: StaleBranchSessionDto[];
+  repositories: number;
+  unchecked: number;
+  total_size: string;
+}
+
+export interface BranchGroupDto {
+  branch: string | null;
+  session_paths: string[];
+  size: string;
+  last_activity: string;
+}
+
+export interface ResumeCommandDto {
+  session_id: string;
+  cwd: string;
+  program: string;
+  shell_line: string;
+}
+
+export interface RelocationReportDto {
+  old_cwd: string;
+  new_cwd: string;
+  source: string;
+  target: string;
+  merge: boolean;
+  sessions: number;
+  transcripts: number;
+  rewritten_entries: number;
+  conflicts: string[];
+  fingerprint: string;
+  dry_run: boolean;
+}
+
+export interface DiffMessageDto {
+  uuid: string | null;
+  line_number: number;
+  role: string;
+  text: string;
+  timestamp: string | null;
+}
+
+export interface DiffRowDto {
+  kind: "shared" | "left" | "right";
+  matched_by: "uuid" | "content" | null;
+  left: DiffMessageDto | null;
+  right: DiffMessageDto | null;
+}
+
+export interface SessionDiffDto {
+  shared_prefix: number;
+  divergence_row: number | null;
+  left_divergence_line: number | null;
+  right_divergence_line: number | null;
+  shared_count: number;
+  left_only_count: number;
+  right_only_count: number;
+  rows: DiffRowDto[];
+}
+
+export interface IntegrityIssueDto {
+  kind: "invalid_line" | "truncated_tail" | "duplicate_uuid" | "broken_parent";
+  description: string;
+  repairable: boolean;
+}
+
+export interface SessionIntegrityDto {
+  project_name: string;
+  session: SessionDto;
+  valid_entries: number;
+  repairable: boolean;
+  issues: IntegrityIssueDto[];
+}
+
+export interface RepairOutcomeDto {
+  path: string;
+  backup_path: string | null;
+  removed_lines: number;
+}
+
+export interface ReplacementDto {
+  placeholder: string;
+  category: string;
+  original: string;
+  occurrences: number;
+}
+
+export interface ExportResultDto {
+  path: string;
+  replacements: ReplacementDto[];
+}
+
+export interface SecretFindingDto {
+  line: number;
+  column: number;
+  entry_uuid: string | null;
+  entry_type: string;
+  detector: string;
+  masked: string;
+}
+
+export interface SessionSecretsDto {
+  project_name: string;
+  session: SessionDto;
+  findings: SecretFindingDto[];
+}
+
+export interface RedactionOutcomeDto {
+  path: string;
+  backup_path: string | null;
+  redacted: number;
+}
+
+export interface DoctorFindingDto {
+  severity: "info" | "warning" | "error";
+  check: "root" | "projects" | "permissions" | "symlinks" | "disk";
+  message: string;
+  path: string | null;
+  suggestion: string;
+}
+
+export interface DayActivityDto {
+  date: string;
+  sessions: number;
+  messages: number;
+}
+
+export interface HourlyActivityDto {
+  weekday: number;
+  hour: number;
+  messages: number;
+}
+
+export interface ActivityDto {
+  days: DayActivityDto[];
+  hourly: HourlyActivityDto[];
+  total_messages: number;
+  active_days: number;
+}
+
+export interface TokenUsageDto {
+  input: number;
+  output: number;
+  cache_creation: number;
+  cache_read: number;
+}
+
+export interface NamedCountDto {
+  name: string;
+  count: number;
+}
+
+export interface ProjectDetailDto {
+  project: ProjectSummaryDto;
+  cwd: string | null;
+  first_activity: string | null;
+  last_activity: string | null;
+  average_session_size: string;
+  message_count: number;
+  tokens: TokenUsageDto;
+  top_tools: NamedCountDto[];
+  git_branches: NamedCountDto[];
+  largest_sessions: SessionDto[];
+}
+
+export interface RankedSessionDto {
+  project_name: string;
+  session: SessionDto;
+  total_size: string;
+  project_median: string;
+  median_ratio: number | null;
+  is_outlier: boolean;
+  growth_per_day: string | null;
+}
+
+export interface StoreRankingDto {
+  largest_sessions: RankedSessionDto[];
+  fastest_growing: RankedSessionDto[];
+  largest_projects: ProjectSummaryDto[];
+}
+
+export interface ToolCountsDto {
+  name: string;
+  calls: number;
+  errors: number;
+  error_rate: number;
+}
+
+export interface ToolUsageDto {
+  calls: number;
+  errors: number;
+  error_rate: number;
+  tools: ToolCountsDto[];
+  mcp_servers: ToolCountsDto[];
+}
+
+export interface ProjectToolUsageDto {
+  project_name: string;
+  project_path: string;
+  usage: ToolUsageDto;
+}
+
+export interface SessionToolUsageDto {
+  project_name: string;
+  session: SessionDto;
+  usage: ToolUsageDto;
+}
+
+export interface ToolReportDto {
+  overall: ToolUsageDto;
+  projects: ProjectToolUsageDto[];
+  sessions: SessionToolUsageDto[];
+  failing_sessions: SessionToolUsageDto[];
 }